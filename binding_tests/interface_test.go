@@ -263,7 +263,7 @@ func TestSignWithdraw(t *testing.T) {
 	// test signer
 	signer, err := sdk.NewSigner(s, sdk.L1SignerTypeEth{})
 	assert.Nil(t, err)
-	_, err = signer.SignWithdraw(tx, l2SourceTokenSymbol, nil, nil)
+	_, err = signer.SignWithdraw(tx, l2SourceTokenSymbol)
 	assert.Nil(t, err)
 }
 
@@ -3,7 +3,9 @@ mod network;
 mod type_convert;
 
 use crate::crypto::{get_public_key_hash, verify_musig};
-use crate::network::{zklink_main_net_url, zklink_test_net_url};
+use crate::network::{
+    zklink_main_net_url, zklink_network_url, zklink_network_ws_url, zklink_test_net_url, Network,
+};
 
 use zklink_sdk_signers::eth_signer::error::EthSignerError;
 use zklink_sdk_signers::eth_signer::packed_eth_signature::PackedEthSignature;
@@ -1,11 +1,53 @@
-use zklink_sdk_provider::network::Network;
+use zklink_sdk_provider::network::{CustomNetwork, Network as ProviderNetwork};
+use zklink_sdk_types::basic_types::ChainId;
+
+/// The `Network` of the bindings, the custom variant has named fields for uniffi.
+pub enum Network {
+    MainNet,
+    TestNet,
+    DevNet,
+    Custom {
+        url: String,
+        ws_url: Option<String>,
+        chain_id: Option<ChainId>,
+    },
+}
+
+impl From<Network> for ProviderNetwork {
+    fn from(network: Network) -> Self {
+        match network {
+            Network::MainNet => ProviderNetwork::MainNet,
+            Network::TestNet => ProviderNetwork::TestNet,
+            Network::DevNet => ProviderNetwork::DevNet,
+            Network::Custom {
+                url,
+                ws_url,
+                chain_id,
+            } => ProviderNetwork::new_custom(CustomNetwork {
+                url,
+                ws_url,
+                chain_id,
+            }),
+        }
+    }
+}
 
 pub fn zklink_main_net_url() -> String {
-    let network = Network::MainNet;
+    let network = ProviderNetwork::MainNet;
     network.url().into()
 }
 
 pub fn zklink_test_net_url() -> String {
-    let network = Network::TestNet;
+    let network = ProviderNetwork::TestNet;
     network.url().into()
 }
+
+pub fn zklink_network_url(network: Network) -> String {
+    ProviderNetwork::from(network).url().into()
+}
+
+pub fn zklink_network_ws_url(network: Network) -> Option<String> {
+    ProviderNetwork::from(network)
+        .ws_url()
+        .map(|url| url.into())
+}
@@ -0,0 +1,14 @@
+//! Generate the TypeScript definitions of the txs and the rpc responses as a
+//! `typescript_custom_section`, the results of `RpcClient` are typed by them.
+use std::path::PathBuf;
+use zklink_sdk_provider::schema::typescript_definitions;
+
+fn main() {
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    let section = format!(
+        "#[wasm_bindgen(typescript_custom_section)]\nconst RPC_TYPES: &'static str = r###\"\n{}\"###;\n",
+        typescript_definitions()
+    );
+    std::fs::write(out_dir.join("rpc_types.rs"), section).unwrap();
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -2,6 +2,7 @@
 #[cfg(feature = "web")]
 pub mod json_rpc_signer;
 pub mod rpc_client;
+pub mod rpc_response;
 pub mod rpc_type_converter;
 #[cfg(not(feature = "web"))]
 pub mod signer;
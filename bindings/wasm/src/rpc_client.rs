@@ -1,3 +1,9 @@
+use crate::rpc_response::{
+    AccountBalancesResp, AccountInfoResp, AccountOrderSlotsResp, AccountSnapshotResp,
+    BlockNumberResp, BlockOnChainResp, BlockResp, ChainsResp, ForwardTxsResp, PendingBlockResp,
+    TokenReserveResp, TokensResp, TxHashResp, TxHistoryResp, TxResp, WebSocketEventsResp,
+    WithdrawTxsResp,
+};
 use crate::rpc_type_converter::{
     AccountQuery, TxLayer1Signature, TxOracleSignature, TxZkLinkSignature,
 };
@@ -5,24 +11,29 @@ use getrandom::getrandom;
 use jsonrpsee::core::params::ArrayParams;
 use jsonrpsee::core::traits::ToRpcParams;
 use jsonrpsee::types::request::Request;
+use jsonrpsee::types::ErrorObjectOwned;
 use jsonrpsee::types::Id;
+use serde::Serialize;
+use serde_wasm_bindgen::Serializer;
 use std::convert::TryFrom;
 use std::str::FromStr;
 use wasm_bindgen::prelude::wasm_bindgen;
-use wasm_bindgen::JsValue;
+use wasm_bindgen::{JsCast, JsValue};
 use zklink_sdk_provider::error::RpcError;
-use zklink_sdk_provider::network::Network;
+use zklink_sdk_provider::network::{CustomNetwork, Network};
 use zklink_sdk_provider::response::{AccountQuery as RpcAccountQuery, OracleSignature};
 use zklink_sdk_provider::web_socket::ws_message::message::request::ClientOffset;
 use zklink_sdk_provider::web_socket::ws_message::topic::Topic;
 use zklink_sdk_signers::zklink_signer::ZkLinkSignature;
 use zklink_sdk_types::basic_types::tx_hash::TxHash;
-use zklink_sdk_types::basic_types::{AccountId, BlockNumber, SubAccountId, TokenId};
+use zklink_sdk_types::basic_types::{AccountId, BlockNumber, ChainId, SubAccountId, TokenId};
 use zklink_sdk_types::prelude::ZkLinkAddress;
 use zklink_sdk_types::signatures::TxLayer1Signature as TypesTxLayer1Signature;
 use zklink_sdk_types::tx_type::zklink_tx::ZkLinkTx;
 use zklink_sdk_types::tx_type::zklink_tx::ZkLinkTxType;
 
+/// Send the request and return the `result` of the response as a JS object typed by `$resp_type`,
+/// one of the types of `rpc_response`.
 macro_rules! rpc_request {
     ($method:expr,$builder:expr, $server_url:expr, $resp_type: ty) => {{
         let params = $builder
@@ -42,7 +53,16 @@ macro_rules! rpc_request {
             .json::<serde_json::Value>()
             .await
             .map_err(RpcError::ResponseError)?;
-        Ok(serde_wasm_bindgen::to_value(&res.to_string())?)
+        if let Some(error) = res.get("error") {
+            let error: ErrorObjectOwned = serde_json::from_value(error.clone())
+                .map_err(|e| RpcError::ParseJsonError(e.to_string()))?;
+            return Err(RpcError::NodeError(error.into()).into());
+        }
+        let result = res
+            .get("result")
+            .unwrap_or(&serde_json::Value::Null)
+            .serialize(&Serializer::json_compatible())?;
+        Ok(result.unchecked_into::<$resp_type>())
     }};
 }
 
@@ -60,25 +80,51 @@ pub fn uuid_str() -> String {
 
 #[wasm_bindgen]
 pub struct RpcClient {
+    network: Network,
     server_url: String,
 }
 
 #[wasm_bindgen]
 impl RpcClient {
+    /// The `custom_url`, `custom_ws_url` and `custom_chain_id` are used if the `network` is not
+    /// one of "mainnet", "testnet" and "devnet".
     #[wasm_bindgen(constructor)]
-    pub fn new(network: &str, custom_url: Option<String>) -> Result<RpcClient, JsValue> {
-        let server_url = if let Ok(network) = Network::from_str(network) {
-            network.url().to_owned()
-        } else {
-            custom_url.ok_or(RpcError::InvalidNetwork)?
+    pub fn new(
+        network: &str,
+        custom_url: Option<String>,
+        custom_ws_url: Option<String>,
+        custom_chain_id: Option<u8>,
+    ) -> Result<RpcClient, JsValue> {
+        let network = match Network::from_str(network) {
+            Ok(network) => network,
+            Err(_) => Network::new_custom(CustomNetwork {
+                url: custom_url.ok_or(RpcError::InvalidNetwork)?,
+                ws_url: custom_ws_url,
+                chain_id: custom_chain_id.map(ChainId),
+            }),
         };
-        Ok(RpcClient { server_url })
+        Ok(RpcClient {
+            server_url: network.url().to_owned(),
+            network,
+        })
+    }
+
+    /// The WebSocket endpoint of the network
+    #[wasm_bindgen(getter, js_name=wsUrl)]
+    pub fn ws_url(&self) -> Option<String> {
+        self.network.ws_url().map(|url| url.to_owned())
+    }
+
+    /// The zklink chain id of the custom network
+    #[wasm_bindgen(getter, js_name=chainId)]
+    pub fn chain_id(&self) -> Option<u8> {
+        self.network.chain_id().map(|chain_id| *chain_id)
     }
 
     #[wasm_bindgen(js_name=getSupportTokens)]
-    pub async fn tokens(&self) -> Result<JsValue, JsValue> {
+    pub async fn tokens(&self) -> Result<TokensResp, JsValue> {
         let builder = ArrayParams::new();
-        rpc_request!("getSupportTokens",builder,&self.server_url,HashMap<TokenId, TokenResp>)
+        rpc_request!("getSupportTokens", builder, &self.server_url, TokensResp)
     }
 
     #[wasm_bindgen(js_name=getAccountSnapshot)]
@@ -87,7 +133,7 @@ impl RpcClient {
         account_query: AccountQuery,
         sub_account_id: Option<u8>,
         block_number: Option<u32>,
-    ) -> Result<JsValue, JsValue> {
+    ) -> Result<AccountSnapshotResp, JsValue> {
         let mut builder = ArrayParams::new();
         let _ = builder.insert(RpcAccountQuery::from(account_query));
         let _ = builder.insert(sub_account_id.map(|id| SubAccountId(id)));
@@ -107,7 +153,7 @@ impl RpcClient {
         l1_signature: Option<TxLayer1Signature>,
         l2_signature: Option<TxZkLinkSignature>,
         oracle_signature: Option<TxOracleSignature>,
-    ) -> Result<JsValue, JsValue> {
+    ) -> Result<TxHashResp, JsValue> {
         let mut builder = ArrayParams::new();
         let zklink_tx: ZkLinkTx =
             serde_wasm_bindgen::from_value(tx).map_err(|_e| RpcError::InvalidInputParameter)?;
@@ -125,22 +171,17 @@ impl RpcClient {
         let _ = builder.insert(l1_signature);
         let _ = builder.insert(l2_signature.map(|s| ZkLinkSignature::from(s)));
         let _ = builder.insert(oracle_signature);
-        rpc_request!("sendTransaction", builder, &self.server_url, TxHash)
+        rpc_request!("sendTransaction", builder, &self.server_url, TxHashResp)
     }
 
     #[wasm_bindgen(js_name=getSupportChains)]
-    pub async fn get_support_chains(&self) -> Result<JsValue, JsValue> {
+    pub async fn get_support_chains(&self) -> Result<ChainsResp, JsValue> {
         let builder = ArrayParams::new();
-        rpc_request!(
-            "getSupportChains",
-            builder,
-            &self.server_url,
-            Vec<ChainResp>
-        )
+        rpc_request!("getSupportChains", builder, &self.server_url, ChainsResp)
     }
 
     #[wasm_bindgen(js_name=getLatestBlockNumber)]
-    pub async fn block_info(&self) -> Result<JsValue, JsValue> {
+    pub async fn block_info(&self) -> Result<BlockNumberResp, JsValue> {
         let builder = ArrayParams::new();
         rpc_request!(
             "getLatestBlockNumber",
@@ -156,7 +197,7 @@ impl RpcClient {
         block_number: Option<u32>,
         include_tx: bool,
         include_update: bool,
-    ) -> Result<JsValue, JsValue> {
+    ) -> Result<BlockResp, JsValue> {
         let mut builder = ArrayParams::new();
         let _ = builder.insert(block_number.map(|b| BlockNumber(b)));
         let _ = builder.insert(include_tx);
@@ -171,7 +212,7 @@ impl RpcClient {
         include_tx: bool,
         include_update: bool,
         limit: Option<usize>,
-    ) -> Result<JsValue, JsValue> {
+    ) -> Result<PendingBlockResp, JsValue> {
         let mut builder = ArrayParams::new();
         let _ = builder.insert(last_tx_timestamp_micro);
         let _ = builder.insert(include_tx);
@@ -181,12 +222,15 @@ impl RpcClient {
             "getPendingBlock",
             builder,
             &self.server_url,
-            Vec<TxHashOrDetailResp>
+            PendingBlockResp
         )
     }
 
     #[wasm_bindgen(js_name=getBlockOnChainByNumber)]
-    pub async fn block_onchain_detail(&self, block_number: u32) -> Result<JsValue, JsValue> {
+    pub async fn block_onchain_detail(
+        &self,
+        block_number: u32,
+    ) -> Result<BlockOnChainResp, JsValue> {
         let mut builder = ArrayParams::new();
         let _ = builder.insert(BlockNumber(block_number));
         rpc_request!(
@@ -198,7 +242,10 @@ impl RpcClient {
     }
 
     #[wasm_bindgen(js_name=getAccount)]
-    pub async fn account_info(&self, account_query: AccountQuery) -> Result<JsValue, JsValue> {
+    pub async fn account_info(
+        &self,
+        account_query: AccountQuery,
+    ) -> Result<AccountInfoResp, JsValue> {
         let mut builder = ArrayParams::new();
         let _ = builder.insert(RpcAccountQuery::from(account_query));
         rpc_request!("getAccount", builder, &self.server_url, AccountInfoResp)
@@ -209,7 +256,7 @@ impl RpcClient {
         &self,
         account_id: u32,
         sub_account_id: Option<u8>,
-    ) -> Result<JsValue, JsValue> {
+    ) -> Result<AccountBalancesResp, JsValue> {
         let mut builder = ArrayParams::new();
         let _ = builder.insert(AccountId(account_id));
         let _ = builder.insert(sub_account_id.map(|id| SubAccountId(id)));
@@ -217,7 +264,7 @@ impl RpcClient {
             "getAccountBalances",
             builder,
             &self.server_url,
-            SubAccountBalances
+            AccountBalancesResp
         )
     }
 
@@ -226,7 +273,7 @@ impl RpcClient {
         &self,
         account_id: u32,
         sub_account_id: Option<u8>,
-    ) -> Result<JsValue, JsValue> {
+    ) -> Result<AccountOrderSlotsResp, JsValue> {
         let mut builder = ArrayParams::new();
         let _ = builder.insert(AccountId(account_id));
         let _ = builder.insert(sub_account_id.map(|id| SubAccountId(id)));
@@ -234,20 +281,29 @@ impl RpcClient {
             "getAccountOrderSlots",
             builder,
             &self.server_url,
-            SubAccountOrders
+            AccountOrderSlotsResp
         )
     }
 
     #[wasm_bindgen(js_name=getTokenReserve)]
-    pub async fn token_remain(&self, token_id: u32, mapping: bool) -> Result<JsValue, JsValue> {
+    pub async fn token_remain(
+        &self,
+        token_id: u32,
+        mapping: bool,
+    ) -> Result<TokenReserveResp, JsValue> {
         let mut builder = ArrayParams::new();
         let _ = builder.insert(TokenId(token_id));
         let _ = builder.insert(mapping);
-        rpc_request!("getTokenReserve",builder,&self.server_url,HashMap<ChainId, BigUintSerdeWrapper>)
+        rpc_request!(
+            "getTokenReserve",
+            builder,
+            &self.server_url,
+            TokenReserveResp
+        )
     }
 
     #[wasm_bindgen(js_name=getTransactionByHash)]
-    pub async fn tx_info(&self, hash: String, include_update: bool) -> Result<JsValue, JsValue> {
+    pub async fn tx_info(&self, hash: String, include_update: bool) -> Result<TxResp, JsValue> {
         let hash = TxHash::from_hex(&hash).map_err(|_e| RpcError::InvalidInputParameter)?;
         let mut builder = ArrayParams::new();
         let _ = builder.insert(hash);
@@ -262,7 +318,7 @@ impl RpcClient {
         address: String,
         page_index: u64,
         page_size: u32,
-    ) -> Result<JsValue, JsValue> {
+    ) -> Result<TxHistoryResp, JsValue> {
         let address =
             ZkLinkAddress::from_hex(&address).map_err(|_e| RpcError::InvalidInputParameter)?;
         let mut builder = ArrayParams::new();
@@ -274,7 +330,7 @@ impl RpcClient {
             "getAccountTransactionHistory",
             builder,
             &self.server_url,
-            Page<ZkLinkTxHistory>
+            TxHistoryResp
         )
     }
 
@@ -283,7 +339,7 @@ impl RpcClient {
         &self,
         last_tx_timestamp: u64,
         max_txs: u32,
-    ) -> Result<JsValue, JsValue> {
+    ) -> Result<WithdrawTxsResp, JsValue> {
         let mut builder = ArrayParams::new();
         let _ = builder.insert(last_tx_timestamp);
         let _ = builder.insert(max_txs);
@@ -291,7 +347,7 @@ impl RpcClient {
             "getFastWithdrawTxs",
             builder,
             &self.server_url,
-            Vec<FastWithdrawTxResp>
+            WithdrawTxsResp
         )
     }
 
@@ -301,17 +357,12 @@ impl RpcClient {
         sub_account_id: u8,
         offset_id: i64,
         limit: i64,
-    ) -> Result<JsValue, JsValue> {
+    ) -> Result<ForwardTxsResp, JsValue> {
         let mut builder = ArrayParams::new();
         let _ = builder.insert(SubAccountId(sub_account_id));
         let _ = builder.insert(offset_id);
         let _ = builder.insert(limit);
-        rpc_request!(
-            "pullForwardTxs",
-            builder,
-            &self.server_url,
-            Vec<ForwardTxResp>
-        )
+        rpc_request!("pullForwardTxs", builder, &self.server_url, ForwardTxsResp)
     }
 
     #[wasm_bindgen(js_name=getWebSocketEvents)]
@@ -320,7 +371,7 @@ impl RpcClient {
         topic: String,
         from_topic_index_included: f64,
         limit: Option<usize>,
-    ) -> Result<JsValue, JsValue> {
+    ) -> Result<WebSocketEventsResp, JsValue> {
         let topic = Topic::from_str(&topic).map_err(|_e| RpcError::InvalidInputParameter)?;
         let client_offset = ClientOffset {
             from_topic_index_included: from_topic_index_included as i64,
@@ -329,6 +380,11 @@ impl RpcClient {
         let mut builder = ArrayParams::new();
         let _ = builder.insert(topic);
         let _ = builder.insert(client_offset);
-        rpc_request!("getWebSocketEvents", builder, &self.server_url, bool)
+        rpc_request!(
+            "getWebSocketEvents",
+            builder,
+            &self.server_url,
+            WebSocketEventsResp
+        )
     }
 }
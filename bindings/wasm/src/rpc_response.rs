@@ -0,0 +1,43 @@
+//! The TypeScript types of the results of `RpcClient`, they refer to the definitions generated
+//! from the JSON Schemas of the provider by the build script.
+use wasm_bindgen::prelude::wasm_bindgen;
+
+include!(concat!(env!("OUT_DIR"), "/rpc_types.rs"));
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "Record<string, TokenResp>")]
+    pub type TokensResp;
+    #[wasm_bindgen(typescript_type = "AccountSnapshotResp")]
+    pub type AccountSnapshotResp;
+    #[wasm_bindgen(typescript_type = "TxHash")]
+    pub type TxHashResp;
+    #[wasm_bindgen(typescript_type = "ChainResp[]")]
+    pub type ChainsResp;
+    #[wasm_bindgen(typescript_type = "BlockNumberResp")]
+    pub type BlockNumberResp;
+    #[wasm_bindgen(typescript_type = "BlockResp")]
+    pub type BlockResp;
+    #[wasm_bindgen(typescript_type = "TxHashOrDetailResp[]")]
+    pub type PendingBlockResp;
+    #[wasm_bindgen(typescript_type = "BlockOnChainResp")]
+    pub type BlockOnChainResp;
+    #[wasm_bindgen(typescript_type = "AccountInfoResp")]
+    pub type AccountInfoResp;
+    #[wasm_bindgen(typescript_type = "Record<string, Record<string, BigIntSerdeWrapper>>")]
+    pub type AccountBalancesResp;
+    #[wasm_bindgen(typescript_type = "Record<string, Record<string, ResponseTidyOrder>>")]
+    pub type AccountOrderSlotsResp;
+    #[wasm_bindgen(typescript_type = "Record<string, BigUintSerdeWrapper>")]
+    pub type TokenReserveResp;
+    #[wasm_bindgen(typescript_type = "TxResp")]
+    pub type TxResp;
+    #[wasm_bindgen(typescript_type = "Page_for_ZkLinkTxHistory")]
+    pub type TxHistoryResp;
+    #[wasm_bindgen(typescript_type = "WithdrawTxResp[]")]
+    pub type WithdrawTxsResp;
+    #[wasm_bindgen(typescript_type = "ForwardTxResp[]")]
+    pub type ForwardTxsResp;
+    #[wasm_bindgen(typescript_type = "Event_for_Topic_and_ServerEvent[]")]
+    pub type WebSocketEventsResp;
+}
@@ -77,7 +77,7 @@ impl Signer {
     }
 
     #[wasm_bindgen(js_name=signChangePubkeyWithEthEcdsaAuth)]
-    pub fn sign_change_pubkey_with_eth_ecdsa_auth(
+    pub async fn sign_change_pubkey_with_eth_ecdsa_auth(
         &self,
         tx: ChangePubKey,
     ) -> Result<JsValue, JsValue> {
@@ -85,7 +85,8 @@ impl Signer {
         let change_pubkey: TxChangePubKey = serde_wasm_bindgen::from_value(inner_tx)?;
         let signature = self
             .inner
-            .sign_change_pubkey_with_eth_ecdsa_auth(change_pubkey)?;
+            .sign_change_pubkey_with_eth_ecdsa_auth(change_pubkey)
+            .await?;
         Ok(serde_wasm_bindgen::to_value(&signature)?)
     }
 
@@ -106,18 +107,14 @@ impl Signer {
     }
 
     #[wasm_bindgen(js_name=signTransfer)]
-    pub fn sign_transfer(
+    pub async fn sign_transfer(
         &self,
         tx: Transfer,
         token_symbol: &str,
-        chain_id: Option<String>,
-        addr: Option<String>,
     ) -> Result<JsValue, JsValue> {
         let inner_tx = tx.json_value()?;
         let transfer: TxTransfer = serde_wasm_bindgen::from_value(inner_tx)?;
-        let signature = self
-            .inner
-            .sign_transfer(transfer, token_symbol, chain_id, addr)?;
+        let signature = self.inner.sign_transfer(transfer, token_symbol).await?;
         Ok(serde_wasm_bindgen::to_value(&signature)?)
     }
 
@@ -138,18 +135,14 @@ impl Signer {
     }
 
     #[wasm_bindgen(js_name=signWithdraw)]
-    pub fn sign_withdraw(
+    pub async fn sign_withdraw(
         &self,
         tx: Withdraw,
         token_symbol: &str,
-        chain_id: Option<String>,
-        addr: Option<String>,
     ) -> Result<JsValue, JsValue> {
         let inner_tx = tx.json_value()?;
         let withdraw: TxWithdraw = serde_wasm_bindgen::from_value(inner_tx)?;
-        let signature = self
-            .inner
-            .sign_withdraw(withdraw, token_symbol, chain_id, addr)?;
+        let signature = self.inner.sign_withdraw(withdraw, token_symbol).await?;
         Ok(serde_wasm_bindgen::to_value(&signature)?)
     }
 
@@ -1,9 +1,12 @@
+use serde::Serialize;
+use serde_wasm_bindgen::Serializer;
 use std::str::FromStr;
 use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen::JsValue;
 use zklink_sdk_types::basic_types::BigUint;
-use zklink_sdk_types::prelude::{PubKeyHash, ZkLinkAddress, H256};
-use zklink_sdk_wallet::eth::EthTxOption as InnerEthTxOption;
+use zklink_sdk_types::prelude::{PubKeyHash, ZkLinkAddress, H256, U256};
+use zklink_sdk_wallet::error::WalletError;
+use zklink_sdk_wallet::eth::{EthTxOption as InnerEthTxOption, ExodusProof, FeeStrategy};
 use zklink_sdk_wallet::wallet::{WaitForTxStatus, Wallet as InnerWallet};
 
 #[wasm_bindgen]
@@ -37,6 +40,7 @@ pub struct EthTxOption {
 #[wasm_bindgen]
 impl EthTxOption {
     #[wasm_bindgen(constructor)]
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         is_support_eip1559: bool,
         to: String,
@@ -44,6 +48,8 @@ impl EthTxOption {
         value: Option<String>,
         gas: Option<f64>,
         gas_price: Option<String>,
+        max_fee_per_gas: Option<String>,
+        max_priority_fee_per_gas: Option<String>,
     ) -> Result<EthTxOption, JsValue> {
         let value = if let Some(v) = value {
             Some(
@@ -62,6 +68,23 @@ impl EthTxOption {
         } else {
             None
         };
+        let max_fee_per_gas = if let Some(m) = max_fee_per_gas {
+            Some(
+                BigUint::from_str(&m)
+                    .map_err(|error| JsValue::from_str(&format!("error: {error}")))?,
+            )
+        } else {
+            None
+        };
+
+        let max_priority_fee_per_gas = if let Some(m) = max_priority_fee_per_gas {
+            Some(
+                BigUint::from_str(&m)
+                    .map_err(|error| JsValue::from_str(&format!("error: {error}")))?,
+            )
+        } else {
+            None
+        };
         let inner = InnerEthTxOption {
             is_support_eip1559,
             to: ZkLinkAddress::from_str(&to)
@@ -70,6 +93,8 @@ impl EthTxOption {
             value,
             gas: gas.map(|g| g as u64),
             gas_price,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
         };
         Ok(EthTxOption { inner })
     }
@@ -79,6 +104,22 @@ impl EthTxOption {
     }
 }
 
+/// How to fill the fees of the txs, see `Wallet.setFeeStrategy`.
+#[wasm_bindgen]
+pub enum FeeStrategyType {
+    Legacy,
+    Eip1559,
+    Fixed,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WaitForTxReceipt {
+    status: String,
+    gas_used: Option<String>,
+    receipt: serde_json::Value,
+}
+
 #[wasm_bindgen]
 pub struct Wallet {
     inner: InnerWallet,
@@ -88,7 +129,7 @@ pub struct Wallet {
 impl Wallet {
     #[wasm_bindgen(constructor)]
     pub fn new(url: &str, private_key: &str) -> Result<Wallet, JsValue> {
-        let inner = InnerWallet::new(url, private_key);
+        let inner = InnerWallet::new(url, private_key)?;
         Ok(Wallet { inner })
     }
 
@@ -129,8 +170,71 @@ impl Wallet {
     ) -> Result<WaitForTxStatus, JsValue> {
         let tx_hash = H256::from_str(&tx_hash)
             .map_err(|error| JsValue::from_str(&format!("error: {error}")))?;
-        let status = self.inner.wait_for_transaction(tx_hash, timeout).await?;
-        Ok(status)
+        let result = self.inner.wait_for_transaction(tx_hash, timeout).await?;
+        Ok(result.status)
+    }
+
+    /// Wait for the tx like `waitForTransaction`, return `{ status, gasUsed, receipt }`, the
+    /// `receipt` is the JSON of `eth_getTransactionReceipt`, `gasUsed` and `receipt` are null if
+    /// the tx is still pending.
+    #[wasm_bindgen(js_name=waitForTransactionReceipt)]
+    pub async fn wait_for_transaction_receipt(
+        &self,
+        tx_hash: String,
+        timeout: Option<u32>,
+    ) -> Result<JsValue, JsValue> {
+        let tx_hash = H256::from_str(&tx_hash)
+            .map_err(|error| JsValue::from_str(&format!("error: {error}")))?;
+        let result = self.inner.wait_for_transaction(tx_hash, timeout).await?;
+        let receipt = WaitForTxReceipt {
+            status: format!("{:?}", result.status),
+            gas_used: result.gas_used().map(|gas_used| gas_used.to_string()),
+            receipt: serde_json::to_value(&result.receipt)
+                .map_err(|error| JsValue::from_str(&format!("error: {error}")))?,
+        };
+        Ok(receipt.serialize(&Serializer::json_compatible())?)
+    }
+
+    /// Set how to fill the fees of the txs if they are not set in `EthTxOption`, the fees are
+    /// required by `FeeStrategyType.Fixed`.
+    #[wasm_bindgen(js_name=setFeeStrategy)]
+    pub fn set_fee_strategy(
+        &mut self,
+        strategy: FeeStrategyType,
+        max_fee_per_gas: Option<String>,
+        max_priority_fee_per_gas: Option<String>,
+    ) -> Result<(), JsValue> {
+        self.inner.fee_strategy = match strategy {
+            FeeStrategyType::Legacy => FeeStrategy::Legacy,
+            FeeStrategyType::Eip1559 => FeeStrategy::Eip1559,
+            FeeStrategyType::Fixed => {
+                let parse = |fee: Option<String>| {
+                    fee.and_then(|fee| U256::from_dec_str(&fee).ok())
+                        .ok_or(WalletError::InvalidInputParameter)
+                };
+                FeeStrategy::Fixed {
+                    max_fee_per_gas: parse(max_fee_per_gas)?,
+                    max_priority_fee_per_gas: parse(max_priority_fee_per_gas)?,
+                }
+            }
+        };
+        Ok(())
+    }
+
+    #[wasm_bindgen(js_name=speedUpTransaction)]
+    pub async fn speed_up_transaction(&self, tx_hash: String) -> Result<String, JsValue> {
+        let tx_hash = H256::from_str(&tx_hash)
+            .map_err(|error| JsValue::from_str(&format!("error: {error}")))?;
+        let tx_hash = self.inner.speed_up_transaction(tx_hash).await?;
+        Ok(hex::encode(tx_hash.as_bytes()))
+    }
+
+    #[wasm_bindgen(js_name=cancelTransaction)]
+    pub async fn cancel_transaction(&self, tx_hash: String) -> Result<String, JsValue> {
+        let tx_hash = H256::from_str(&tx_hash)
+            .map_err(|error| JsValue::from_str(&format!("error: {error}")))?;
+        let tx_hash = self.inner.cancel_transaction(tx_hash).await?;
+        Ok(hex::encode(tx_hash.as_bytes()))
     }
 
     #[wasm_bindgen(js_name=approveERC20)]
@@ -257,4 +361,115 @@ impl Wallet {
             .await?;
         Ok(hex::encode(tx_hash.as_bytes()))
     }
+
+    #[wasm_bindgen(js_name=getPendingBalance)]
+    pub async fn get_pending_balance(
+        &self,
+        address: String,
+        token_id: u16,
+        eth_params: EthTxOption,
+    ) -> Result<String, JsValue> {
+        let address = ZkLinkAddress::from_str(&address)
+            .map_err(|error| JsValue::from_str(&format!("error: {error}")))?;
+        let eth_params: InnerEthTxOption =
+            serde_wasm_bindgen::from_value(eth_params.json_value().unwrap()).unwrap();
+        let balance = self
+            .inner
+            .get_pending_balance(address, token_id, eth_params)
+            .await?;
+        Ok(balance.to_string())
+    }
+
+    #[wasm_bindgen(js_name=withdrawPendingBalance)]
+    pub async fn withdraw_pending_balance(
+        &self,
+        owner: String,
+        token_id: u16,
+        amount: String,
+        eth_params: EthTxOption,
+    ) -> Result<String, JsValue> {
+        let owner = ZkLinkAddress::from_str(&owner)
+            .map_err(|error| JsValue::from_str(&format!("error: {error}")))?;
+        let amount = BigUint::from_str(&amount)
+            .map_err(|error| JsValue::from_str(&format!("error: {error}")))?;
+        let eth_params: InnerEthTxOption =
+            serde_wasm_bindgen::from_value(eth_params.json_value().unwrap()).unwrap();
+        let tx_hash = self
+            .inner
+            .withdraw_pending_balance(owner, token_id, amount, eth_params)
+            .await?;
+        Ok(hex::encode(tx_hash.as_bytes()))
+    }
+
+    #[wasm_bindgen(js_name=isExodusMode)]
+    pub async fn is_exodus_mode(&self, eth_params: EthTxOption) -> Result<bool, JsValue> {
+        let eth_params: InnerEthTxOption =
+            serde_wasm_bindgen::from_value(eth_params.json_value().unwrap()).unwrap();
+        Ok(self.inner.is_exodus_mode(eth_params).await?)
+    }
+
+    #[wasm_bindgen(js_name=activateExodusMode)]
+    pub async fn activate_exodus_mode(&self, eth_params: EthTxOption) -> Result<String, JsValue> {
+        let eth_params: InnerEthTxOption =
+            serde_wasm_bindgen::from_value(eth_params.json_value().unwrap()).unwrap();
+        let tx_hash = self.inner.activate_exodus_mode(eth_params).await?;
+        Ok(hex::encode(tx_hash.as_bytes()))
+    }
+
+    #[wasm_bindgen(js_name=cancelOutstandingDepositsForExodusMode)]
+    pub async fn cancel_outstanding_deposits_for_exodus_mode(
+        &self,
+        n: f64,
+        deposits_pubdata: Vec<String>,
+        eth_params: EthTxOption,
+    ) -> Result<String, JsValue> {
+        let deposits_pubdata = deposits_pubdata
+            .iter()
+            .map(|pubdata| hex::decode(pubdata.trim_start_matches("0x")))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|error| JsValue::from_str(&format!("error: {error}")))?;
+        let eth_params: InnerEthTxOption =
+            serde_wasm_bindgen::from_value(eth_params.json_value().unwrap()).unwrap();
+        let tx_hash = self
+            .inner
+            .cancel_outstanding_deposits_for_exodus_mode(n as u64, deposits_pubdata, eth_params)
+            .await?;
+        Ok(hex::encode(tx_hash.as_bytes()))
+    }
+
+    #[wasm_bindgen(js_name=performExodus)]
+    pub async fn perform_exodus(
+        &self,
+        exodus_proof: JsValue,
+        eth_params: EthTxOption,
+    ) -> Result<String, JsValue> {
+        let exodus_proof: ExodusProof = serde_wasm_bindgen::from_value(exodus_proof)?;
+        let eth_params: InnerEthTxOption =
+            serde_wasm_bindgen::from_value(eth_params.json_value().unwrap()).unwrap();
+        let tx_hash = self.inner.perform_exodus(exodus_proof, eth_params).await?;
+        Ok(hex::encode(tx_hash.as_bytes()))
+    }
+
+    #[wasm_bindgen(js_name=performedExodus)]
+    pub async fn performed_exodus(
+        &self,
+        account_id: u32,
+        sub_account_id: u8,
+        withdraw_token_id: u16,
+        deduct_token_id: u16,
+        eth_params: EthTxOption,
+    ) -> Result<bool, JsValue> {
+        let eth_params: InnerEthTxOption =
+            serde_wasm_bindgen::from_value(eth_params.json_value().unwrap()).unwrap();
+        Ok(self
+            .inner
+            .performed_exodus(
+                account_id,
+                sub_account_id,
+                withdraw_token_id,
+                deduct_token_id,
+                eth_params,
+            )
+            .await?)
+    }
 }
@@ -59,7 +59,7 @@ func HighLevelWithdraw() {
 	if err != nil {
 		return
 	}
-	txSignature, err := signer.SignWithdraw(tx, "USDT", nil, nil)
+	txSignature, err := signer.SignWithdraw(tx, "USDT")
 	fmt.Println("tx signature: %s", txSignature)
 	if err != nil {
 		return
@@ -42,7 +42,7 @@ func HighLevelTransfer() {
 	if err != nil {
 		return
 	}
-	txSignature, err := signer.SignTransfer(tx, tokenSymbol, nil, nil)
+	txSignature, err := signer.SignTransfer(tx, tokenSymbol)
 	if err != nil {
 		return
 	}
@@ -32,7 +32,7 @@ def main():
     )
     tx = sdk.Withdraw(builder)
     signer = sdk.Signer(private_key, sdk.L1SignerType.ETH())
-    tx_signature = signer.sign_withdraw(tx, "USDT", None, None)
+    tx_signature = signer.sign_withdraw(tx, "USDT")
     print(tx_signature)
 
 if __name__ == "__main__":
@@ -0,0 +1,283 @@
+use crate::error::{SignError, ZkLinkClientError};
+use crate::signer::{L1SignerType, Signer};
+use crate::ChangePubKeyAuthRequest;
+use jsonrpsee::http_client::HttpClient;
+use std::sync::{Mutex, RwLock, RwLockReadGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+use zklink_sdk_provider::nonce_manager::NonceManager;
+use zklink_sdk_provider::response::{AccountQuery, TokenResp};
+use zklink_sdk_provider::token_registry::{Rounding, TokenRegistry};
+use zklink_sdk_provider::{ZkLinkRpcClient, ZkLinkRpcProvider};
+use zklink_sdk_signers::eth_signer::pk_signer::EthSigner;
+use zklink_sdk_types::basic_types::tx_hash::TxHash;
+use zklink_sdk_types::basic_types::{
+    AccountId, BigUint, ChainId, Nonce, SubAccountId, TimeStamp, TokenId, ZkLinkAddress,
+};
+use zklink_sdk_types::prelude::TxSignature;
+use zklink_sdk_types::tx_builder::{ChangePubKeyBuilder, TransferBuilder, WithdrawBuilder};
+
+/// The parameters of `Transfer`, the account id, nonce and timestamp are filled by the client.
+#[derive(Debug, Clone)]
+pub struct TransferParams {
+    pub from_sub_account_id: SubAccountId,
+    pub to_address: ZkLinkAddress,
+    pub to_sub_account_id: SubAccountId,
+    pub token: TokenId,
+    pub amount: BigUint,
+    pub fee: BigUint,
+}
+
+/// The parameters of `Withdraw`, the account id, nonce and timestamp are filled by the client.
+#[derive(Debug, Clone)]
+pub struct WithdrawParams {
+    pub sub_account_id: SubAccountId,
+    pub to_chain_id: ChainId,
+    pub to_address: ZkLinkAddress,
+    pub l2_source_token: TokenId,
+    pub l1_target_token: TokenId,
+    pub amount: BigUint,
+    pub fee: BigUint,
+    pub withdraw_to_l1: bool,
+    pub withdraw_fee_ratio: u16,
+}
+
+/// The parameters of `ChangePubKey`, the new pubkey hash is the one of the client signer.
+pub struct ChangePubKeyParams {
+    pub chain_id: ChainId,
+    pub sub_account_id: SubAccountId,
+    pub fee_token: TokenId,
+    pub fee: BigUint,
+    pub auth: ChangePubKeyAuthRequest,
+}
+
+/// `ZkLinkClient` builds, signs and submits the txs of one account in one call.
+///
+/// The account id is looked up by the layer1 address of the signer, the nonces are handed out
+/// by [`NonceManager`] and released if the tx is not submitted, and the token symbols used in
+/// the layer1 signing messages and the layer1 decimals of the withdrawals are resolved by
+/// [`TokenRegistry`].
+pub struct ZkLinkClient {
+    signer: Signer,
+    provider: ZkLinkRpcProvider,
+    address: ZkLinkAddress,
+    account_id: Mutex<Option<AccountId>>,
+    tokens: RwLock<TokenRegistry>,
+    nonces: NonceManager<HttpClient>,
+}
+
+impl ZkLinkClient {
+    pub fn new(
+        private_key: &str,
+        l1_signer_type: L1SignerType,
+        provider: ZkLinkRpcProvider,
+    ) -> Result<Self, ZkLinkClientError> {
+        let address = match &l1_signer_type {
+            L1SignerType::Eth => {
+                let eth_signer = EthSigner::try_from(private_key).map_err(SignError::from)?;
+                eth_signer.get_address().into()
+            }
+            L1SignerType::Starknet { address, .. } => ZkLinkAddress::from_hex(address)
+                .map_err(|_| ZkLinkClientError::InvalidAddress(address.clone()))?,
+        };
+        let signer = Signer::new(private_key, l1_signer_type)?;
+        let nonces = NonceManager::new((*provider).clone());
+        Ok(Self {
+            signer,
+            provider,
+            address,
+            account_id: Mutex::new(None),
+            tokens: Default::default(),
+            nonces,
+        })
+    }
+
+    /// The layer1 address of the account.
+    pub fn address(&self) -> &ZkLinkAddress {
+        &self.address
+    }
+
+    pub fn provider(&self) -> &ZkLinkRpcProvider {
+        &self.provider
+    }
+
+    pub fn nonce_manager(&self) -> &NonceManager<HttpClient> {
+        &self.nonces
+    }
+
+    /// The account id, which is fetched by `getAccount` at the first time.
+    pub async fn account_id(&self) -> Result<AccountId, ZkLinkClientError> {
+        let cached = *self.account_id.lock().unwrap();
+        if let Some(account_id) = cached {
+            return Ok(account_id);
+        }
+        let account = self
+            .provider
+            .account_info(AccountQuery::Address(self.address.clone()))
+            .await?;
+        *self.account_id.lock().unwrap() = Some(account.id);
+        Ok(account.id)
+    }
+
+    /// The token info, the registry is refreshed if the token is unknown.
+    pub async fn token(&self, token_id: TokenId) -> Result<TokenResp, ZkLinkClientError> {
+        if let Ok(token) = self.tokens.read().unwrap().token(token_id) {
+            return Ok(token.clone());
+        }
+        self.refresh_tokens().await?;
+        Ok(self.tokens.read().unwrap().token(token_id)?.clone())
+    }
+
+    /// The tokens and chains supported by the node, loaded when a token is used first time.
+    pub fn token_registry(&self) -> RwLockReadGuard<'_, TokenRegistry> {
+        self.tokens.read().unwrap()
+    }
+
+    /// Reload the tokens and chains from the node, they are merged into the registry so the
+    /// chain tokens of `getEthProperty` are kept, see [`TokenRegistry::merge`].
+    pub async fn refresh_tokens(&self) -> Result<(), ZkLinkClientError> {
+        // fetch before taking the lock, it must not be held across an await
+        let tokens = self.provider.tokens().await?;
+        let chains = self.provider.get_support_chains().await?;
+        self.tokens.write().unwrap().merge(tokens, chains);
+        Ok(())
+    }
+
+    pub async fn transfer(&self, params: TransferParams) -> Result<TxHash, ZkLinkClientError> {
+        let account_id = self.account_id().await?;
+        let token = self.token(params.token).await?;
+        let nonce = self.nonces.next_nonce(account_id).await?;
+        let tx = TransferBuilder {
+            account_id,
+            to_address: params.to_address,
+            from_sub_account_id: params.from_sub_account_id,
+            to_sub_account_id: params.to_sub_account_id,
+            token: params.token,
+            amount: params.amount,
+            fee: params.fee,
+            nonce,
+            timestamp: now(),
+        }
+        .build();
+        let signature = self.signer.sign_transfer(tx, &token.symbol).await;
+        self.submit(account_id, nonce, signature).await
+    }
+
+    pub async fn withdraw(&self, params: WithdrawParams) -> Result<TxHash, ZkLinkClientError> {
+        let account_id = self.account_id().await?;
+        let token = self.token(params.l2_source_token).await?;
+        self.check_l1_amount(params.l1_target_token, params.to_chain_id, &params.amount)
+            .await?;
+        let nonce = self.nonces.next_nonce(account_id).await?;
+        let tx = WithdrawBuilder {
+            account_id,
+            sub_account_id: params.sub_account_id,
+            to_chain_id: params.to_chain_id,
+            to_address: params.to_address,
+            l2_source_token: params.l2_source_token,
+            l1_target_token: params.l1_target_token,
+            amount: params.amount,
+            data_hash: None,
+            fee: params.fee,
+            nonce,
+            withdraw_to_l1: params.withdraw_to_l1,
+            withdraw_fee_ratio: params.withdraw_fee_ratio,
+            timestamp: now(),
+        }
+        .build();
+        let signature = self.signer.sign_withdraw(tx, &token.symbol).await;
+        self.submit(account_id, nonce, signature).await
+    }
+
+    /// Set the pubkey hash of the account to the one of the client signer.
+    pub async fn change_pubkey(
+        &self,
+        params: ChangePubKeyParams,
+    ) -> Result<TxHash, ZkLinkClientError> {
+        let account_id = self.account_id().await?;
+        let nonce = self.nonces.next_nonce(account_id).await?;
+        let tx = ChangePubKeyBuilder {
+            chain_id: params.chain_id,
+            account_id,
+            sub_account_id: params.sub_account_id,
+            new_pubkey_hash: self.signer.pubkey_hash(),
+            fee_token: params.fee_token,
+            fee: params.fee,
+            nonce,
+            eth_signature: None,
+            timestamp: now(),
+        }
+        .build();
+        let signature = match params.auth {
+            ChangePubKeyAuthRequest::Onchain => {
+                self.signer.sign_change_pubkey_with_onchain_auth_data(tx)
+            }
+            ChangePubKeyAuthRequest::EthECDSA => {
+                self.signer.sign_change_pubkey_with_eth_ecdsa_auth(tx).await
+            }
+            ChangePubKeyAuthRequest::EthCreate2 { data } => self
+                .signer
+                .sign_change_pubkey_with_create2data_auth(tx, data),
+        };
+        self.submit(account_id, nonce, signature).await
+    }
+
+    /// The amount withdrawn must be represented exactly with the layer1 decimals of the token,
+    /// or the extra decimals would be lost.
+    async fn check_l1_amount(
+        &self,
+        token_id: TokenId,
+        chain_id: ChainId,
+        amount: &BigUint,
+    ) -> Result<(), ZkLinkClientError> {
+        self.token(token_id).await?;
+        self.tokens
+            .read()
+            .unwrap()
+            .l2_to_l1_amount(token_id, chain_id, amount, Rounding::Exact)?;
+        Ok(())
+    }
+
+    /// Submit the signed tx, the nonce is released if it's not used.
+    async fn submit(
+        &self,
+        account_id: AccountId,
+        nonce: Nonce,
+        signature: Result<TxSignature, SignError>,
+    ) -> Result<TxHash, ZkLinkClientError> {
+        let signature = match signature {
+            Ok(signature) => signature,
+            Err(e) => {
+                self.nonces.release_nonce(account_id, nonce).await;
+                return Err(e.into());
+            }
+        };
+        let ret = self
+            .provider
+            .tx_submit(signature.tx, signature.layer1_signature, None)
+            .await
+            .map_err(ZkLinkClientError::from);
+        match &ret {
+            Ok(_) => {}
+            // resync if it's rejected because of the nonce
+            Err(ZkLinkClientError::NodeError(e)) if e.is_nonce_error() => {
+                let _ = self.nonces.resync(account_id).await;
+            }
+            // the tx is rejected, the nonce is not used
+            Err(ZkLinkClientError::NodeError(_)) => {
+                self.nonces.release_nonce(account_id, nonce).await
+            }
+            // the tx may or may not be received by the node
+            Err(_) => {
+                let _ = self.nonces.resync(account_id).await;
+            }
+        }
+        ret
+    }
+}
+
+fn now() -> TimeStamp {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time before unix epoch");
+    TimeStamp(now.as_secs() as u32)
+}
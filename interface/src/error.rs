@@ -1,6 +1,12 @@
+#[cfg(not(any(feature = "ffi", feature = "web", target_arch = "wasm32")))]
+use jsonrpsee::core::ClientError;
 use thiserror::Error;
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::JsValue;
+#[cfg(not(any(feature = "ffi", feature = "web", target_arch = "wasm32")))]
+use zklink_sdk_provider::error::{ProviderError, ZkLinkNodeError};
+#[cfg(not(any(feature = "ffi", target_arch = "wasm32")))]
+use zklink_sdk_provider::token_registry::TokenRegistryError;
 use zklink_sdk_signers::eth_signer::error::EthSignerError;
 use zklink_sdk_signers::starknet_signer::error::StarkSignerError;
 use zklink_sdk_signers::zklink_signer::error::ZkSignerError;
@@ -15,6 +21,9 @@ pub enum SignError {
     StarkSigningError(#[from] StarkSignerError),
     #[error("Incorrect tx format")]
     IncorrectTx,
+    #[cfg(not(any(feature = "ffi", target_arch = "wasm32")))]
+    #[error("{0}")]
+    TokenError(#[from] TokenRegistryError),
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -23,3 +32,38 @@ impl From<SignError> for JsValue {
         JsValue::from_str(&format!("error: {error}"))
     }
 }
+
+#[cfg(not(any(feature = "ffi", feature = "web", target_arch = "wasm32")))]
+#[derive(Debug, Error)]
+pub enum ZkLinkClientError {
+    #[error("{0}")]
+    SignError(#[from] SignError),
+    #[error("Rpc error: {0}")]
+    RpcError(ClientError),
+    #[error("{0}")]
+    NodeError(ZkLinkNodeError),
+    #[error("{0}")]
+    TokenError(#[from] TokenRegistryError),
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+}
+
+#[cfg(not(any(feature = "ffi", feature = "web", target_arch = "wasm32")))]
+impl From<ClientError> for ZkLinkClientError {
+    fn from(error: ClientError) -> Self {
+        match ZkLinkNodeError::from_client_error(&error) {
+            Some(error) => Self::NodeError(error),
+            None => Self::RpcError(error),
+        }
+    }
+}
+
+#[cfg(not(any(feature = "ffi", feature = "web", target_arch = "wasm32")))]
+impl From<ProviderError> for ZkLinkClientError {
+    fn from(error: ProviderError) -> Self {
+        match error {
+            ProviderError::RpcError(error) => Self::RpcError(error),
+            ProviderError::NodeError(error) => Self::NodeError(error),
+        }
+    }
+}
@@ -1,29 +1,19 @@
 use crate::error::SignError;
-use crate::sign_change_pubkey::{
-    do_sign_change_pubkey_with_create2data_auth, do_sign_change_pubkey_with_onchain_auth_data,
-};
-use crate::sign_forced_exit::sign_forced_exit;
-use crate::sign_order_matching::sign_order_matching;
-use crate::sign_transfer::{sign_eth_transfer, sign_starknet_transfer};
-use crate::sign_withdraw::{sign_eth_withdraw, sign_starknet_withdraw};
+use crate::layer1_signer::Layer1Signer;
+use crate::signer::{zklink_signer_seed, Signer};
+use crate::L1Type;
+use std::sync::Arc;
 use zklink_sdk_signers::eth_signer::json_rpc_signer::{
     JsonRpcSigner as EthJsonRpcSigner, Signer as EthereumAccountSigner,
 };
 use zklink_sdk_signers::starknet_signer::starknet_json_rpc_signer::{
     Signer as StarknetAccountSigner, StarknetJsonRpcSigner,
 };
-
-use crate::sign_auto_deleveraging::sign_auto_deleveraging;
-use crate::sign_contract_matching::sign_contract_matching;
-use crate::sign_funding::sign_funding;
-use crate::sign_liquidation::sign_liquidation;
-use zklink_sdk_signers::starknet_signer::error::StarkSignerError;
 use zklink_sdk_signers::starknet_signer::StarkEcdsaSignature;
 use zklink_sdk_signers::zklink_signer::ZkLinkSigner;
-use zklink_sdk_types::basic_types::GetBytes;
 use zklink_sdk_types::prelude::PackedEthSignature;
 use zklink_sdk_types::signatures::TxSignature;
-use zklink_sdk_types::tx_type::change_pubkey::{ChangePubKey, ChangePubKeyAuthData, Create2Data};
+use zklink_sdk_types::tx_type::change_pubkey::{ChangePubKey, Create2Data};
 use zklink_sdk_types::tx_type::contract::{
     AutoDeleveraging, Contract, ContractMatching, Funding, Liquidation,
 };
@@ -31,20 +21,14 @@ use zklink_sdk_types::tx_type::forced_exit::ForcedExit;
 use zklink_sdk_types::tx_type::order_matching::{Order, OrderMatching};
 use zklink_sdk_types::tx_type::transfer::Transfer;
 use zklink_sdk_types::tx_type::withdraw::Withdraw;
-use zklink_sdk_types::tx_type::ZkSignatureTrait;
 
 pub enum JsonRpcAccountSigner {
     EthereumSigner(EthereumAccountSigner),
     StarknetSigner(StarknetAccountSigner),
 }
-pub enum Layer1JsonRpcSigner {
-    EthSigner(EthJsonRpcSigner),
-    StarknetSigner(StarknetJsonRpcSigner),
-}
 
 pub struct JsonRpcSigner {
-    zklink_signer: ZkLinkSigner,
-    layer1_signer: Layer1JsonRpcSigner,
+    signer: Signer,
     signature_seed: Vec<u8>,
 }
 
@@ -54,66 +38,49 @@ impl JsonRpcSigner {
         pub_key: Option<String>,
         chain_id: Option<String>,
     ) -> Result<Self, SignError> {
-        let eth_json_rpc_signer = match signer {
-            JsonRpcAccountSigner::EthereumSigner(signer) => {
-                Layer1JsonRpcSigner::EthSigner(EthJsonRpcSigner::new(signer))
-            }
-            JsonRpcAccountSigner::StarknetSigner(signer) => Layer1JsonRpcSigner::StarknetSigner(
-                StarknetJsonRpcSigner::new(signer, pub_key.unwrap(), chain_id.unwrap()),
-            ),
+        let layer1_signer: Arc<dyn Layer1Signer> = match signer {
+            JsonRpcAccountSigner::EthereumSigner(signer) => Arc::new(EthJsonRpcSigner::new(signer)),
+            JsonRpcAccountSigner::StarknetSigner(signer) => Arc::new(StarknetJsonRpcSigner::new(
+                signer,
+                pub_key.unwrap(),
+                chain_id.unwrap(),
+            )),
         };
         let default_zklink_signer = ZkLinkSigner::new()?;
         Ok(Self {
-            zklink_signer: default_zklink_signer,
-            layer1_signer: eth_json_rpc_signer,
+            signer: Signer::new_with_layer1_signer(default_zklink_signer, layer1_signer),
             signature_seed: vec![],
         })
     }
 
     pub async fn init_zklink_signer(&mut self, signature: Option<String>) -> Result<(), SignError> {
-        let (zklink_signer, seed) = if let Some(s) = signature {
-            match &self.layer1_signer {
-                Layer1JsonRpcSigner::EthSigner(_) => {
-                    let signature = PackedEthSignature::from_hex(&s)?;
-                    let seed = signature.serialize_packed();
-                    (ZkLinkSigner::new_from_seed(&seed)?, seed.to_vec())
-                }
-                Layer1JsonRpcSigner::StarknetSigner(_) => {
-                    let signature = StarkEcdsaSignature::from_hex(&s)?;
-                    let seed = signature.to_bytes_be();
-                    (ZkLinkSigner::new_from_seed(&seed)?, seed.to_vec())
-                }
+        let layer1_signer = self.signer.layer1_signer().clone();
+        let seed = if let Some(s) = signature {
+            match layer1_signer.l1_type() {
+                L1Type::Eth => PackedEthSignature::from_hex(&s)?
+                    .serialize_packed()
+                    .to_vec(),
+                L1Type::Starknet => StarkEcdsaSignature::from_hex(&s)?.to_bytes_be().to_vec(),
             }
         } else {
-            match &self.layer1_signer {
-                Layer1JsonRpcSigner::EthSigner(signer) => {
-                    ZkLinkSigner::new_from_eth_rpc_signer(signer).await?
-                }
-                Layer1JsonRpcSigner::StarknetSigner(signer) => {
-                    ZkLinkSigner::new_from_starknet_rpc_signer(signer).await?
-                }
-            }
+            zklink_signer_seed(&*layer1_signer).await?
         };
-        self.zklink_signer = zklink_signer;
+        let zklink_signer = ZkLinkSigner::new_from_seed(&seed)?;
+        self.signer = Signer::new_with_layer1_signer(zklink_signer, layer1_signer);
         self.signature_seed = seed;
         Ok(())
     }
 
     pub fn pub_key_hash(&self) -> String {
-        let pub_key = self.zklink_signer.public_key();
-        pub_key.public_key_hash().as_hex()
+        self.signer.pubkey_hash().as_hex()
     }
 
     pub fn public_key(&self) -> String {
-        let pub_key = self.zklink_signer.public_key();
-        pub_key.as_hex()
+        self.signer.public_key().as_hex()
     }
 
     pub fn address(&self) -> Option<String> {
-        match &self.layer1_signer {
-            Layer1JsonRpcSigner::EthSigner(s) => s.address(),
-            Layer1JsonRpcSigner::StarknetSigner(s) => Some(s.address()),
-        }
+        Some(self.signer.layer1_signer().address()).filter(|address| !address.is_empty())
     }
 
     pub fn signature_seed(&self) -> Vec<u8> {
@@ -125,14 +92,7 @@ impl JsonRpcSigner {
         tx: Transfer,
         token_symbol: &str,
     ) -> Result<TxSignature, SignError> {
-        match &self.layer1_signer {
-            Layer1JsonRpcSigner::EthSigner(signer) => {
-                sign_eth_transfer(signer, &self.zklink_signer, tx, token_symbol).await
-            }
-            Layer1JsonRpcSigner::StarknetSigner(signer) => {
-                sign_starknet_transfer(signer, &self.zklink_signer, tx, token_symbol).await
-            }
-        }
+        self.signer.sign_transfer(tx, token_symbol).await
     }
 
     #[inline]
@@ -140,7 +100,7 @@ impl JsonRpcSigner {
         &self,
         tx: ChangePubKey,
     ) -> Result<TxSignature, SignError> {
-        do_sign_change_pubkey_with_onchain_auth_data(tx, &self.zklink_signer)
+        self.signer.sign_change_pubkey_with_onchain_auth_data(tx)
     }
 
     #[inline]
@@ -149,38 +109,16 @@ impl JsonRpcSigner {
         tx: ChangePubKey,
         create2data: Create2Data,
     ) -> Result<TxSignature, SignError> {
-        do_sign_change_pubkey_with_create2data_auth(tx, create2data, &self.zklink_signer)
+        self.signer
+            .sign_change_pubkey_with_create2data_auth(tx, create2data)
     }
 
     #[inline]
     pub async fn sign_change_pubkey_with_eth_ecdsa_auth(
         &self,
-        mut tx: ChangePubKey,
+        tx: ChangePubKey,
     ) -> Result<TxSignature, SignError> {
-        tx.sign(&self.zklink_signer)?;
-        let should_valid = tx.is_signature_valid();
-        assert!(should_valid);
-
-        // create auth data
-        let eth_sign_msg = ChangePubKey::get_eth_sign_msg(&tx.new_pk_hash, tx.nonce, tx.account_id);
-        let eth_signature = match &self.layer1_signer {
-            Layer1JsonRpcSigner::EthSigner(signer) => {
-                signer.sign_message(eth_sign_msg.as_bytes()).await?
-            }
-            Layer1JsonRpcSigner::StarknetSigner(_) => {
-                //starknet only support change_pubkey_onchain
-                return Err(
-                    StarkSignerError::SignError("Not support for starknet".to_string()).into(),
-                );
-            }
-        };
-
-        tx.eth_auth_data = ChangePubKeyAuthData::EthECDSA { eth_signature };
-
-        Ok(TxSignature {
-            tx: tx.into(),
-            layer1_signature: None,
-        })
+        self.signer.sign_change_pubkey_with_eth_ecdsa_auth(tx).await
     }
 
     pub async fn sign_withdraw(
@@ -188,59 +126,42 @@ impl JsonRpcSigner {
         tx: Withdraw,
         l2_source_token_symbol: &str,
     ) -> Result<TxSignature, SignError> {
-        match &self.layer1_signer {
-            Layer1JsonRpcSigner::EthSigner(signer) => {
-                sign_eth_withdraw(signer, &self.zklink_signer, tx, l2_source_token_symbol).await
-            }
-            Layer1JsonRpcSigner::StarknetSigner(signer) => {
-                sign_starknet_withdraw(signer, &self.zklink_signer, tx, l2_source_token_symbol)
-                    .await
-            }
-        }
+        self.signer.sign_withdraw(tx, l2_source_token_symbol).await
     }
 
     #[inline]
     pub fn sign_forced_exit(&self, tx: ForcedExit) -> Result<TxSignature, SignError> {
-        let signature = sign_forced_exit(&self.zklink_signer, tx)?;
-        Ok(signature)
+        self.signer.sign_forced_exit(tx)
     }
 
     #[inline]
     pub fn create_signed_order(&self, order: &Order) -> Result<Order, SignError> {
-        let signed_order = order.create_signed_order(&self.zklink_signer)?;
-        Ok(signed_order)
+        self.signer.create_signed_order(order)
     }
 
     #[inline]
     pub fn sign_order_matching(&self, tx: OrderMatching) -> Result<TxSignature, SignError> {
-        let signature = sign_order_matching(&self.zklink_signer, tx)?;
-        Ok(signature)
+        self.signer.sign_order_matching(tx)
     }
 
     pub fn sign_auto_deleveraging(&self, tx: AutoDeleveraging) -> Result<TxSignature, SignError> {
-        let signature = sign_auto_deleveraging(&self.zklink_signer, tx)?;
-        Ok(signature)
+        self.signer.sign_auto_deleveraging(tx)
     }
 
     pub fn sign_contract_matching(&self, tx: ContractMatching) -> Result<TxSignature, SignError> {
-        let signature = sign_contract_matching(&self.zklink_signer, tx)?;
-        Ok(signature)
+        self.signer.sign_contract_matching(tx)
     }
 
     pub fn sign_funding(&self, tx: Funding) -> Result<TxSignature, SignError> {
-        let signature = sign_funding(&self.zklink_signer, tx)?;
-        Ok(signature)
+        self.signer.sign_funding(tx)
     }
 
     pub fn sign_liquidation(&self, tx: Liquidation) -> Result<TxSignature, SignError> {
-        let signature = sign_liquidation(&self.zklink_signer, tx)?;
-        Ok(signature)
+        self.signer.sign_liquidation(tx)
     }
 
     #[inline]
     pub fn create_signed_contract(&self, contract: &Contract) -> Result<Contract, SignError> {
-        let mut contract = contract.clone();
-        contract.signature = self.zklink_signer.sign_musig(&contract.get_bytes())?;
-        Ok(contract)
+        self.signer.create_signed_contract(contract)
     }
 }
@@ -0,0 +1,308 @@
+use crate::error::SignError;
+use crate::L1Type;
+use async_trait::async_trait;
+use cfg_if::cfg_if;
+#[cfg(feature = "web")]
+use zklink_sdk_signers::eth_signer::json_rpc_signer::JsonRpcSigner as EthJsonRpcSigner;
+use zklink_sdk_signers::eth_signer::{EthSigner, EthSignerError, EthTypedData};
+use zklink_sdk_signers::starknet_signer::error::StarkSignerError;
+#[cfg(feature = "web")]
+use zklink_sdk_signers::starknet_signer::starknet_json_rpc_signer::StarknetJsonRpcSigner;
+use zklink_sdk_signers::starknet_signer::typed_data::message::{Message, TypedDataMessage};
+use zklink_sdk_signers::starknet_signer::typed_data::TypedData;
+use zklink_sdk_signers::starknet_signer::StarkSigner;
+use zklink_sdk_types::prelude::TxLayer1Signature;
+
+cfg_if! {
+    if #[cfg(target_arch = "wasm32")] {
+        /// The js signers of the browser are not `Send`.
+        pub trait MaybeSendSync {}
+        impl<T> MaybeSendSync for T {}
+    } else {
+        pub trait MaybeSendSync: Send + Sync {}
+        impl<T: Send + Sync> MaybeSendSync for T {}
+    }
+}
+
+/// The typed data signed by the layer1 signers.
+#[derive(Debug, Clone)]
+pub enum Layer1TypedData {
+    /// The EIP-712 typed data, the raw json is for the wallets and the hash is signed by the
+    /// private keys.
+    Eth(EthTypedData),
+    /// The starknet typed data message, the domain is built with the chain id of the signer.
+    Starknet(TypedDataMessage),
+}
+
+/// The layer1 signer of the account, implement it to plug in the remote signers, KMS or the
+/// hardware wallets.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait Layer1Signer: MaybeSendSync {
+    fn l1_type(&self) -> L1Type;
+
+    /// The hex address of the layer1 account.
+    fn address(&self) -> String;
+
+    /// Sign the message by `personal_sign` of ethereum, the starknet signers sign it as the
+    /// `Message` typed data.
+    async fn sign_message(&self, message: &[u8]) -> Result<TxLayer1Signature, SignError>;
+
+    async fn sign_typed_data(
+        &self,
+        typed_data: &Layer1TypedData,
+    ) -> Result<TxLayer1Signature, SignError>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Layer1Signer for EthSigner {
+    fn l1_type(&self) -> L1Type {
+        L1Type::Eth
+    }
+
+    fn address(&self) -> String {
+        format!("{:?}", self.get_address())
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<TxLayer1Signature, SignError> {
+        Ok(EthSigner::sign_message(self, message)?.into())
+    }
+
+    async fn sign_typed_data(
+        &self,
+        typed_data: &Layer1TypedData,
+    ) -> Result<TxLayer1Signature, SignError> {
+        match typed_data {
+            Layer1TypedData::Eth(typed_data) => {
+                Ok(self.sign_hash(typed_data.data_hash.as_bytes())?.into())
+            }
+            Layer1TypedData::Starknet(_) => {
+                Err(EthSignerError::Eip712Failed("not an EIP-712 typed data".into()).into())
+            }
+        }
+    }
+}
+
+/// The starknet private key signer of a deployed account.
+pub struct StarknetPkSigner {
+    signer: StarkSigner,
+    chain_id: String,
+    address: String,
+}
+
+impl StarknetPkSigner {
+    pub fn new(signer: StarkSigner, chain_id: String, address: String) -> Self {
+        Self {
+            signer,
+            chain_id,
+            address,
+        }
+    }
+
+    fn sign(&self, message: TypedDataMessage) -> Result<TxLayer1Signature, SignError> {
+        let typed_data = TypedData::new(message, self.chain_id.clone());
+        let signature = self.signer.sign_message(&typed_data, &self.address)?;
+        Ok(signature.into())
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl Layer1Signer for StarknetPkSigner {
+    fn l1_type(&self) -> L1Type {
+        L1Type::Starknet
+    }
+
+    fn address(&self) -> String {
+        self.address.clone()
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<TxLayer1Signature, SignError> {
+        self.sign(message_typed_data(message)?)
+    }
+
+    async fn sign_typed_data(
+        &self,
+        typed_data: &Layer1TypedData,
+    ) -> Result<TxLayer1Signature, SignError> {
+        match typed_data {
+            Layer1TypedData::Starknet(message) => self.sign(message.clone()),
+            Layer1TypedData::Eth(_) => {
+                Err(StarkSignerError::SignError("not a starknet typed data".into()).into())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+#[async_trait(?Send)]
+impl Layer1Signer for EthJsonRpcSigner {
+    fn l1_type(&self) -> L1Type {
+        L1Type::Eth
+    }
+
+    fn address(&self) -> String {
+        EthJsonRpcSigner::address(self).unwrap_or_default()
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<TxLayer1Signature, SignError> {
+        Ok(EthJsonRpcSigner::sign_message(self, message).await?.into())
+    }
+
+    async fn sign_typed_data(
+        &self,
+        typed_data: &Layer1TypedData,
+    ) -> Result<TxLayer1Signature, SignError> {
+        match typed_data {
+            Layer1TypedData::Eth(typed_data) => {
+                Ok(EthJsonRpcSigner::sign_typed_data(self, typed_data)
+                    .await?
+                    .into())
+            }
+            Layer1TypedData::Starknet(_) => {
+                Err(EthSignerError::Eip712Failed("not an EIP-712 typed data".into()).into())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+#[async_trait(?Send)]
+impl Layer1Signer for StarknetJsonRpcSigner {
+    fn l1_type(&self) -> L1Type {
+        L1Type::Starknet
+    }
+
+    fn address(&self) -> String {
+        StarknetJsonRpcSigner::address(self)
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<TxLayer1Signature, SignError> {
+        let message = message_typed_data(message)?;
+        Ok(StarknetJsonRpcSigner::sign_message(self, message)
+            .await?
+            .into())
+    }
+
+    async fn sign_typed_data(
+        &self,
+        typed_data: &Layer1TypedData,
+    ) -> Result<TxLayer1Signature, SignError> {
+        match typed_data {
+            Layer1TypedData::Starknet(message) => {
+                Ok(StarknetJsonRpcSigner::sign_message(self, message.clone())
+                    .await?
+                    .into())
+            }
+            Layer1TypedData::Eth(_) => {
+                Err(StarkSignerError::SignError("not a starknet typed data".into()).into())
+            }
+        }
+    }
+}
+
+fn message_typed_data(message: &[u8]) -> Result<TypedDataMessage, SignError> {
+    let data = String::from_utf8(message.to_vec())
+        .map_err(|e| StarkSignerError::SignError(e.to_string()))?;
+    Ok(TypedDataMessage::CreateL2Key {
+        message: Message { data },
+    })
+}
+
+#[cfg(not(feature = "web"))]
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::signer::{L1SignerType, Signer};
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use zklink_sdk_signers::zklink_signer::pk_signer::ZkLinkSigner;
+    use zklink_sdk_types::basic_types::BigUint;
+    use zklink_sdk_types::prelude::*;
+
+    const STARKNET_PRIVATE_KEY: &str =
+        "0x02c5dbad71c92a45cc4b40573ae661f8147869a91d57b8d9b8f48c8af7f83159";
+    const STARKNET_ADDRESS: &str =
+        "0x04A69b67bcaBfA7D3CCb96e1d25C2e6fC93589fE24A6fD04566B8700ff97a71a";
+    const STARKNET_CHAIN_ID: &str = "SN_GOERLI";
+
+    fn transfer() -> Transfer {
+        TransferBuilder {
+            account_id: AccountId(1),
+            from_sub_account_id: SubAccountId(1),
+            to_sub_account_id: SubAccountId(1),
+            to_address: ZkLinkAddress::from_str("0x0000000000000000000000000000000000000000")
+                .unwrap(),
+            token: TokenId(1),
+            amount: BigUint::from_str("1000000000000000000").unwrap(),
+            fee: BigUint::from_str("10000000000").unwrap(),
+            nonce: Nonce(1),
+            timestamp: TimeStamp(1646101085),
+        }
+        .build()
+    }
+
+    #[tokio::test]
+    async fn test_layer1_signer_eth() {
+        let eth_signer = EthSigner::from(H256::repeat_byte(5));
+        let zklink_signer = ZkLinkSigner::new_from_eth_signer(&eth_signer).unwrap();
+        let signer = Signer::new_from_layer1_signer(Arc::new(eth_signer))
+            .await
+            .unwrap();
+        assert_eq!(
+            signer.pubkey_hash(),
+            zklink_signer.public_key().public_key_hash()
+        );
+
+        let signature = signer.sign_transfer(transfer(), "USD").await.unwrap();
+        let Some(TxLayer1Signature::EthereumSignature(eth_signature)) = signature.layer1_signature
+        else {
+            panic!("transfer must has eth signature")
+        };
+        assert_eq!(eth_signature.as_hex(), "0x08c9cd25416c871a153e9d51385c28413311e8ed055a195e4f5e8c229244e1a05bab15a9e6eb1cff9a5d237d878c41553215341742779745574a631d89e09a831b");
+        let ZkLinkTx::Transfer(tx) = signature.tx else {
+            panic!("must is transfer")
+        };
+        assert_eq!(tx.signature.signature.as_hex(), "0x2aa6ebe4695f2c57e79fc284f87098ffefed9d4a53adadcd601b69bc3825511e5c859a5345526e52a77660e993dd92322fef64ad4521847ecd0215b556487902");
+    }
+
+    #[tokio::test]
+    async fn test_layer1_signer_starknet() {
+        let stark_signer = StarkSigner::new_from_hex_str(STARKNET_PRIVATE_KEY).unwrap();
+        let layer1_signer = StarknetPkSigner::new(
+            stark_signer,
+            STARKNET_CHAIN_ID.to_string(),
+            STARKNET_ADDRESS.to_string(),
+        );
+        let signer = Signer::new_from_layer1_signer(Arc::new(layer1_signer))
+            .await
+            .unwrap();
+        let expected_signer = Signer::new(
+            STARKNET_PRIVATE_KEY,
+            L1SignerType::Starknet {
+                chain_id: STARKNET_CHAIN_ID.to_string(),
+                address: STARKNET_ADDRESS.to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(signer.pubkey_hash(), expected_signer.pubkey_hash());
+
+        let signature = signer.sign_transfer(transfer(), "USD").await.unwrap();
+        let expected = expected_signer
+            .sign_transfer(transfer(), "USD")
+            .await
+            .unwrap();
+        let (
+            Some(TxLayer1Signature::StarkSignature(starknet_signature)),
+            Some(TxLayer1Signature::StarkSignature(expected_signature)),
+        ) = (signature.layer1_signature, expected.layer1_signature)
+        else {
+            panic!("transfer must has starknet signature")
+        };
+        assert_eq!(
+            starknet_signature.to_bytes_be(),
+            expected_signature.to_bytes_be()
+        );
+    }
+}
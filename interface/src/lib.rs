@@ -1,8 +1,11 @@
 use zklink_sdk_types::tx_type::change_pubkey::Create2Data;
 
+#[cfg(not(any(feature = "ffi", feature = "web", target_arch = "wasm32")))]
+pub mod client;
 pub mod error;
 #[cfg(feature = "web")]
 pub mod json_rpc_signer;
+pub mod layer1_signer;
 pub mod sign_auto_deleveraging;
 pub mod sign_change_pubkey;
 pub mod sign_contract_matching;
@@ -12,9 +15,13 @@ pub mod sign_liquidation;
 pub mod sign_order_matching;
 pub mod sign_transfer;
 pub mod sign_withdraw;
-#[cfg(not(feature = "web"))]
 pub mod signer;
 
+pub enum L1Type {
+    Eth,
+    Starknet,
+}
+
 pub enum ChangePubKeyAuthRequest {
     Onchain,
     EthECDSA,
@@ -1,15 +1,18 @@
 use crate::error::SignError;
+use crate::layer1_signer::Layer1Signer;
+use crate::L1Type;
 #[cfg(feature = "ffi")]
 use std::sync::Arc;
+use zklink_sdk_signers::eth_signer::error::EthSignerError;
 #[cfg(feature = "ffi")]
 use zklink_sdk_signers::eth_signer::packed_eth_signature::PackedEthSignature;
-#[cfg(not(feature = "web"))]
+#[cfg(feature = "ffi")]
 use zklink_sdk_signers::eth_signer::pk_signer::EthSigner;
 use zklink_sdk_signers::zklink_signer::pk_signer::ZkLinkSigner;
 #[cfg(feature = "ffi")]
 use zklink_sdk_types::prelude::GetBytes;
 use zklink_sdk_types::prelude::ZkLinkAddress;
-use zklink_sdk_types::signatures::TxSignature;
+use zklink_sdk_types::signatures::{TxLayer1Signature, TxSignature};
 use zklink_sdk_types::tx_type::change_pubkey::Create2Data;
 use zklink_sdk_types::tx_type::change_pubkey::{ChangePubKey, ChangePubKeyAuthData};
 use zklink_sdk_types::tx_type::ZkSignatureTrait;
@@ -83,19 +86,26 @@ pub fn do_sign_change_pubkey_with_create2data_auth(
     })
 }
 
-#[cfg(not(feature = "web"))]
-pub fn do_sign_change_pubkey_with_eth_ecdsa_auth(
-    eth_signer: &EthSigner,
+/// Only the ethereum signers sign the ECDSA auth data, the starknet accounts change the pubkey
+/// by the onchain auth.
+pub async fn do_sign_change_pubkey_with_eth_ecdsa_auth(
+    layer1_signer: &dyn Layer1Signer,
     zklink_signer: &ZkLinkSigner,
     mut tx: ChangePubKey,
 ) -> Result<TxSignature, SignError> {
+    if !matches!(layer1_signer.l1_type(), L1Type::Eth) {
+        return Err(EthSignerError::InvalidEthSigner.into());
+    }
     tx.sign(zklink_signer)?;
     let should_valid = tx.is_signature_valid();
     assert!(should_valid);
 
     // create auth data
     let eth_sign_msg = ChangePubKey::get_eth_sign_msg(&tx.new_pk_hash, tx.nonce, tx.account_id);
-    let eth_signature = eth_signer.sign_message(eth_sign_msg.as_bytes())?;
+    let eth_signature = match layer1_signer.sign_message(eth_sign_msg.as_bytes()).await? {
+        TxLayer1Signature::EthereumSignature(signature) => signature,
+        _ => return Err(EthSignerError::InvalidEthSigner.into()),
+    };
     tx.eth_auth_data = ChangePubKeyAuthData::EthECDSA { eth_signature };
 
     Ok(TxSignature {
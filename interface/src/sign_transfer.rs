@@ -1,106 +1,51 @@
 use crate::error::SignError;
-#[cfg(feature = "web")]
-use zklink_sdk_signers::eth_signer::json_rpc_signer::JsonRpcSigner;
-#[cfg(not(feature = "web"))]
-use zklink_sdk_signers::eth_signer::pk_signer::EthSigner;
-#[cfg(feature = "web")]
-use zklink_sdk_signers::starknet_signer::starknet_json_rpc_signer::StarknetJsonRpcSigner;
+use crate::layer1_signer::{Layer1Signer, Layer1TypedData};
+use crate::L1Type;
 use zklink_sdk_signers::starknet_signer::typed_data::message::TypedDataMessage;
-#[cfg(not(feature = "web"))]
-use zklink_sdk_signers::starknet_signer::typed_data::TypedData;
-#[cfg(not(feature = "web"))]
-use zklink_sdk_signers::starknet_signer::StarkSigner;
 use zklink_sdk_signers::zklink_signer::pk_signer::ZkLinkSigner;
 use zklink_sdk_types::basic_types::GetBytes;
 use zklink_sdk_types::prelude::TxSignature;
 use zklink_sdk_types::tx_type::transfer::Transfer;
 
-#[cfg(not(feature = "web"))]
-pub fn sign_eth_transfer(
-    eth_signer: &EthSigner,
-    zklink_syner: &ZkLinkSigner,
-    mut tx: Transfer,
-    token_symbol: &str,
-) -> Result<TxSignature, SignError> {
-    tx.signature = zklink_syner.sign_musig(&tx.get_bytes())?;
-    let message = tx.get_eth_sign_msg(token_symbol).as_bytes().to_vec();
-    let eth_signature = eth_signer.sign_message(&message)?;
-
-    Ok(TxSignature {
-        tx: tx.into(),
-        layer1_signature: Some(eth_signature.into()),
-    })
-}
-
-#[cfg(feature = "web")]
-pub async fn sign_eth_transfer(
-    eth_signer: &JsonRpcSigner,
-    zklink_signer: &ZkLinkSigner,
-    mut tx: Transfer,
-    token_symbol: &str,
-) -> Result<TxSignature, SignError> {
-    tx.signature = zklink_signer.sign_musig(&tx.get_bytes())?;
-    let message = tx.get_eth_sign_msg(token_symbol).as_bytes().to_vec();
-    let eth_signature = eth_signer.sign_message(&message).await?;
-
-    Ok(TxSignature {
-        tx: tx.into(),
-        layer1_signature: Some(eth_signature.into()),
-    })
-}
-
-#[cfg(feature = "web")]
-pub async fn sign_starknet_transfer(
-    starknet_signer: &StarknetJsonRpcSigner,
+/// Sign the transfer by the zklink signer, the layer1 signer signs the message of the transfer
+/// (ethereum) or the typed data of it (starknet).
+pub async fn sign_transfer(
+    layer1_signer: &dyn Layer1Signer,
     zklink_signer: &ZkLinkSigner,
     mut tx: Transfer,
     token_symbol: &str,
 ) -> Result<TxSignature, SignError> {
     tx.signature = zklink_signer.sign_musig(&tx.get_bytes())?;
-    let message = tx.get_starknet_sign_msg(token_symbol);
-    let starknet_signature = starknet_signer
-        .sign_message(TypedDataMessage::Transaction { message })
-        .await?;
+    let layer1_signature = match layer1_signer.l1_type() {
+        L1Type::Eth => {
+            let message = tx.get_eth_sign_msg(token_symbol);
+            layer1_signer.sign_message(message.as_bytes()).await?
+        }
+        L1Type::Starknet => {
+            let message = tx.get_starknet_sign_msg(token_symbol);
+            let typed_data = Layer1TypedData::Starknet(TypedDataMessage::Transaction { message });
+            layer1_signer.sign_typed_data(&typed_data).await?
+        }
+    };
 
     Ok(TxSignature {
         tx: tx.into(),
-        layer1_signature: Some(starknet_signature.into()),
+        layer1_signature: Some(layer1_signature),
     })
 }
 
-#[cfg(not(feature = "web"))]
-pub fn sign_starknet_transfer(
-    signer: &StarkSigner,
-    zklink_signer: &ZkLinkSigner,
-    mut tx: Transfer,
-    token_symbol: &str,
-    chain_id: &str,
-    addr: &str,
-) -> Result<TxSignature, SignError> {
-    tx.signature = zklink_signer.sign_musig(&tx.get_bytes())?;
-    let message = tx.get_starknet_sign_msg(token_symbol);
-    let typed_data = TypedData::new(
-        TypedDataMessage::Transaction { message },
-        chain_id.to_string(),
-    );
-    let starknet_signature = signer.sign_message(&typed_data, addr)?;
-
-    Ok(TxSignature {
-        tx: tx.into(),
-        layer1_signature: Some(starknet_signature.into()),
-    })
-}
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::str::FromStr;
+    use zklink_sdk_signers::eth_signer::EthSigner;
     use zklink_sdk_types::basic_types::BigUint;
     use zklink_sdk_types::prelude::*;
 
-    #[test]
-    fn test_sign_transfer() {
+    #[tokio::test]
+    async fn test_sign_transfer() {
         let eth_pk = H256::repeat_byte(5);
-        let eth_signer = eth_pk.into();
+        let eth_signer: EthSigner = eth_pk.into();
         let zk_signer = ZkLinkSigner::new_from_eth_signer(&eth_signer).unwrap();
         let builder = TransferBuilder {
             account_id: AccountId(1),
@@ -116,7 +61,9 @@ mod tests {
         };
         let tx = builder.build();
 
-        let signature = sign_eth_transfer(&eth_signer, &zk_signer, tx, "USD").unwrap();
+        let signature = sign_transfer(&eth_signer, &zk_signer, tx, "USD")
+            .await
+            .unwrap();
         let eth_sign = signature
             .layer1_signature
             .expect("transfer must has eth signature");
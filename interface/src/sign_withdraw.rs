@@ -1,103 +1,49 @@
 use crate::error::SignError;
-#[cfg(feature = "web")]
-use zklink_sdk_signers::eth_signer::json_rpc_signer::JsonRpcSigner;
-#[cfg(not(feature = "web"))]
-use zklink_sdk_signers::eth_signer::pk_signer::EthSigner;
-#[cfg(feature = "web")]
-use zklink_sdk_signers::starknet_signer::starknet_json_rpc_signer::StarknetJsonRpcSigner;
+use crate::layer1_signer::{Layer1Signer, Layer1TypedData};
+use crate::L1Type;
 use zklink_sdk_signers::starknet_signer::typed_data::message::TypedDataMessage;
-#[cfg(not(feature = "web"))]
-use zklink_sdk_signers::starknet_signer::typed_data::TypedData;
-#[cfg(not(feature = "web"))]
-use zklink_sdk_signers::starknet_signer::StarkSigner;
 use zklink_sdk_signers::zklink_signer::pk_signer::ZkLinkSigner;
 use zklink_sdk_types::prelude::TxSignature;
 use zklink_sdk_types::tx_type::withdraw::Withdraw;
 use zklink_sdk_types::tx_type::ZkSignatureTrait;
-#[cfg(not(feature = "web"))]
-pub fn sign_starknet_withdraw(
-    signer: &StarkSigner,
-    zklink_singer: &ZkLinkSigner,
-    mut tx: Withdraw,
-    l2_source_token_symbol: &str,
-    chain_id: &str,
-    addr: &str,
-) -> Result<TxSignature, SignError> {
-    tx.sign(zklink_singer)?;
-    let message = tx.get_starknet_sign_msg(l2_source_token_symbol);
-    let typed_data = TypedData::new(
-        TypedDataMessage::Transaction { message },
-        chain_id.to_string(),
-    );
-    let signature = signer.sign_message(&typed_data, addr)?;
 
-    Ok(TxSignature {
-        tx: tx.into(),
-        layer1_signature: Some(signature.into()),
-    })
-}
-
-#[cfg(not(feature = "web"))]
-pub fn sign_eth_withdraw(
-    eth_signer: &EthSigner,
+/// Sign the withdraw by the zklink signer, the layer1 signer signs the message of the withdraw
+/// (ethereum) or the typed data of it (starknet).
+pub async fn sign_withdraw(
+    layer1_signer: &dyn Layer1Signer,
     zklink_singer: &ZkLinkSigner,
     mut tx: Withdraw,
     l2_source_token_symbol: &str,
 ) -> Result<TxSignature, SignError> {
     tx.sign(zklink_singer)?;
-    let message = tx.get_eth_sign_msg(l2_source_token_symbol);
-    let signature = eth_signer.sign_message(message.as_bytes())?;
-
-    Ok(TxSignature {
-        tx: tx.into(),
-        layer1_signature: Some(signature.into()),
-    })
-}
-
-#[cfg(feature = "web")]
-pub async fn sign_eth_withdraw(
-    eth_signer: &JsonRpcSigner,
-    zklink_singer: &ZkLinkSigner,
-    mut tx: Withdraw,
-    l2_source_token_symbol: &str,
-) -> Result<TxSignature, SignError> {
-    tx.sign(zklink_singer)?;
-    let message = tx.get_eth_sign_msg(l2_source_token_symbol);
-    let eth_signature = eth_signer.sign_message(message.as_bytes()).await?;
+    let layer1_signature = match layer1_signer.l1_type() {
+        L1Type::Eth => {
+            let message = tx.get_eth_sign_msg(l2_source_token_symbol);
+            layer1_signer.sign_message(message.as_bytes()).await?
+        }
+        L1Type::Starknet => {
+            let message = tx.get_starknet_sign_msg(l2_source_token_symbol);
+            let typed_data = Layer1TypedData::Starknet(TypedDataMessage::Transaction { message });
+            layer1_signer.sign_typed_data(&typed_data).await?
+        }
+    };
 
     Ok(TxSignature {
         tx: tx.into(),
-        layer1_signature: Some(eth_signature.into()),
+        layer1_signature: Some(layer1_signature),
     })
 }
 
-#[cfg(feature = "web")]
-pub async fn sign_starknet_withdraw(
-    stark_signer: &StarknetJsonRpcSigner,
-    zklink_singer: &ZkLinkSigner,
-    mut tx: Withdraw,
-    l2_source_token_symbol: &str,
-) -> Result<TxSignature, SignError> {
-    tx.sign(zklink_singer)?;
-    let message = tx.get_starknet_sign_msg(l2_source_token_symbol);
-    let stark_signature = stark_signer
-        .sign_message(TypedDataMessage::Transaction { message })
-        .await?;
-
-    Ok(TxSignature {
-        tx: tx.into(),
-        layer1_signature: Some(stark_signature.into()),
-    })
-}
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::str::FromStr;
+    use zklink_sdk_signers::eth_signer::EthSigner;
     use zklink_sdk_types::basic_types::BigUint;
     use zklink_sdk_types::prelude::*;
 
-    #[test]
-    fn test_sign_withdraw() {
+    #[tokio::test]
+    async fn test_sign_withdraw() {
         let eth_pk = H256::repeat_byte(5);
         let builder = WithdrawBuilder {
             account_id: AccountId(1),
@@ -116,9 +62,11 @@ mod tests {
             timestamp: TimeStamp(1649749979),
         };
         let tx = builder.build();
-        let eth_signer = eth_pk.into();
+        let eth_signer: EthSigner = eth_pk.into();
         let zk_signer = ZkLinkSigner::new_from_eth_signer(&eth_signer).unwrap();
-        let signature = sign_eth_withdraw(&eth_signer, &zk_signer, tx, "USD").unwrap();
+        let signature = sign_withdraw(&eth_signer, &zk_signer, tx, "USD")
+            .await
+            .unwrap();
 
         // let eth_sign = signature
         //     .layer1_signature
@@ -1,10 +1,11 @@
 use crate::error::SignError;
+use crate::layer1_signer::{Layer1Signer, StarknetPkSigner};
 use crate::sign_auto_deleveraging::sign_auto_deleveraging;
 use crate::sign_forced_exit::sign_forced_exit;
 use crate::sign_liquidation::sign_liquidation;
-use crate::sign_transfer::{sign_eth_transfer, sign_starknet_transfer};
-use crate::sign_withdraw::{sign_eth_withdraw, sign_starknet_withdraw};
-use zklink_sdk_types::prelude::{PubKeyHash, TxSignature};
+use crate::sign_transfer::sign_transfer;
+use crate::sign_withdraw::sign_withdraw;
+use zklink_sdk_types::prelude::{PubKeyHash, TxLayer1Signature, TxSignature};
 
 use crate::sign_change_pubkey::{
     do_sign_change_pubkey_with_create2data_auth, do_sign_change_pubkey_with_eth_ecdsa_auth,
@@ -13,9 +14,11 @@ use crate::sign_change_pubkey::{
 use crate::sign_contract_matching::sign_contract_matching;
 use crate::sign_funding::sign_funding;
 use crate::sign_order_matching::sign_order_matching;
+pub use crate::L1Type;
 use cfg_if::cfg_if;
-#[cfg(feature = "ffi")]
 use std::sync::Arc;
+#[cfg(not(any(feature = "ffi", target_arch = "wasm32")))]
+use zklink_sdk_provider::token_registry::TokenRegistry;
 use zklink_sdk_signers::eth_signer::error::EthSignerError;
 use zklink_sdk_signers::eth_signer::pk_signer::EthSigner;
 use zklink_sdk_signers::starknet_signer::error::StarkSignerError;
@@ -42,19 +45,12 @@ cfg_if! {
     }
 }
 
-pub enum Layer1Sginer {
-    EthSigner(EthSigner),
-    StarknetSigner(StarkSigner),
-}
-
-pub enum L1Type {
-    Eth,
-    Starknet,
-}
-
+/// The signer of the account, the txs are signed by the zklink signer and the layer1
+/// signatures (the `Transfer`, `Withdraw` and the ECDSA auth of `ChangePubKey`) are signed by
+/// any [`Layer1Signer`], e.g. the private key signers, the browser wallets or a KMS.
 pub struct Signer {
     zklink_signer: ZkLinkSigner,
-    layer1_signer: Layer1Sginer,
+    layer1_signer: Arc<dyn Layer1Signer>,
 }
 
 pub enum L1SignerType {
@@ -64,13 +60,13 @@ pub enum L1SignerType {
 
 impl Signer {
     pub fn new(private_key: &str, l1_signer_type: L1SignerType) -> Result<Self, SignError> {
-        let (zklink_signer, layer1_signer) = match l1_signer_type {
+        let (zklink_signer, layer1_signer): (_, Arc<dyn Layer1Signer>) = match l1_signer_type {
             L1SignerType::Eth { .. } => {
                 let eth_signer = EthSigner::try_from(private_key)
                     .map_err(|_| EthSignerError::InvalidEthSigner)?;
                 (
                     ZkLinkSigner::new_from_hex_eth_signer(private_key)?,
-                    Layer1Sginer::EthSigner(eth_signer),
+                    Arc::new(eth_signer),
                 )
             }
             L1SignerType::Starknet {
@@ -80,14 +76,36 @@ impl Signer {
                     .map_err(|_| StarkSignerError::InvalidStarknetSigner)?;
                 (
                     ZkLinkSigner::new_from_hex_stark_signer(private_key, &address, &chain_id)?,
-                    Layer1Sginer::StarknetSigner(stark_signer),
+                    Arc::new(StarknetPkSigner::new(stark_signer, chain_id, address)),
                 )
             }
         };
-        Ok(Self {
+        Ok(Self::new_with_layer1_signer(zklink_signer, layer1_signer))
+    }
+
+    pub fn new_with_layer1_signer(
+        zklink_signer: ZkLinkSigner,
+        layer1_signer: Arc<dyn Layer1Signer>,
+    ) -> Self {
+        Self {
             zklink_signer,
             layer1_signer,
-        })
+        }
+    }
+
+    /// Create the zklink signer from the signature of the layer1 signer, the same as
+    /// [`ZkLinkSigner::new_from_eth_signer`] and [`ZkLinkSigner::new_from_starknet_signer`].
+    pub async fn new_from_layer1_signer(
+        layer1_signer: Arc<dyn Layer1Signer>,
+    ) -> Result<Self, SignError> {
+        let seed = zklink_signer_seed(&*layer1_signer).await?;
+        let zklink_signer = ZkLinkSigner::new_from_seed(&seed)?;
+        Ok(Self::new_with_layer1_signer(zklink_signer, layer1_signer))
+    }
+
+    #[inline]
+    pub fn layer1_signer(&self) -> &Arc<dyn Layer1Signer> {
+        &self.layer1_signer
     }
 
     #[inline]
@@ -121,85 +139,101 @@ impl Signer {
         do_sign_change_pubkey_with_onchain_auth_data(tx, &self.zklink_signer)
     }
 
-    #[cfg(not(feature = "web"))]
-    #[inline]
+    #[cfg(not(feature = "ffi"))]
+    pub async fn sign_change_pubkey_with_eth_ecdsa_auth(
+        &self,
+        tx: ChangePubKey,
+    ) -> Result<TxSignature, SignError> {
+        do_sign_change_pubkey_with_eth_ecdsa_auth(&*self.layer1_signer, &self.zklink_signer, tx)
+            .await
+    }
+
+    #[cfg(not(feature = "ffi"))]
+    pub async fn sign_transfer(
+        &self,
+        tx: Transfer,
+        token_symbol: &str,
+    ) -> Result<TxSignature, SignError> {
+        sign_transfer(&*self.layer1_signer, &self.zklink_signer, tx, token_symbol).await
+    }
+
+    #[cfg(not(feature = "ffi"))]
+    pub async fn sign_withdraw(
+        &self,
+        tx: Withdraw,
+        l2_source_token_symbol: &str,
+    ) -> Result<TxSignature, SignError> {
+        sign_withdraw(
+            &*self.layer1_signer,
+            &self.zklink_signer,
+            tx,
+            l2_source_token_symbol,
+        )
+        .await
+    }
+
+    /// Sign the `Transfer` with the symbol of the token resolved by the registry.
+    #[cfg(not(any(feature = "ffi", target_arch = "wasm32")))]
+    pub async fn sign_transfer_with_registry(
+        &self,
+        tx: Transfer,
+        tokens: &TokenRegistry,
+    ) -> Result<TxSignature, SignError> {
+        let token_symbol = tokens.symbol(tx.token)?;
+        self.sign_transfer(tx, token_symbol).await
+    }
+
+    /// Sign the `Withdraw` with the symbol of the `l2_source_token` resolved by the registry.
+    #[cfg(not(any(feature = "ffi", target_arch = "wasm32")))]
+    pub async fn sign_withdraw_with_registry(
+        &self,
+        tx: Withdraw,
+        tokens: &TokenRegistry,
+    ) -> Result<TxSignature, SignError> {
+        let l2_source_token_symbol = tokens.symbol(tx.l2_source_token)?;
+        self.sign_withdraw(tx, l2_source_token_symbol).await
+    }
+
+    /// The layer1 signers of the ffi signer are the private key signers, they sign without
+    /// waiting.
+    #[cfg(feature = "ffi")]
     pub fn sign_change_pubkey_with_eth_ecdsa_auth(
         &self,
         tx: ChangePubKey,
     ) -> Result<TxSignature, SignError> {
-        #[cfg(feature = "ffi")]
-        let tx = (*tx).clone();
-        if let Layer1Sginer::EthSigner(signer) = &self.layer1_signer {
-            do_sign_change_pubkey_with_eth_ecdsa_auth(signer, &self.zklink_signer, tx)
-        } else {
-            Err(EthSignerError::InvalidEthSigner.into())
-        }
+        futures::executor::block_on(do_sign_change_pubkey_with_eth_ecdsa_auth(
+            &*self.layer1_signer,
+            &self.zklink_signer,
+            (*tx).clone(),
+        ))
     }
 
-    #[cfg(not(feature = "web"))]
+    #[cfg(feature = "ffi")]
     pub fn sign_transfer(
         &self,
         tx: Transfer,
         token_symbol: &str,
-        starknet_chain_id: Option<String>,
-        starknet_addr: Option<String>,
     ) -> Result<TxSignature, SignError> {
-        #[cfg(feature = "ffi")]
-        let tx = (*tx).clone();
-        match &self.layer1_signer {
-            Layer1Sginer::EthSigner(signer) => {
-                sign_eth_transfer(signer, &self.zklink_signer, tx, token_symbol)
-            }
-            Layer1Sginer::StarknetSigner(signer) => {
-                let chain_id = starknet_chain_id.ok_or(SignError::StarkSigningError(
-                    StarkSignerError::SignError("Invalid starknet_chain_id".to_string()),
-                ))?;
-                let addr = starknet_addr.ok_or(SignError::StarkSigningError(
-                    StarkSignerError::SignError("Invalid starknet_addr".to_string()),
-                ))?;
-                sign_starknet_transfer(
-                    signer,
-                    &self.zklink_signer,
-                    tx,
-                    token_symbol,
-                    &chain_id,
-                    &addr,
-                )
-            }
-        }
+        futures::executor::block_on(sign_transfer(
+            &*self.layer1_signer,
+            &self.zklink_signer,
+            (*tx).clone(),
+            token_symbol,
+        ))
     }
 
-    #[cfg(not(feature = "web"))]
+    #[cfg(feature = "ffi")]
     pub fn sign_withdraw(
         &self,
         tx: Withdraw,
         l2_source_token_symbol: &str,
-        starknet_chain_id: Option<String>,
-        starknet_addr: Option<String>,
     ) -> Result<TxSignature, SignError> {
-        #[cfg(feature = "ffi")]
-        let tx = (*tx).clone();
-        match &self.layer1_signer {
-            Layer1Sginer::EthSigner(signer) => {
-                sign_eth_withdraw(signer, &self.zklink_signer, tx, l2_source_token_symbol)
-            }
-            Layer1Sginer::StarknetSigner(signer) => {
-                let chain_id = starknet_chain_id.ok_or(SignError::StarkSigningError(
-                    StarkSignerError::SignError("Invalid starknet_chain_id".to_string()),
-                ))?;
-                let addr = starknet_addr.ok_or(SignError::StarkSigningError(
-                    StarkSignerError::SignError("Invalid starknet_addr".to_string()),
-                ))?;
-                sign_starknet_withdraw(
-                    signer,
-                    &self.zklink_signer,
-                    tx,
-                    l2_source_token_symbol,
-                    &chain_id,
-                    &addr,
-                )
-            }
-        }
+        futures::executor::block_on(sign_withdraw(
+            &*self.layer1_signer,
+            &self.zklink_signer,
+            (*tx).clone(),
+            l2_source_token_symbol,
+        ))
     }
 
     pub fn sign_forced_exit(&self, tx: ForcedExit) -> Result<TxSignature, SignError> {
@@ -260,3 +294,19 @@ impl Signer {
         Ok(contract)
     }
 }
+
+/// The seed of the zklink signer, which is the layer1 signature of the fixed message.
+pub(crate) async fn zklink_signer_seed(
+    layer1_signer: &dyn Layer1Signer,
+) -> Result<Vec<u8>, SignError> {
+    let message = match layer1_signer.l1_type() {
+        L1Type::Eth => ZkLinkSigner::SIGN_MESSAGE,
+        L1Type::Starknet => ZkLinkSigner::STARKNET_SIGN_MESSAGE,
+    };
+    let seed = match layer1_signer.sign_message(message.as_bytes()).await? {
+        TxLayer1Signature::EthereumSignature(signature) => signature.serialize_packed().to_vec(),
+        TxLayer1Signature::EIP1271Signature(signature) => signature.0,
+        TxLayer1Signature::StarkSignature(signature) => signature.signature.to_bytes_be().to_vec(),
+    };
+    Ok(seed)
+}
@@ -0,0 +1,134 @@
+#![cfg(not(any(feature = "ffi", feature = "web", target_arch = "wasm32")))]
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+    use zklink_sdk_interface::client::{TransferParams, WithdrawParams, ZkLinkClient};
+    use zklink_sdk_interface::error::ZkLinkClientError;
+    use zklink_sdk_interface::signer::L1SignerType;
+    use zklink_sdk_provider::mock_node::{MockAccount, MockZkLinkNode};
+    use zklink_sdk_provider::network::Network;
+    use zklink_sdk_provider::response::{ChainTokenResp, TokenResp};
+    use zklink_sdk_provider::token_registry::TokenRegistryError;
+    use zklink_sdk_provider::{ZkLinkRpcClient, ZkLinkRpcProvider};
+    use zklink_sdk_signers::eth_signer::pk_signer::EthSigner;
+    use zklink_sdk_signers::zklink_signer::ZkLinkSigner;
+    use zklink_sdk_types::basic_types::{
+        AccountId, BigUint, ChainId, SubAccountId, TokenId, ZkLinkAddress,
+    };
+
+    const PRIVATE_KEY: &str = "be725250b123a39dab5b7579334d5888987c72a58f4508062545fe6e08ca94f4";
+    const TO_ADDRESS: &str = "0xAFAFf3aD1a0425D792432D9eCD1c3e26Ef2C42E9";
+
+    async fn start_node() -> (MockZkLinkNode, ZkLinkRpcProvider) {
+        let node = MockZkLinkNode::new();
+        node.add_token(TokenResp {
+            id: TokenId(18),
+            symbol: "USDC".to_string(),
+            usd_price: Default::default(),
+            chains: HashMap::new(),
+        });
+        let eth_signer = EthSigner::try_from(PRIVATE_KEY).unwrap();
+        let mut account = MockAccount::new(AccountId(10), eth_signer.get_address().into());
+        let signer = ZkLinkSigner::new_from_hex_eth_signer(PRIVATE_KEY).unwrap();
+        account.pub_key_hash = signer.public_key().public_key_hash();
+        node.add_account(account);
+        node.set_balance(
+            AccountId(10),
+            SubAccountId(1),
+            TokenId(18),
+            BigUint::from(10000u32),
+        );
+        let (addr, handle) = node.start_rpc_server("127.0.0.1:0").await.unwrap();
+        tokio::spawn(handle.stopped());
+        let provider =
+            ZkLinkRpcProvider::new(Network::custom(format!("http://{addr}")), None).unwrap();
+        (node, provider)
+    }
+
+    fn transfer_params(amount: u32) -> TransferParams {
+        TransferParams {
+            from_sub_account_id: SubAccountId(1),
+            to_address: ZkLinkAddress::from_str(TO_ADDRESS).unwrap(),
+            to_sub_account_id: SubAccountId(1),
+            token: TokenId(18),
+            amount: BigUint::from(amount),
+            fee: BigUint::from(3u32),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_transfer() {
+        let (node, provider) = start_node().await;
+        let client = ZkLinkClient::new(PRIVATE_KEY, L1SignerType::Eth, provider).unwrap();
+        assert_eq!(client.account_id().await.unwrap(), AccountId(10));
+
+        let tx_hash = client.transfer(transfer_params(1000)).await.unwrap();
+        let tx_resp = client.provider().tx_info(tx_hash, false).await.unwrap();
+        assert!(tx_resp.receipt.success);
+        // the next nonce is handed out locally
+        let tx_hash = client.transfer(transfer_params(1000)).await.unwrap();
+        let tx_resp = client.provider().tx_info(tx_hash, false).await.unwrap();
+        assert!(tx_resp.receipt.success);
+
+        let account = node.account(AccountId(10)).unwrap();
+        assert_eq!(
+            account.balance(SubAccountId(1), TokenId(18)),
+            BigUint::from(10000u32 - 2 * 1003)
+        );
+        assert_eq!(node.txs().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_client_token_not_found() {
+        let (_node, provider) = start_node().await;
+        let client = ZkLinkClient::new(PRIVATE_KEY, L1SignerType::Eth, provider).unwrap();
+        let mut params = transfer_params(1000);
+        params.token = TokenId(1);
+        assert!(matches!(
+            client.transfer(params).await,
+            Err(ZkLinkClientError::TokenError(
+                TokenRegistryError::TokenNotFound(TokenId(1))
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_client_withdraw_inexact_amount() {
+        let (node, provider) = start_node().await;
+        node.add_token(TokenResp {
+            id: TokenId(17),
+            symbol: "USDT".to_string(),
+            usd_price: Default::default(),
+            chains: HashMap::from([(
+                ChainId(1),
+                ChainTokenResp {
+                    chain_id: ChainId(1),
+                    address: ZkLinkAddress::default(),
+                    decimals: 6,
+                    fast_withdraw: false,
+                },
+            )]),
+        });
+        let client = ZkLinkClient::new(PRIVATE_KEY, L1SignerType::Eth, provider).unwrap();
+        // 1 wei of layer2 could not be withdrawn to the layer1 token of 6 decimals
+        let params = WithdrawParams {
+            sub_account_id: SubAccountId(1),
+            to_chain_id: ChainId(1),
+            to_address: ZkLinkAddress::from_str(TO_ADDRESS).unwrap(),
+            l2_source_token: TokenId(17),
+            l1_target_token: TokenId(17),
+            amount: BigUint::from(1u32),
+            fee: BigUint::from(3u32),
+            withdraw_to_l1: true,
+            withdraw_fee_ratio: 0,
+        };
+        assert!(matches!(
+            client.withdraw(params).await,
+            Err(ZkLinkClientError::TokenError(
+                TokenRegistryError::InexactAmount(_, 6)
+            ))
+        ));
+        assert!(node.txs().is_empty());
+    }
+}
@@ -0,0 +1,20 @@
+//! Export the JSON Schema and the TypeScript definitions of the txs and the rpc responses.
+//!
+//! `cargo run -p zklink_sdk_provider --features schemars --example export_schema -- <out_dir>`
+use std::path::PathBuf;
+use zklink_sdk_provider::schema::{json_schema, typescript_definitions};
+
+fn main() -> std::io::Result<()> {
+    let out_dir = PathBuf::from(std::env::args().nth(1).unwrap_or_else(|| "schema".into()));
+    std::fs::create_dir_all(&out_dir)?;
+
+    let schema = serde_json::to_string_pretty(&json_schema())?;
+    std::fs::write(out_dir.join("zklink_sdk.schema.json"), schema)?;
+    let ts = format!(
+        "// Generated by the `export_schema` example of zklink_sdk_provider, do not edit.\n\n{}",
+        typescript_definitions()
+    );
+    std::fs::write(out_dir.join("zklink_sdk.d.ts"), ts)?;
+    println!("exported the schema to {}", out_dir.display());
+    Ok(())
+}
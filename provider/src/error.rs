@@ -1,3 +1,6 @@
+#[cfg(not(target_arch = "wasm32"))]
+use jsonrpsee::core::ClientError;
+use jsonrpsee::types::ErrorObjectOwned;
 use thiserror::Error;
 use wasm_bindgen::JsValue;
 
@@ -17,6 +20,8 @@ pub enum RpcError {
     ParseJsonError(String),
     #[error("Get error result: {0}")]
     GetErrorResult(String),
+    #[error("{0}")]
+    NodeError(#[from] ZkLinkNodeError),
 }
 
 impl From<RpcError> for JsValue {
@@ -24,3 +29,155 @@ impl From<RpcError> for JsValue {
         JsValue::from_str(&format!("error: {error}"))
     }
 }
+
+/// The errors returned by the zklink node, either as the error of a rpc call or as the
+/// `fail_reason` of an executed tx. The original message is kept in every variant.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum ZkLinkNodeError {
+    #[error("{0}")]
+    InsufficientBalance(String),
+    #[error("{0}")]
+    InvalidNonce(String),
+    #[error("{0}")]
+    InvalidSignature(String),
+    #[error("{0}")]
+    AccountNotFound(String),
+    #[error("{0}")]
+    OrderSlotConflict(String),
+    #[error("{0}")]
+    TxNotFound(String),
+    #[error("Node error {code:?}: {message}")]
+    Other { code: Option<i32>, message: String },
+}
+
+impl ZkLinkNodeError {
+    /// Classify the error returned by the node, the code is only kept in [`Self::Other`].
+    ///
+    /// The zklink node doesn't publish a table of its rpc error codes, so the errors are
+    /// classified by [`Self::classify_by_message`] until the codes are documented.
+    pub fn new(code: Option<i32>, message: impl Into<String>) -> Self {
+        Self::classify_by_message(code, message.into())
+    }
+
+    /// The heuristic to classify the errors by the keywords of the message.
+    ///
+    /// The order slot and the order nonce are checked before the account nonce, the messages
+    /// about the orders mention the order nonce but must not resync the account nonce.
+    pub fn classify_by_message(code: Option<i32>, message: String) -> Self {
+        let lower = message.to_lowercase();
+        if lower.contains("slot") || lower.contains("order nonce") {
+            Self::OrderSlotConflict(message)
+        } else if lower.contains("nonce") {
+            Self::InvalidNonce(message)
+        } else if lower.contains("insufficient") {
+            Self::InsufficientBalance(message)
+        } else if lower.contains("signature") {
+            Self::InvalidSignature(message)
+        } else if lower.contains("account") && lower.contains("not") {
+            Self::AccountNotFound(message)
+        } else if lower.contains("tx not found") || lower.contains("transaction not found") {
+            Self::TxNotFound(message)
+        } else {
+            Self::Other { code, message }
+        }
+    }
+
+    /// Decode the `fail_reason` of the tx receipt.
+    pub fn from_fail_reason(reason: &str) -> Self {
+        Self::new(None, reason)
+    }
+
+    /// Decode the error of a rpc call, `None` if it's not returned by the node, e.g. a network
+    /// error.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_client_error(error: &ClientError) -> Option<Self> {
+        match error {
+            ClientError::Call(error) => Some(error.into()),
+            _ => None,
+        }
+    }
+
+    /// The error could be fixed by resyncing the nonces from the node.
+    pub fn is_nonce_error(&self) -> bool {
+        matches!(self, Self::InvalidNonce(_))
+    }
+}
+
+/// The error of a rpc call, the errors returned by the node are decoded into
+/// [`ZkLinkNodeError`].
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Error)]
+pub enum ProviderError {
+    #[error("Rpc error: {0}")]
+    RpcError(ClientError),
+    #[error("{0}")]
+    NodeError(#[from] ZkLinkNodeError),
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl From<ClientError> for ProviderError {
+    fn from(error: ClientError) -> Self {
+        match ZkLinkNodeError::from_client_error(&error) {
+            Some(error) => Self::NodeError(error),
+            None => Self::RpcError(error),
+        }
+    }
+}
+
+impl From<&ErrorObjectOwned> for ZkLinkNodeError {
+    fn from(error: &ErrorObjectOwned) -> Self {
+        Self::new(Some(error.code()), error.message())
+    }
+}
+
+impl From<ErrorObjectOwned> for ZkLinkNodeError {
+    fn from(error: ErrorObjectOwned) -> Self {
+        (&error).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_node_error_decode() {
+        assert!(
+            ZkLinkNodeError::from_fail_reason("Nonce mismatch: expected 1, got 0").is_nonce_error()
+        );
+        assert_eq!(
+            ZkLinkNodeError::from_fail_reason("Insufficient balance"),
+            ZkLinkNodeError::InsufficientBalance("Insufficient balance".to_string())
+        );
+        assert_eq!(
+            ZkLinkNodeError::from_fail_reason("Order nonce of slot 1 is used"),
+            ZkLinkNodeError::OrderSlotConflict("Order nonce of slot 1 is used".to_string())
+        );
+        // the order nonce is not the account nonce
+        assert_eq!(
+            ZkLinkNodeError::from_fail_reason("Order nonce is too low"),
+            ZkLinkNodeError::OrderSlotConflict("Order nonce is too low".to_string())
+        );
+        // the codes are not trusted, the message decides
+        let error = ErrorObjectOwned::owned(101, "Slot 1 is used", None::<()>);
+        assert!(!ZkLinkNodeError::from(error).is_nonce_error());
+        let error = ErrorObjectOwned::owned(-32000, "Tx not found", None::<()>);
+        assert_eq!(
+            ZkLinkNodeError::from(error),
+            ZkLinkNodeError::TxNotFound("Tx not found".to_string())
+        );
+        let error = ErrorObjectOwned::owned(-32000, "Account not found", None::<()>);
+        assert_eq!(
+            ZkLinkNodeError::from(error),
+            ZkLinkNodeError::AccountNotFound("Account not found".to_string())
+        );
+        let error = ErrorObjectOwned::owned(-32000, "Internal error", None::<()>);
+        assert_eq!(
+            ZkLinkNodeError::from(error),
+            ZkLinkNodeError::Other {
+                code: Some(-32000),
+                message: "Internal error".to_string()
+            }
+        );
+    }
+}
@@ -1,18 +1,53 @@
 pub mod error;
+#[cfg(all(feature = "mock", not(target_arch = "wasm32")))]
+pub mod mock_node;
 pub mod network;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod nonce_manager;
+pub mod order_slots;
 pub mod response;
 #[cfg(not(target_arch = "wasm32"))]
 mod rpc;
+#[cfg(feature = "schemars")]
+pub mod schema;
+pub mod token_registry;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod tx_tracker;
 pub mod web_socket;
 
 #[cfg(not(any(feature = "ffi", target_arch = "wasm32")))]
 mod not_ffi {
     pub use crate::rpc::{ZkLinkRpcClient, ZkLinkRpcServer};
 
+    use crate::error::ProviderError;
     use crate::network::Network;
+    use crate::response::*;
+    use crate::web_socket::ws_message::message::request::ClientOffset;
+    use crate::web_socket::ws_message::message::response::TxTopicEvent;
+    use crate::web_socket::ws_message::topic::Topic;
     use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+    use std::collections::HashMap;
     use std::ops::Deref;
     use std::time::Duration;
+    use zklink_sdk_types::basic_types::tx_hash::TxHash;
+    use zklink_sdk_types::basic_types::{
+        AccountId, BlockNumber, ChainId, SubAccountId, TokenId, ZkLinkAddress,
+    };
+    use zklink_sdk_types::prelude::BigUintSerdeWrapper;
+    use zklink_sdk_types::signatures::TxLayer1Signature;
+    use zklink_sdk_types::tx_type::zklink_tx::{ZkLinkTx, ZkLinkTxType};
+
+    /// Call the rpc methods of [`ZkLinkRpcClient`] and decode the errors returned by the node
+    /// into [`ProviderError::NodeError`].
+    macro_rules! rpc_methods {
+        ($(fn $name:ident(&self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)+) => {
+            $(
+                pub async fn $name(&self $(, $arg: $ty)*) -> Result<$ret, ProviderError> {
+                    Ok(ZkLinkRpcClient::$name(&self.0 $(, $arg)*).await?)
+                }
+            )+
+        };
+    }
 
     /// `ZkLinkRpcProvider` is capable of interacting with the ZKLink node via its
     /// JSON RPC interface.
@@ -28,13 +63,90 @@ mod not_ffi {
     }
 
     impl ZkLinkRpcProvider {
-        pub fn new(network: Network, timeout: Option<Duration>) -> Self {
+        /// Fails if the url of the (custom) network is malformed.
+        pub fn new(network: Network, timeout: Option<Duration>) -> Result<Self, ProviderError> {
             let mut builder = HttpClientBuilder::default();
             if let Some(timeout) = timeout {
                 builder = builder.request_timeout(timeout);
             }
-            let client = builder.build(network.url()).unwrap();
-            Self(client)
+            let client = builder
+                .build(network.url())
+                .map_err(ProviderError::RpcError)?;
+            Ok(Self(client))
+        }
+
+        rpc_methods! {
+            fn get_support_chains(&self) -> Vec<ChainResp>;
+            fn tokens(&self) -> HashMap<TokenId, TokenResp>;
+            fn block_info(&self) -> BlockNumberResp;
+            fn block_detail(
+                &self,
+                block_number: Option<BlockNumber>,
+                include_tx: bool,
+                include_update: bool
+            ) -> BlockResp;
+            fn pending_block_detail(
+                &self,
+                last_tx_timestamp_micro: u64,
+                include_tx: bool,
+                include_update: bool,
+                limit: Option<usize>
+            ) -> Vec<TxHashOrDetailResp>;
+            fn block_onchain_detail(&self, block_number: BlockNumber) -> BlockOnChainResp;
+            fn account_info(&self, account_query: AccountQuery) -> AccountInfoResp;
+            fn global_vars_info(&self, sub_account_query: SubAccountId) -> GlobalVarsResp;
+            fn account_balances(
+                &self,
+                account_id: AccountId,
+                sub_account_id: Option<SubAccountId>
+            ) -> SubAccountBalances;
+            fn account_order_slots(
+                &self,
+                account_id: AccountId,
+                sub_account_id: Option<SubAccountId>
+            ) -> SubAccountOrders;
+            fn account_positions(
+                &self,
+                account_id: AccountId,
+                sub_account_id: Option<SubAccountId>
+            ) -> SubAccountPositions;
+            fn token_remain(
+                &self,
+                token_id: TokenId,
+                mapping: bool
+            ) -> HashMap<ChainId, BigUintSerdeWrapper>;
+            fn account_snapshot(
+                &self,
+                account_query: AccountQuery,
+                sub_account_id: Option<SubAccountId>,
+                block_number: Option<BlockNumber>
+            ) -> AccountSnapshotResp;
+            fn tx_info(&self, hash: TxHash, include_update: bool) -> TxResp;
+            fn tx_history(
+                &self,
+                tx_type: ZkLinkTxType,
+                address: ZkLinkAddress,
+                page_index: u64,
+                page_size: u32
+            ) -> Page<ZkLinkTxHistory>;
+            fn tx_withdraw(
+                &self,
+                last_tx_timestamp_micro: u64,
+                max_txs: u32
+            ) -> Vec<WithdrawTxResp>;
+            fn get_websocket_events(
+                &self,
+                topic: Topic,
+                offset: ClientOffset
+            ) -> Vec<TxTopicEvent>;
+            fn get_change_pubkey_chain_id(&self) -> ChainId;
+            fn get_eth_property(&self) -> EthPropertyResp;
+            fn tx_submit(
+                &self,
+                tx: ZkLinkTx,
+                l1_signature: Option<TxLayer1Signature>,
+                oracle_signature: Option<OracleSignature>
+            ) -> TxHash;
         }
     }
 }
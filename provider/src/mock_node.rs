@@ -0,0 +1,682 @@
+//! An in-process zklink node for hermetic tests.
+//!
+//! `MockZkLinkNode` keeps accounts, balances, nonces and the executed txs in memory, serves
+//! them by the same json rpc interface of the real node and pushes the tx results to the
+//! WebSocket topic subscribers.
+use crate::response::*;
+use crate::rpc::ZkLinkRpcServer;
+use crate::web_socket::proto::event::Event;
+use crate::web_socket::proto::request::TopicsRequest;
+use crate::web_socket::proto::response::{TopicsResponse, WsResponse};
+use crate::web_socket::proto::topic::{TopicMethod, TopicTrait};
+use crate::web_socket::ws_message::message::request::ClientOffset;
+use crate::web_socket::ws_message::message::response::{ServerEvent, TxTopicEvent};
+use crate::web_socket::ws_message::topic::Topic;
+use chrono::Utc;
+use futures::{SinkExt, StreamExt};
+use jsonrpsee::core::{async_trait, RpcResult};
+use jsonrpsee::server::{ServerBuilder, ServerHandle};
+use jsonrpsee::types::error::{CALL_EXECUTION_FAILED_CODE, INVALID_PARAMS_CODE};
+use jsonrpsee::types::{ErrorObject, ErrorObjectOwned};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::Message;
+use zklink_sdk_signers::zklink_signer::PubKeyHash;
+use zklink_sdk_types::basic_types::tx_hash::TxHash;
+use zklink_sdk_types::basic_types::{
+    AccountId, BigUint, BlockNumber, ChainId, Nonce, SubAccountId, TokenId, ZkLinkAddress,
+};
+use zklink_sdk_types::prelude::BigUintSerdeWrapper;
+use zklink_sdk_types::signatures::TxLayer1Signature;
+use zklink_sdk_types::tx_type::zklink_tx::{ZkLinkTx, ZkLinkTxType};
+use zklink_sdk_types::tx_type::ZkSignatureTrait;
+
+/// The max events returned by `getWebSocketEvents`.
+const MAX_EVENTS_LIMIT: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct MockAccount {
+    pub id: AccountId,
+    pub address: ZkLinkAddress,
+    pub nonce: Nonce,
+    pub pub_key_hash: PubKeyHash,
+    pub sub_account_nonces: SubAccountNonces,
+    pub balances: HashMap<SubAccountId, HashMap<TokenId, BigUint>>,
+}
+
+impl MockAccount {
+    pub fn new(id: AccountId, address: ZkLinkAddress) -> Self {
+        Self {
+            id,
+            address,
+            nonce: Nonce(0),
+            pub_key_hash: PubKeyHash::zero(),
+            sub_account_nonces: HashMap::new(),
+            balances: HashMap::new(),
+        }
+    }
+
+    pub fn balance(&self, sub_account_id: SubAccountId, token: TokenId) -> BigUint {
+        self.balances
+            .get(&sub_account_id)
+            .and_then(|balances| balances.get(&token))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn add_balance(&mut self, sub_account_id: SubAccountId, token: TokenId, amount: &BigUint) {
+        let balance = self
+            .balances
+            .entry(sub_account_id)
+            .or_default()
+            .entry(token)
+            .or_default();
+        *balance += amount;
+    }
+
+    /// Return false and change nothing if the balance is not enough.
+    fn sub_balance(
+        &mut self,
+        sub_account_id: SubAccountId,
+        token: TokenId,
+        amount: &BigUint,
+    ) -> bool {
+        let balance = self
+            .balances
+            .get_mut(&sub_account_id)
+            .and_then(|balances| balances.get_mut(&token));
+        match balance {
+            Some(balance) if *balance >= *amount => {
+                *balance -= amount;
+                true
+            }
+            Some(_) => false,
+            None => *amount == BigUint::default(),
+        }
+    }
+
+    fn to_resp(&self) -> AccountInfoResp {
+        AccountInfoResp {
+            id: self.id,
+            address: self.address.clone(),
+            nonce: self.nonce,
+            pub_key_hash: self.pub_key_hash,
+            sub_account_nonces: self.sub_account_nonces.clone(),
+        }
+    }
+}
+
+/// A withdrawal to layer1 executed by `Withdraw` or `ForcedExit`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockWithdrawal {
+    pub chain_id: ChainId,
+    pub to: ZkLinkAddress,
+    pub token: TokenId,
+    pub amount: BigUint,
+}
+
+#[derive(Debug, Default)]
+struct NodeState {
+    chains: Vec<ChainResp>,
+    tokens: HashMap<TokenId, TokenResp>,
+    accounts: HashMap<AccountId, MockAccount>,
+    txs: HashMap<TxHash, TxResp>,
+    tx_log: Vec<TxHash>,
+    withdrawals: Vec<MockWithdrawal>,
+    events: HashMap<Topic, Vec<TxTopicEvent>>,
+    block_number: u32,
+}
+
+impl NodeState {
+    fn account_by_query(&self, query: &AccountQuery) -> Option<&MockAccount> {
+        match query {
+            AccountQuery::Id(id) => self.accounts.get(id),
+            AccountQuery::Address(address) => {
+                self.accounts.values().find(|a| a.address == *address)
+            }
+        }
+    }
+
+    /// Return the account of `address`, a new account is created if not exist.
+    fn account_by_address_mut(&mut self, address: &ZkLinkAddress) -> &mut MockAccount {
+        let id = match self.accounts.values().find(|a| a.address == *address) {
+            Some(account) => account.id,
+            None => {
+                let id = AccountId(self.accounts.keys().map(|id| id.0 + 1).max().unwrap_or(0));
+                self.accounts
+                    .insert(id, MockAccount::new(id, address.clone()));
+                id
+            }
+        };
+        self.accounts.get_mut(&id).unwrap()
+    }
+
+    /// Check the signer and the nonce of the tx, return the account and the sub account to be
+    /// notified.
+    fn check_tx(&self, tx: &ZkLinkTx) -> Result<(AccountId, SubAccountId), ErrorObjectOwned> {
+        let (account_id, sub_account_id, signer, expected_signer) = match tx {
+            ZkLinkTx::Transfer(tx) => (
+                tx.account_id,
+                tx.from_sub_account_id,
+                tx.verify_signature(),
+                None,
+            ),
+            ZkLinkTx::Withdraw(tx) => (
+                tx.account_id,
+                tx.sub_account_id,
+                tx.verify_signature(),
+                None,
+            ),
+            ZkLinkTx::ChangePubKey(tx) => (
+                tx.account_id,
+                tx.sub_account_id,
+                tx.verify_signature(),
+                Some(tx.new_pk_hash),
+            ),
+            ZkLinkTx::ForcedExit(tx) => (
+                tx.initiator_account_id,
+                tx.initiator_sub_account_id,
+                tx.verify_signature(),
+                None,
+            ),
+            _ => {
+                return Err(execution_error(format!(
+                    "{:?} is not supported by mock node",
+                    tx.tx_type()
+                )))
+            }
+        };
+        let signer = signer.ok_or_else(|| execution_error("Invalid signature"))?;
+        let account = self
+            .accounts
+            .get(&account_id)
+            .ok_or_else(|| execution_error("Account not found"))?;
+        let expected_signer = expected_signer.unwrap_or(account.pub_key_hash);
+        if signer != expected_signer {
+            return Err(execution_error("Invalid signature"));
+        }
+        if tx.nonce() != account.nonce {
+            return Err(execution_error(format!(
+                "Nonce mismatch: expected {}, got {}",
+                account.nonce,
+                tx.nonce()
+            )));
+        }
+        // the target of the forced exit must exist, it's not created as the transfer receiver
+        if let ZkLinkTx::ForcedExit(tx) = tx {
+            if !self.accounts.values().any(|a| a.address == tx.target) {
+                return Err(execution_error("Account not found"));
+            }
+        }
+        Ok((account_id, sub_account_id))
+    }
+
+    /// Execute the checked tx, return the fail reason if the tx failed.
+    fn execute_tx(&mut self, account_id: AccountId, tx: &ZkLinkTx) -> Option<String> {
+        let account = self.accounts.get_mut(&account_id).unwrap();
+        account.nonce.0 += 1;
+        let fail_reason = Some("Insufficient balance".to_string());
+        match tx {
+            ZkLinkTx::Transfer(tx) => {
+                let total = &tx.amount + &tx.fee;
+                if !account.sub_balance(tx.from_sub_account_id, tx.token, &total) {
+                    return fail_reason;
+                }
+                self.account_by_address_mut(&tx.to).add_balance(
+                    tx.to_sub_account_id,
+                    tx.token,
+                    &tx.amount,
+                );
+            }
+            ZkLinkTx::Withdraw(tx) => {
+                let total = &tx.amount + &tx.fee;
+                if !account.sub_balance(tx.sub_account_id, tx.l2_source_token, &total) {
+                    return fail_reason;
+                }
+                self.withdrawals.push(MockWithdrawal {
+                    chain_id: tx.to_chain_id,
+                    to: tx.to.clone(),
+                    token: tx.l1_target_token,
+                    amount: tx.amount.clone(),
+                });
+            }
+            ZkLinkTx::ChangePubKey(tx) => {
+                if !account.sub_balance(tx.sub_account_id, tx.fee_token, &tx.fee) {
+                    return fail_reason;
+                }
+                account.pub_key_hash = tx.new_pk_hash;
+            }
+            // the forced exit has no fee, the exit amount is withdrawn to the target address
+            ZkLinkTx::ForcedExit(tx) => {
+                let target = self
+                    .accounts
+                    .values_mut()
+                    .find(|a| a.address == tx.target)
+                    .unwrap();
+                if !target.sub_balance(
+                    tx.target_sub_account_id,
+                    tx.l2_source_token,
+                    &tx.exit_amount,
+                ) {
+                    return fail_reason;
+                }
+                self.withdrawals.push(MockWithdrawal {
+                    chain_id: tx.to_chain_id,
+                    to: tx.target.clone(),
+                    token: tx.l1_target_token,
+                    amount: tx.exit_amount.clone(),
+                });
+            }
+            _ => unreachable!(),
+        }
+        None
+    }
+
+    fn push_event(&mut self, topic: Topic, data: ServerEvent) -> TxTopicEvent {
+        let events = self.events.entry(topic.clone()).or_default();
+        let event = Event::new(topic, events.len() as i64 + 1, data, None);
+        events.push(event.clone());
+        event
+    }
+}
+
+/// `MockZkLinkNode` implements `ZkLinkRpcServer` in memory.
+///
+/// Only `Transfer`, `Withdraw`, `ChangePubKey` and `ForcedExit` can be submitted. The tx format,
+/// the zklink signature and the nonce are checked before execution. The layer1 authorization
+/// of `ChangePubKey` is not checked.
+#[derive(Debug, Clone)]
+pub struct MockZkLinkNode {
+    state: Arc<Mutex<NodeState>>,
+    events: broadcast::Sender<TxTopicEvent>,
+}
+
+impl Default for MockZkLinkNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockZkLinkNode {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(1024);
+        Self {
+            state: Default::default(),
+            events,
+        }
+    }
+
+    pub fn add_chain(&self, chain: ChainResp) {
+        self.state.lock().unwrap().chains.push(chain);
+    }
+
+    pub fn add_token(&self, token: TokenResp) {
+        self.state.lock().unwrap().tokens.insert(token.id, token);
+    }
+
+    /// Insert or replace the account.
+    pub fn add_account(&self, account: MockAccount) {
+        self.state
+            .lock()
+            .unwrap()
+            .accounts
+            .insert(account.id, account);
+    }
+
+    pub fn account(&self, account_id: AccountId) -> Option<MockAccount> {
+        self.state
+            .lock()
+            .unwrap()
+            .accounts
+            .get(&account_id)
+            .cloned()
+    }
+
+    pub fn set_balance(
+        &self,
+        account_id: AccountId,
+        sub_account_id: SubAccountId,
+        token: TokenId,
+        amount: BigUint,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(account) = state.accounts.get_mut(&account_id) {
+            account
+                .balances
+                .entry(sub_account_id)
+                .or_default()
+                .insert(token, amount);
+        }
+    }
+
+    /// All the executed txs in order.
+    /// The withdrawals to layer1 in the execution order.
+    pub fn withdrawals(&self) -> Vec<MockWithdrawal> {
+        self.state.lock().unwrap().withdrawals.clone()
+    }
+
+    pub fn txs(&self) -> Vec<TxResp> {
+        let state = self.state.lock().unwrap();
+        state
+            .tx_log
+            .iter()
+            .map(|hash| state.txs[hash].clone())
+            .collect()
+    }
+
+    /// Receive all the events pushed after subscribing.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<TxTopicEvent> {
+        self.events.subscribe()
+    }
+
+    /// Start the json rpc server on `addr`, e.g. "127.0.0.1:0".
+    pub async fn start_rpc_server(
+        &self,
+        addr: &str,
+    ) -> Result<(SocketAddr, ServerHandle), std::io::Error> {
+        let server = ServerBuilder::default().build(addr).await?;
+        let addr = server.local_addr()?;
+        let handle = server.start(self.clone().into_rpc());
+        Ok((addr, handle))
+    }
+
+    /// Serve the WebSocket topics on `listener` until it fails.
+    pub async fn serve_ws(&self, listener: TcpListener) -> std::io::Error {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    tokio::spawn(serve_ws_connection(stream, self.subscribe_events()));
+                }
+                Err(e) => return e,
+            }
+        }
+    }
+
+    fn submit(&self, tx: ZkLinkTx) -> Result<TxHash, ErrorObjectOwned> {
+        tx.validate()
+            .map_err(|e| ErrorObject::owned(INVALID_PARAMS_CODE, e.to_string(), None::<()>))?;
+        let mut state = self.state.lock().unwrap();
+        let (account_id, sub_account_id) = state.check_tx(&tx)?;
+        let fail_reason = state.execute_tx(account_id, &tx);
+        state.block_number += 1;
+
+        let tx_hash = tx.tx_hash();
+        let tx_resp = TxResp {
+            tx_hash,
+            tx,
+            receipt: TxReceiptResp {
+                executed: true,
+                executed_timestamp: Some(Utc::now()),
+                success: fail_reason.is_none(),
+                fail_reason,
+                block: Some(BlockNumber(state.block_number)),
+                index: Some(0),
+            },
+            updates: vec![],
+        };
+        state.txs.insert(tx_hash, tx_resp.clone());
+        state.tx_log.push(tx_hash);
+        let topic = Topic::TxExecuteResult { sub_account_id };
+        let event = state.push_event(topic, ServerEvent::TxExecuteResult(tx_resp));
+        // no receivers is fine
+        let _ = self.events.send(event);
+        Ok(tx_hash)
+    }
+}
+
+fn execution_error(msg: impl Into<String>) -> ErrorObjectOwned {
+    ErrorObject::owned(CALL_EXECUTION_FAILED_CODE, msg, None::<()>)
+}
+
+fn unsupported<T>(method: &str) -> RpcResult<T> {
+    Err(execution_error(format!(
+        "{method} is not supported by mock node"
+    )))
+}
+
+async fn serve_ws_connection(
+    stream: tokio::net::TcpStream,
+    mut events: broadcast::Receiver<TxTopicEvent>,
+) {
+    let Ok(ws) = tokio_tungstenite::accept_async(stream).await else {
+        return;
+    };
+    let (mut sink, mut stream) = ws.split();
+    let mut topics: Vec<Topic> = vec![];
+    loop {
+        let msg = tokio::select! {
+            msg = stream.next() => match msg {
+                Some(Ok(Message::Text(text))) => {
+                    let Ok(request) = serde_json::from_str::<TopicsRequest<Topic>>(&text) else {
+                        continue;
+                    };
+                    match request.method {
+                        TopicMethod::Subscribe => topics.extend(request.topics.clone()),
+                        TopicMethod::UnSubscribe => topics.retain(|t| !t.matched(&request.topics)),
+                    }
+                    let response = WsResponse {
+                        result: TopicsResponse {
+                            topics: request.topics,
+                        },
+                        error_code: 0,
+                        error_msg: String::new(),
+                        id: request.id,
+                    };
+                    serde_json::to_string(&response)
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                Some(Ok(_)) => continue,
+            },
+            event = events.recv() => match event {
+                Ok(event) if event.topic.matched(&topics) => serde_json::to_string(&event),
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return,
+            },
+        };
+        let Ok(msg) = msg else {
+            continue;
+        };
+        if sink.send(Message::Text(msg)).await.is_err() {
+            return;
+        }
+    }
+}
+
+#[async_trait]
+impl ZkLinkRpcServer for MockZkLinkNode {
+    async fn get_support_chains(&self) -> RpcResult<Vec<ChainResp>> {
+        Ok(self.state.lock().unwrap().chains.clone())
+    }
+
+    async fn tokens(&self) -> RpcResult<HashMap<TokenId, TokenResp>> {
+        Ok(self.state.lock().unwrap().tokens.clone())
+    }
+
+    async fn block_info(&self) -> RpcResult<BlockNumberResp> {
+        let block_number = self.state.lock().unwrap().block_number;
+        Ok(BlockNumberResp {
+            last_block_number: block_number,
+            timestamp: Utc::now().timestamp() as u64,
+            committed: block_number,
+            verified: block_number,
+        })
+    }
+
+    async fn block_detail(
+        &self,
+        _block_number: Option<BlockNumber>,
+        _include_tx: bool,
+        _include_update: bool,
+    ) -> RpcResult<BlockResp> {
+        unsupported("getBlockByNumber")
+    }
+
+    async fn pending_block_detail(
+        &self,
+        _last_tx_timestamp_micro: u64,
+        _include_tx: bool,
+        _include_update: bool,
+        _limit: Option<usize>,
+    ) -> RpcResult<Vec<TxHashOrDetailResp>> {
+        Ok(vec![])
+    }
+
+    async fn block_onchain_detail(
+        &self,
+        _block_number: BlockNumber,
+    ) -> RpcResult<BlockOnChainResp> {
+        // there is no layer1 for mock node
+        Ok(BlockOnChainResp {
+            committed: vec![],
+            proved: vec![],
+            verified: vec![],
+        })
+    }
+
+    async fn account_info(&self, account_query: AccountQuery) -> RpcResult<AccountInfoResp> {
+        let state = self.state.lock().unwrap();
+        state
+            .account_by_query(&account_query)
+            .map(MockAccount::to_resp)
+            .ok_or_else(|| execution_error("Account not found"))
+    }
+
+    async fn global_vars_info(&self, _sub_account_id: SubAccountId) -> RpcResult<GlobalVarsResp> {
+        unsupported("getSubAccountGlobalVars")
+    }
+
+    async fn account_balances(
+        &self,
+        account_id: AccountId,
+        sub_account_id: Option<SubAccountId>,
+    ) -> RpcResult<SubAccountBalances> {
+        let state = self.state.lock().unwrap();
+        let account = state
+            .accounts
+            .get(&account_id)
+            .ok_or_else(|| execution_error("Account not found"))?;
+        let balances = account
+            .balances
+            .iter()
+            .filter(|(id, _)| sub_account_id.map_or(true, |sub_account_id| **id == sub_account_id))
+            .map(|(id, balances)| {
+                let balances = balances
+                    .iter()
+                    .map(|(token, amount)| (*token, amount.clone().into()))
+                    .collect();
+                (*id, balances)
+            })
+            .collect();
+        Ok(balances)
+    }
+
+    async fn account_order_slots(
+        &self,
+        _account_id: AccountId,
+        _sub_account_id: Option<SubAccountId>,
+    ) -> RpcResult<SubAccountOrders> {
+        Ok(HashMap::new())
+    }
+
+    async fn account_positions(
+        &self,
+        _account_id: AccountId,
+        _sub_account_id: Option<SubAccountId>,
+    ) -> RpcResult<SubAccountPositions> {
+        Ok(HashMap::new())
+    }
+
+    async fn token_remain(
+        &self,
+        _token_id: TokenId,
+        _mapping: bool,
+    ) -> RpcResult<HashMap<ChainId, BigUintSerdeWrapper>> {
+        unsupported("getTokenReserve")
+    }
+
+    async fn account_snapshot(
+        &self,
+        _account_query: AccountQuery,
+        _sub_account_id: Option<SubAccountId>,
+        _block_number: Option<BlockNumber>,
+    ) -> RpcResult<AccountSnapshotResp> {
+        unsupported("getAccountSnapshot")
+    }
+
+    async fn tx_info(&self, hash: TxHash, _include_update: bool) -> RpcResult<TxResp> {
+        let state = self.state.lock().unwrap();
+        state
+            .txs
+            .get(&hash)
+            .cloned()
+            .ok_or_else(|| execution_error("Tx not found"))
+    }
+
+    async fn tx_history(
+        &self,
+        _tx_type: ZkLinkTxType,
+        _address: ZkLinkAddress,
+        _page_index: u64,
+        _page_size: u32,
+    ) -> RpcResult<Page<ZkLinkTxHistory>> {
+        unsupported("getAccountTransactionHistory")
+    }
+
+    async fn tx_withdraw(
+        &self,
+        _last_tx_timestamp_micro: u64,
+        _max_txs: u32,
+    ) -> RpcResult<Vec<WithdrawTxResp>> {
+        Ok(vec![])
+    }
+
+    async fn get_websocket_events(
+        &self,
+        topic: Topic,
+        offset: ClientOffset,
+    ) -> RpcResult<Vec<TxTopicEvent>> {
+        let state = self.state.lock().unwrap();
+        let limit = offset
+            .limit
+            .unwrap_or(MAX_EVENTS_LIMIT)
+            .min(MAX_EVENTS_LIMIT);
+        let events = state
+            .events
+            .get(&topic)
+            .map(|events| {
+                events
+                    .iter()
+                    .filter(|e| e.topic_index >= offset.from_topic_index_included)
+                    .take(limit)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(events)
+    }
+
+    async fn get_change_pubkey_chain_id(&self) -> RpcResult<ChainId> {
+        let state = self.state.lock().unwrap();
+        state
+            .chains
+            .first()
+            .map(|chain| chain.chain_id)
+            .ok_or_else(|| execution_error("No chain"))
+    }
+
+    async fn get_eth_property(&self) -> RpcResult<EthPropertyResp> {
+        unsupported("getEthProperty")
+    }
+
+    async fn tx_submit(
+        &self,
+        tx: ZkLinkTx,
+        _l1_signature: Option<TxLayer1Signature>,
+        _oracle_signature: Option<OracleSignature>,
+    ) -> RpcResult<TxHash> {
+        self.submit(tx)
+    }
+}
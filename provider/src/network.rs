@@ -1,9 +1,10 @@
 use crate::error::RpcError;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
+use zklink_sdk_types::basic_types::ChainId;
 
 /// Network to be used for a zklink client.
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum Network {
     /// Mainnet.
@@ -12,6 +13,20 @@ pub enum Network {
     TestNet,
     /// Develop network
     DevNet,
+    /// Self-hosted node, e.g. a staging cluster or a local mock node
+    Custom(CustomNetwork),
+}
+
+/// The endpoints of a self-hosted node.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomNetwork {
+    /// json rpc endpoint
+    pub url: String,
+    /// WebSocket endpoint
+    pub ws_url: Option<String>,
+    /// the zklink chain id of the node
+    pub chain_id: Option<ChainId>,
 }
 
 impl Network {
@@ -20,19 +35,88 @@ impl Network {
             Network::MainNet => "https://api-v1.zk.link",
             Network::TestNet => "https://aws-gw-v2.zk.link",
             Network::DevNet => "https://dev-gw-v1.zk.link",
+            Network::Custom(network) => &network.url,
+        }
+    }
+
+    /// The WebSocket endpoint, `None` if it's not given to the custom network.
+    pub fn ws_url(&self) -> Option<&str> {
+        match self {
+            Network::MainNet => Some("wss://api-v1.zk.link/ws"),
+            Network::TestNet => Some("wss://aws-gw-v2.zk.link/ws"),
+            Network::DevNet => Some("wss://dev-gw-v1.zk.link/ws"),
+            Network::Custom(network) => network.ws_url.as_deref(),
+        }
+    }
+
+    /// The zklink chain id, only known for the custom network.
+    pub fn chain_id(&self) -> Option<ChainId> {
+        match self {
+            Network::Custom(network) => network.chain_id,
+            _ => None,
         }
     }
+
+    /// Custom network with only the json rpc endpoint.
+    pub fn custom(url: impl Into<String>) -> Self {
+        Self::new_custom(CustomNetwork {
+            url: url.into(),
+            ws_url: None,
+            chain_id: None,
+        })
+    }
+
+    /// Custom network with all the endpoints.
+    pub fn new_custom(network: CustomNetwork) -> Self {
+        Network::Custom(network)
+    }
 }
 
 impl FromStr for Network {
     type Err = RpcError;
 
+    /// Parse the network name, or a http(s) url as the custom network.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "mainet" => Ok(Network::MainNet),
+            // "mainet" is kept for compatibility
+            "mainnet" | "mainet" => Ok(Network::MainNet),
             "testnet" => Ok(Network::TestNet),
             "devnet" => Ok(Network::DevNet),
+            url if url.starts_with("http://") || url.starts_with("https://") => {
+                Ok(Network::custom(url))
+            }
             _ => Err(RpcError::InvalidNetwork),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_network_from_str() {
+        assert_eq!(Network::from_str("mainnet").unwrap(), Network::MainNet);
+        assert_eq!(Network::from_str("mainet").unwrap(), Network::MainNet);
+        assert_eq!(Network::from_str("devnet").unwrap(), Network::DevNet);
+        assert_eq!(Network::MainNet.ws_url(), Some("wss://api-v1.zk.link/ws"));
+        let network = Network::from_str("http://127.0.0.1:3030").unwrap();
+        assert_eq!(network.url(), "http://127.0.0.1:3030");
+        assert!(network.ws_url().is_none());
+        assert!(Network::from_str("localnet").is_err());
+    }
+
+    #[test]
+    fn test_custom_network_serde() {
+        let network = Network::new_custom(CustomNetwork {
+            url: "http://127.0.0.1:3030".to_string(),
+            ws_url: Some("ws://127.0.0.1:3031".to_string()),
+            chain_id: Some(ChainId(1)),
+        });
+        let s = serde_json::to_string(&network).unwrap();
+        let s_expect = r#"{"custom":{"url":"http://127.0.0.1:3030","wsUrl":"ws://127.0.0.1:3031","chainId":1}}"#;
+        assert_eq!(s, s_expect);
+        let network2: Network = serde_json::from_str(&s).unwrap();
+        assert_eq!(network, network2);
+    }
+}
@@ -0,0 +1,187 @@
+use crate::error::{ProviderError, ZkLinkNodeError};
+use crate::response::{AccountQuery, SubAccountNonces};
+use crate::rpc::ZkLinkRpcClient;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use zklink_sdk_types::basic_types::{AccountId, Nonce, SubAccountId};
+
+#[derive(Debug, Clone)]
+struct AccountNonces {
+    nonce: Nonce,
+    sub_account_nonces: SubAccountNonces,
+}
+
+/// The nonces of one account, `None` if they have not been fetched from the node.
+type AccountEntry = Arc<Mutex<Option<AccountNonces>>>;
+
+/// `NonceManager` hands out the account nonces and the sub account nonces to concurrent
+/// signers, every nonce is handed out only once.
+///
+/// The nonces of an account are fetched by `getAccount` when they are used first time. Every
+/// account has its own lock, so fetching the nonces of one account doesn't block the others.
+/// If a tx is rejected or failed because of the nonce, call
+/// [`NonceManager::handle_rejection`] to resync the nonces from the node. If a nonce is not
+/// used, call [`NonceManager::release_nonce`] or [`NonceManager::release_sub_account_nonce`]
+/// to hand it out again.
+#[derive(Debug)]
+pub struct NonceManager<C> {
+    client: C,
+    accounts: std::sync::Mutex<HashMap<AccountId, AccountEntry>>,
+}
+
+impl<C: ZkLinkRpcClient + Send + Sync> NonceManager<C> {
+    pub fn new(client: C) -> Self {
+        Self {
+            client,
+            accounts: Default::default(),
+        }
+    }
+
+    /// The next nonce of the account, used by `Transfer`, `Withdraw`, `ChangePubKey` and
+    /// `ForcedExit`.
+    pub async fn next_nonce(&self, account_id: AccountId) -> Result<Nonce, ProviderError> {
+        let entry = self.entry(account_id);
+        let mut entry = entry.lock().await;
+        let nonces = self.load(&mut entry, account_id).await?;
+        let nonce = nonces.nonce;
+        nonces.nonce.0 += 1;
+        Ok(nonce)
+    }
+
+    /// The next nonce of the sub account, used by `Funding`, `Liquidation` and
+    /// `AutoDeleveraging`.
+    pub async fn next_sub_account_nonce(
+        &self,
+        account_id: AccountId,
+        sub_account_id: SubAccountId,
+    ) -> Result<Nonce, ProviderError> {
+        let entry = self.entry(account_id);
+        let mut entry = entry.lock().await;
+        let nonces = self.load(&mut entry, account_id).await?;
+        let nonce = nonces
+            .sub_account_nonces
+            .entry(sub_account_id)
+            .or_insert(Nonce(0));
+        let next = *nonce;
+        nonce.0 += 1;
+        Ok(next)
+    }
+
+    /// Fetch the nonces of the account from the node, drop all the local changes.
+    pub async fn resync(&self, account_id: AccountId) -> Result<(), ProviderError> {
+        let entry = self.entry(account_id);
+        let mut entry = entry.lock().await;
+        *entry = None;
+        self.load(&mut entry, account_id).await?;
+        Ok(())
+    }
+
+    /// Resync the account if the `fail_reason` of the receipt, or the rpc error message is
+    /// caused by the nonce. Return true if resynced.
+    pub async fn handle_rejection(
+        &self,
+        account_id: AccountId,
+        reason: &str,
+    ) -> Result<bool, ProviderError> {
+        let error = ZkLinkNodeError::from_fail_reason(reason);
+        self.handle_node_error(account_id, &error).await
+    }
+
+    /// Resync the account if the error is caused by the nonce. Return true if resynced.
+    pub async fn handle_node_error(
+        &self,
+        account_id: AccountId,
+        error: &ZkLinkNodeError,
+    ) -> Result<bool, ProviderError> {
+        if !error.is_nonce_error() {
+            return Ok(false);
+        }
+        self.resync(account_id).await?;
+        Ok(true)
+    }
+
+    /// Give back the nonce handed out by [`NonceManager::next_nonce`] which is not used, e.g.
+    /// the signing failed or the tx is rejected by the node. If a later nonce has been handed
+    /// out, the nonces of the account will be fetched again when used next time.
+    pub async fn release_nonce(&self, account_id: AccountId, nonce: Nonce) {
+        let entry = self.entry(account_id);
+        let mut entry = entry.lock().await;
+        match entry.as_mut() {
+            Some(nonces) if nonces.nonce.0 == nonce.0 + 1 => nonces.nonce = nonce,
+            Some(_) => *entry = None,
+            None => {}
+        }
+    }
+
+    /// Give back the nonce handed out by [`NonceManager::next_sub_account_nonce`] which is not
+    /// used. If a later nonce of the sub account has been handed out, the nonces of the
+    /// account will be fetched again when used next time.
+    pub async fn release_sub_account_nonce(
+        &self,
+        account_id: AccountId,
+        sub_account_id: SubAccountId,
+        nonce: Nonce,
+    ) {
+        let entry = self.entry(account_id);
+        let mut entry = entry.lock().await;
+        let Some(nonces) = entry.as_mut() else {
+            return;
+        };
+        match nonces.sub_account_nonces.get_mut(&sub_account_id) {
+            Some(next) if next.0 == nonce.0 + 1 => *next = nonce,
+            Some(_) => *entry = None,
+            None => {}
+        }
+    }
+
+    /// Forget the nonces of the account, they will be fetched again when used next time.
+    pub async fn reset(&self, account_id: AccountId) {
+        *self.entry(account_id).lock().await = None;
+    }
+
+    /// The entry of the account, the map is locked only to look it up.
+    fn entry(&self, account_id: AccountId) -> AccountEntry {
+        self.accounts
+            .lock()
+            .unwrap()
+            .entry(account_id)
+            .or_default()
+            .clone()
+    }
+
+    async fn load<'a>(
+        &self,
+        entry: &'a mut Option<AccountNonces>,
+        account_id: AccountId,
+    ) -> Result<&'a mut AccountNonces, ProviderError> {
+        if entry.is_none() {
+            let account = self
+                .client
+                .account_info(AccountQuery::Id(account_id))
+                .await?;
+            *entry = Some(AccountNonces {
+                nonce: account.nonce,
+                sub_account_nonces: account.sub_account_nonces,
+            });
+        }
+        Ok(entry.as_mut().unwrap())
+    }
+}
+
+/// Return true if the rejected reason is about the nonce, e.g. "Nonce mismatch".
+pub fn is_nonce_error(reason: &str) -> bool {
+    ZkLinkNodeError::from_fail_reason(reason).is_nonce_error()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_nonce_error() {
+        assert!(is_nonce_error("Nonce mismatch: expected 1, got 0"));
+        assert!(is_nonce_error("invalid nonce"));
+        assert!(!is_nonce_error("Insufficient balance"));
+    }
+}
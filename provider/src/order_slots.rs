@@ -0,0 +1,314 @@
+#[cfg(not(target_arch = "wasm32"))]
+use crate::error::ProviderError;
+use crate::response::SubAccountOrders;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::rpc::ZkLinkRpcClient;
+use std::collections::HashMap;
+use thiserror::Error;
+#[cfg(not(target_arch = "wasm32"))]
+use zklink_sdk_types::basic_types::AccountId;
+use zklink_sdk_types::basic_types::{BigUint, Nonce, SlotId, SubAccountId};
+use zklink_sdk_types::params::{MAX_ORDER_NONCE, MAX_SLOT_ID};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum OrderSlotError {
+    #[error("No available order slot in sub account {0}")]
+    NoAvailableSlot(SubAccountId),
+    #[error("Order slot {0} of sub account {1} is not in use")]
+    SlotNotInUse(SlotId, SubAccountId),
+}
+
+/// The slot and the nonce to build the `Order` or `Contract` with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OrderSlot {
+    pub slot_id: SlotId,
+    pub nonce: Nonce,
+}
+
+#[derive(Debug, Clone, Default)]
+struct SlotState {
+    /// the nonce of the order in use, or the next order if the slot is free
+    nonce: Nonce,
+    /// the unfilled amount of the order in use
+    residue: BigUint,
+    in_use: bool,
+    partially_filled: bool,
+}
+
+impl SlotState {
+    fn is_available(&self) -> bool {
+        !self.in_use && self.nonce < MAX_ORDER_NONCE
+    }
+
+    /// The order in the slot is finished, the next order must use the next nonce.
+    fn finish(&mut self) {
+        self.nonce.0 += 1;
+        self.residue = BigUint::default();
+        self.in_use = false;
+        self.partially_filled = false;
+    }
+}
+
+/// `OrderSlotAllocator` picks the order slot and the order nonce for the new orders of an
+/// account, and tracks the residue of the orders locally as they are filled.
+///
+/// A slot with zero residue is free and the next order uses its nonce, when an order is
+/// fully filled the nonce of the slot is increased. The slots whose nonce reaches
+/// `MAX_ORDER_NONCE` are exhausted and never allocated again.
+#[derive(Debug, Clone, Default)]
+pub struct OrderSlotAllocator {
+    slots: HashMap<SubAccountId, HashMap<SlotId, SlotState>>,
+}
+
+impl OrderSlotAllocator {
+    /// Create from the slots returned by `getAccountOrderSlots`.
+    pub fn new(orders: SubAccountOrders) -> Self {
+        let slots = orders
+            .into_iter()
+            .map(|(sub_account_id, orders)| {
+                let orders = orders
+                    .into_iter()
+                    .map(|(slot_id, order)| {
+                        let residue = order.residue.0;
+                        let in_use = residue != BigUint::default();
+                        let state = SlotState {
+                            nonce: order.nonce,
+                            residue,
+                            in_use,
+                            partially_filled: in_use,
+                        };
+                        (slot_id, state)
+                    })
+                    .collect();
+                (sub_account_id, orders)
+            })
+            .collect();
+        Self { slots }
+    }
+
+    /// Load the slots of the account from the node.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn load<C: ZkLinkRpcClient + Sync>(
+        client: &C,
+        account_id: AccountId,
+        sub_account_id: Option<SubAccountId>,
+    ) -> Result<Self, ProviderError> {
+        let orders = client
+            .account_order_slots(account_id, sub_account_id)
+            .await?;
+        Ok(Self::new(orders))
+    }
+
+    /// Allocate a free slot for the new order of `amount`, the slot is in use until the order
+    /// is fully filled or released.
+    pub fn allocate(
+        &mut self,
+        sub_account_id: SubAccountId,
+        amount: BigUint,
+    ) -> Result<OrderSlot, OrderSlotError> {
+        let slots = self.slots.entry(sub_account_id).or_default();
+        let reusable = slots
+            .iter()
+            .filter(|(_, state)| state.is_available())
+            .map(|(slot_id, _)| *slot_id)
+            .min();
+        // the slots never used are free with nonce 0
+        let slot_id = reusable
+            .or_else(|| {
+                (0..=MAX_SLOT_ID.0)
+                    .map(SlotId)
+                    .find(|id| !slots.contains_key(id))
+            })
+            .ok_or(OrderSlotError::NoAvailableSlot(sub_account_id))?;
+        let state = slots.entry(slot_id).or_default();
+        state.residue = amount;
+        state.in_use = true;
+        Ok(OrderSlot {
+            slot_id,
+            nonce: state.nonce,
+        })
+    }
+
+    /// Record the `filled` amount of the order in the slot, the slot is free again if the order
+    /// is fully filled.
+    pub fn fill(
+        &mut self,
+        sub_account_id: SubAccountId,
+        slot_id: SlotId,
+        filled: &BigUint,
+    ) -> Result<(), OrderSlotError> {
+        let state = self.slot_in_use(sub_account_id, slot_id)?;
+        if *filled >= state.residue {
+            state.finish();
+        } else {
+            state.residue -= filled;
+            state.partially_filled = true;
+        }
+        Ok(())
+    }
+
+    /// Release the slot whose order is cancelled or rejected. If the order has been partially
+    /// filled, the next order of the slot uses the next nonce.
+    pub fn release(
+        &mut self,
+        sub_account_id: SubAccountId,
+        slot_id: SlotId,
+    ) -> Result<(), OrderSlotError> {
+        let state = self.slot_in_use(sub_account_id, slot_id)?;
+        if state.partially_filled {
+            state.finish();
+        } else {
+            state.residue = BigUint::default();
+            state.in_use = false;
+        }
+        Ok(())
+    }
+
+    /// The unfilled amount of the order in use.
+    pub fn residue(&self, sub_account_id: SubAccountId, slot_id: SlotId) -> Option<&BigUint> {
+        self.slots
+            .get(&sub_account_id)?
+            .get(&slot_id)
+            .filter(|state| state.in_use)
+            .map(|state| &state.residue)
+    }
+
+    fn slot_in_use(
+        &mut self,
+        sub_account_id: SubAccountId,
+        slot_id: SlotId,
+    ) -> Result<&mut SlotState, OrderSlotError> {
+        self.slots
+            .get_mut(&sub_account_id)
+            .and_then(|slots| slots.get_mut(&slot_id))
+            .filter(|state| state.in_use)
+            .ok_or(OrderSlotError::SlotNotInUse(slot_id, sub_account_id))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::response::ResponseTidyOrder;
+
+    #[test]
+    fn test_order_slot_allocate() {
+        let sub_account_id = SubAccountId(1);
+        let orders = HashMap::from([(
+            sub_account_id,
+            HashMap::from([
+                // partially filled
+                (
+                    SlotId(0),
+                    ResponseTidyOrder::new(Nonce(3), BigUint::from(10u32).into()),
+                ),
+                // fully filled
+                (
+                    SlotId(1),
+                    ResponseTidyOrder::new(Nonce(5), BigUint::default().into()),
+                ),
+                // exhausted
+                (
+                    SlotId(2),
+                    ResponseTidyOrder::new(Nonce(MAX_ORDER_NONCE.0 + 1), BigUint::default().into()),
+                ),
+            ]),
+        )]);
+        let mut allocator = OrderSlotAllocator::new(orders);
+        let slot = allocator
+            .allocate(sub_account_id, BigUint::from(100u32))
+            .unwrap();
+        assert_eq!(
+            slot,
+            OrderSlot {
+                slot_id: SlotId(1),
+                nonce: Nonce(5)
+            }
+        );
+        let slot = allocator
+            .allocate(sub_account_id, BigUint::from(100u32))
+            .unwrap();
+        assert_eq!(
+            slot,
+            OrderSlot {
+                slot_id: SlotId(3),
+                nonce: Nonce(0)
+            }
+        );
+
+        allocator
+            .fill(sub_account_id, SlotId(1), &BigUint::from(40u32))
+            .unwrap();
+        assert_eq!(
+            allocator.residue(sub_account_id, SlotId(1)),
+            Some(&BigUint::from(60u32))
+        );
+        allocator
+            .fill(sub_account_id, SlotId(1), &BigUint::from(60u32))
+            .unwrap();
+        assert_eq!(allocator.residue(sub_account_id, SlotId(1)), None);
+        let slot = allocator
+            .allocate(sub_account_id, BigUint::from(100u32))
+            .unwrap();
+        assert_eq!(
+            slot,
+            OrderSlot {
+                slot_id: SlotId(1),
+                nonce: Nonce(6)
+            }
+        );
+
+        // the order never filled reuses the nonce after released
+        allocator.release(sub_account_id, SlotId(3)).unwrap();
+        let slot = allocator
+            .allocate(sub_account_id, BigUint::from(100u32))
+            .unwrap();
+        assert_eq!(
+            slot,
+            OrderSlot {
+                slot_id: SlotId(3),
+                nonce: Nonce(0)
+            }
+        );
+        assert_eq!(
+            allocator.release(sub_account_id, SlotId(4)),
+            Err(OrderSlotError::SlotNotInUse(SlotId(4), sub_account_id))
+        );
+    }
+
+    #[test]
+    fn test_order_nonce_overflow() {
+        let sub_account_id = SubAccountId(1);
+        // only the slot 0 has one nonce left, the others are exhausted
+        let orders = HashMap::from([(
+            sub_account_id,
+            (0..=MAX_SLOT_ID.0)
+                .map(|id| {
+                    let nonce = if id == 0 {
+                        Nonce(MAX_ORDER_NONCE.0 - 1)
+                    } else {
+                        MAX_ORDER_NONCE
+                    };
+                    (
+                        SlotId(id),
+                        ResponseTidyOrder::new(nonce, BigUint::default().into()),
+                    )
+                })
+                .collect(),
+        )]);
+        let mut allocator = OrderSlotAllocator::new(orders);
+        let slot = allocator
+            .allocate(sub_account_id, BigUint::from(100u32))
+            .unwrap();
+        assert_eq!(slot.slot_id, SlotId(0));
+        assert_eq!(slot.nonce, Nonce(MAX_ORDER_NONCE.0 - 1));
+        // the nonce of the slot 0 reaches `MAX_ORDER_NONCE` after the order is filled
+        allocator
+            .fill(sub_account_id, slot.slot_id, &BigUint::from(100u32))
+            .unwrap();
+        assert_eq!(
+            allocator.allocate(sub_account_id, BigUint::from(100u32)),
+            Err(OrderSlotError::NoAvailableSlot(sub_account_id))
+        );
+    }
+}
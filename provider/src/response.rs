@@ -23,10 +23,12 @@ pub type MarginParams = HashMap<MarginId, ResponseMarginParams>;
 pub type ContractParams = HashMap<PairId, ResponseContractParams>;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ChainResp {
     pub chain_id: ChainId,
     pub chain_type: u8,
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub layer_one_chain_id: U256,
     pub main_contract: ZkLinkAddress,
     pub gas_token_id: TokenId,
@@ -34,6 +36,7 @@ pub struct ChainResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct TokenResp {
     /// id is used for tx signature and serialization
@@ -41,12 +44,14 @@ pub struct TokenResp {
     /// Token symbol (e.g. "ETH" or "USDC")
     pub symbol: String,
     /// Token price
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub usd_price: BigDecimal,
     /// Token info of each layer one chain
     pub chains: HashMap<ChainId, ChainTokenResp>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ChainTokenResp {
     /// chains is used to mark which chain(s) the token can be used
@@ -60,6 +65,7 @@ pub struct ChainTokenResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BlockNumberResp {
     pub last_block_number: u32,
@@ -69,20 +75,25 @@ pub struct BlockNumberResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BlockResp {
     pub number: BlockNumber,
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub commitment: H256,
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub root_hash: H256,
     pub fee_account_id: AccountId,
     pub block_size: u64,
     pub ops_composition_number: u64,
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
     #[serde(with = "ts_microseconds")]
     pub timestamp: DateTime<Utc>,
     pub transactions: Vec<TxHashOrDetailResp>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum TxHashOrDetailResp {
     Hash(TxHash),
@@ -90,6 +101,7 @@ pub enum TxHashOrDetailResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BlockOnChainResp {
     pub committed: Vec<OnChainResp>,
@@ -98,13 +110,16 @@ pub struct BlockOnChainResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct OnChainResp {
     pub chain_id: ChainId,
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub tx_hash: H256,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum AccountQuery {
     Id(AccountId),
@@ -112,6 +127,7 @@ pub enum AccountQuery {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct AccountInfoResp {
     pub id: AccountId,
@@ -122,6 +138,7 @@ pub struct AccountInfoResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct GlobalVarsResp {
     pub sub_account_id: SubAccountId,
@@ -132,6 +149,7 @@ pub struct GlobalVarsResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ResponseMarginParams {
     pub token_id: TokenId,
@@ -141,6 +159,7 @@ pub struct ResponseMarginParams {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ResponseContractParams {
     pub symbol: String,
@@ -151,6 +170,7 @@ pub struct ResponseContractParams {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ResponseTidyOrder {
     pub nonce: Nonce,
@@ -164,6 +184,7 @@ impl ResponseTidyOrder {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ResponsePosition {
     pub direction: bool,
@@ -192,6 +213,7 @@ impl ResponsePosition {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct AccountSnapshotResp {
     pub id: AccountId,
@@ -206,6 +228,7 @@ pub struct AccountSnapshotResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "stateUpdateType")]
 pub enum StateUpdateResp {
     AccountUpdate(AccountUpdateResp),
@@ -234,6 +257,7 @@ impl StateUpdateResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "accountUpdateType")]
 pub enum AccountUpdateResp {
     AccountCreate(AccountCreateResp),
@@ -256,6 +280,7 @@ impl AccountUpdateResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "globalVarUpdate")]
 pub enum GlobalVarsUpdateResp {
     FeeAccountUpdate(FeeAccountUpdateResp),
@@ -276,6 +301,7 @@ impl GlobalVarsUpdateResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct FeeAccountUpdateResp {
     pub update_id: i32,
@@ -285,6 +311,7 @@ pub struct FeeAccountUpdateResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct InsuranceFundAccountUpdateResp {
     pub update_id: i32,
@@ -294,6 +321,7 @@ pub struct InsuranceFundAccountUpdateResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct MarginParamsUpdateResp {
     pub update_id: i32,
@@ -310,6 +338,7 @@ pub struct MarginParamsUpdateResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ContractParamsUpdateResp {
     pub update_id: i32,
@@ -328,6 +357,7 @@ pub struct ContractParamsUpdateResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct AccountCreateResp {
     pub update_id: i32,
@@ -336,6 +366,7 @@ pub struct AccountCreateResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct AccountChangePubkeyUpdateResp {
     pub update_id: i32,
@@ -347,6 +378,7 @@ pub struct AccountChangePubkeyUpdateResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BalanceUpdateResp {
     pub update_id: i32,
@@ -360,6 +392,7 @@ pub struct BalanceUpdateResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct OrderUpdateResp {
     pub update_id: i32,
@@ -371,6 +404,7 @@ pub struct OrderUpdateResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct PositionUpdateResp {
     pub update_id: i32,
@@ -382,6 +416,7 @@ pub struct PositionUpdateResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct TxResp {
     pub tx_hash: TxHash,
@@ -391,9 +426,11 @@ pub struct TxResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct TxReceiptResp {
     pub executed: bool,
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<i64>"))]
     #[serde(with = "ts_microseconds_option")]
     pub executed_timestamp: Option<DateTime<Utc>>,
     pub success: bool,
@@ -403,25 +440,30 @@ pub struct TxReceiptResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BlockTxResp {
     pub tx_hash: TxHash,
     pub tx: ZkLinkTx,
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
     #[serde(with = "ts_microseconds")]
     pub executed_timestamp: DateTime<Utc>,
     pub updates: Vec<StateUpdateResp>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct WithdrawTxResp {
     pub tx_hash: TxHash,
     pub tx: ZkLinkTx,
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
     #[serde(with = "ts_microseconds")]
     pub executed_timestamp: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ForwardTxResp {
     pub tx_id: i64,
@@ -433,6 +475,7 @@ pub struct ForwardTxResp {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Page<T> {
     pub total_page_num: u64,
@@ -442,6 +485,7 @@ pub struct Page<T> {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ZkLinkTxHistory {
     pub chain_id: ChainId,
@@ -452,21 +496,38 @@ pub struct ZkLinkTxHistory {
     pub tx: ZkLinkTx,
     pub tx_hash: TxHash,
     pub tx_receipt: TxReceiptResp,
+    #[cfg_attr(feature = "schemars", schemars(with = "i64"))]
     #[serde(with = "ts_microseconds")]
     pub created_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct EthPropertyResp {
     pub chain_id: ChainId,
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub layer_one_chain_id: U256,
     pub gateways: Vec<GateWayInfo>,
     pub gas_token_id: TokenId,
     pub deposit_confirmation: u64,
 }
 
+impl EthPropertyResp {
+    /// The layer1 token addresses of the gateway on `chain_id`, e.g. for the multicall reads of
+    /// the wallet. The same token has a different address on every chain.
+    pub fn gateway_tokens(&self, chain_id: ChainId) -> Vec<(TokenId, ZkLinkAddress)> {
+        self.gateways
+            .iter()
+            .filter(|gateway| gateway.chain_id == chain_id)
+            .flat_map(|gateway| &gateway.tokens)
+            .map(|token| (token.token_id, token.token_address.clone()))
+            .collect()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct GateWayInfo {
     pub chain_id: ChainId,
@@ -476,6 +537,7 @@ pub struct GateWayInfo {
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct TokenInfo {
     pub token_id: TokenId,
@@ -546,4 +608,56 @@ mod test {
         println!("{:?}", resp);
         assert!(resp.is_ok());
     }
+
+    #[test]
+    fn test_gateway_tokens() {
+        let property: EthPropertyResp = serde_json::from_str(
+            r#"{
+                "chainId": 1,
+                "layerOneChainId": "0x1",
+                "gateways": [
+                    {
+                        "chainId": 2,
+                        "l1GatewayContract": "0x0000000000000000000000000000000000000001",
+                        "l2GatewayContract": "0x0000000000000000000000000000000000000002",
+                        "tokens": [
+                            {"tokenId": 1, "tokenAddress": "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee", "decimal": 18, "fastWithdraw": false},
+                            {"tokenId": 18, "tokenAddress": "0x0000000000000000000000000000000000000012", "decimal": 6, "fastWithdraw": true}
+                        ]
+                    },
+                    {
+                        "chainId": 3,
+                        "l1GatewayContract": "0x0000000000000000000000000000000000000003",
+                        "l2GatewayContract": "0x0000000000000000000000000000000000000004",
+                        "tokens": [
+                            {"tokenId": 18, "tokenAddress": "0x0000000000000000000000000000000000000013", "decimal": 6, "fastWithdraw": true}
+                        ]
+                    }
+                ],
+                "gasTokenId": 1,
+                "depositConfirmation": 12
+            }"#,
+        )
+        .unwrap();
+        let tokens = property.gateway_tokens(ChainId(2));
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].0, TokenId(1));
+        assert_eq!(
+            tokens[0].1,
+            "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee"
+                .parse()
+                .unwrap()
+        );
+        assert_eq!(tokens[1].0, TokenId(18));
+        // the same token has another address on chain 3
+        let tokens = property.gateway_tokens(ChainId(3));
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(
+            tokens[0].1,
+            "0x0000000000000000000000000000000000000013"
+                .parse()
+                .unwrap()
+        );
+        assert!(property.gateway_tokens(ChainId(4)).is_empty());
+    }
 }
@@ -0,0 +1,186 @@
+//! The JSON Schemas of the txs and the rpc responses and the TypeScript definitions generated from
+//! them, enabled by the `schemars` feature.
+//!
+//! The schemas are derived from the same serde attributes as the JSON, so the field names are
+//! camelCase and the big integers, hashes and signatures are strings.
+use crate::response::*;
+use crate::web_socket::ws_message::message::response::TxTopicEvent;
+use crate::web_socket::ws_message::topic::Topic;
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::schema::{RootSchema, Schema, SchemaObject};
+use schemars::JsonSchema;
+use zklink_sdk_types::prelude::{
+    AutoDeleveraging, ChangePubKey, Contract, ContractMatching, Deposit, ForcedExit, FullExit,
+    Funding, Liquidation, Order, OrderMatching, Transfer, TxLayer1Signature, TxSignature,
+    UpdateGlobalVar, Withdraw, ZkLinkSignature, ZkLinkTx,
+};
+use zklink_sdk_utils::schema::string_schema;
+
+impl JsonSchema for Topic {
+    fn schema_name() -> String {
+        "Topic".into()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        string_schema("The topic of the events, e.g. `txExecuteResult@1` of the sub account 1")
+    }
+}
+
+macro_rules! add_definitions {
+    ($gen:expr, $($ty:ty),+ $(,)?) => {
+        $($gen.subschema_for::<$ty>();)+
+    };
+}
+
+/// The JSON Schema (draft 7) of the txs and the rpc responses, every type is one of the
+/// `definitions`.
+pub fn json_schema() -> RootSchema {
+    let mut gen = SchemaSettings::draft07().into_generator();
+    add_definitions!(
+        gen,
+        ZkLinkTx,
+        Transfer,
+        Withdraw,
+        ChangePubKey,
+        ForcedExit,
+        Deposit,
+        FullExit,
+        OrderMatching,
+        ContractMatching,
+        AutoDeleveraging,
+        Funding,
+        Liquidation,
+        UpdateGlobalVar,
+        Order,
+        Contract,
+        ZkLinkSignature,
+        TxLayer1Signature,
+        TxSignature,
+    );
+    add_definitions!(
+        gen,
+        ChainResp,
+        TokenResp,
+        BlockNumberResp,
+        BlockResp,
+        BlockOnChainResp,
+        AccountQuery,
+        AccountInfoResp,
+        AccountSnapshotResp,
+        GlobalVarsResp,
+        StateUpdateResp,
+        TxResp,
+        BlockTxResp,
+        WithdrawTxResp,
+        ForwardTxResp,
+        Page<ZkLinkTxHistory>,
+        EthPropertyResp,
+        SubAccountBalances,
+        SubAccountOrders,
+        TxTopicEvent,
+    );
+    RootSchema {
+        meta_schema: gen.settings().meta_schema.clone(),
+        schema: SchemaObject::default(),
+        definitions: gen.take_definitions(),
+    }
+}
+
+/// The TypeScript definitions (`.d.ts`) of the txs and the rpc responses.
+pub fn typescript_definitions() -> String {
+    zklink_sdk_utils::schema::typescript_definitions(&json_schema())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use schemars::schema::Schema;
+    use serde::Serialize;
+    use std::str::FromStr;
+    use zklink_sdk_signers::zklink_signer::pubkey_hash::PubKeyHash;
+    use zklink_sdk_types::basic_types::BigUint;
+    use zklink_sdk_types::prelude::{
+        AccountId, Nonce, SubAccountId, TimeStamp, TokenId, ZkLinkAddress,
+    };
+
+    /// The JSON of the value has exactly the properties of the definition, the optional ones
+    /// could be missing.
+    fn assert_properties<T: Serialize>(root: &RootSchema, name: &str, value: &T) {
+        let Some(Schema::Object(schema)) = root.definitions.get(name) else {
+            panic!("{name} is not defined");
+        };
+        let object = schema.object.as_ref().unwrap();
+        let json = serde_json::to_value(value).unwrap();
+        let json = json.as_object().unwrap();
+        for key in json.keys() {
+            assert!(object.properties.contains_key(key), "{name}.{key}");
+        }
+        for key in &object.required {
+            assert!(json.contains_key(key), "{name}.{key}");
+        }
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let root = json_schema();
+        let transfer = Transfer {
+            account_id: AccountId(10),
+            from_sub_account_id: SubAccountId(1),
+            to_sub_account_id: SubAccountId(1),
+            to: ZkLinkAddress::from_str("0xAFAFf3aD1a0425D792432D9eCD1c3e26Ef2C42E9").unwrap(),
+            token: TokenId(18),
+            amount: BigUint::from(10000u32),
+            fee: BigUint::from(3u32),
+            nonce: Nonce(1),
+            ts: TimeStamp(1693472232),
+            ..Default::default()
+        };
+        assert_properties(&root, "Transfer", &transfer);
+        let account = AccountInfoResp {
+            id: AccountId(10),
+            address: transfer.to.clone(),
+            nonce: Nonce(3),
+            pub_key_hash: PubKeyHash::default(),
+            sub_account_nonces: [(SubAccountId(1), Nonce(2))].into(),
+        };
+        assert_properties(&root, "AccountInfoResp", &account);
+        let receipt = TxReceiptResp {
+            executed: false,
+            executed_timestamp: None,
+            success: false,
+            fail_reason: None,
+            block: None,
+            index: None,
+        };
+        assert_properties(&root, "TxReceiptResp", &receipt);
+        assert!(root.definitions.contains_key("Page_for_ZkLinkTxHistory"));
+        assert!(root.definitions.contains_key("ChainId"));
+        // the values of the sub account balances and orders
+        assert!(root.definitions.contains_key("BigIntSerdeWrapper"));
+        assert!(root.definitions.contains_key("ResponseTidyOrder"));
+        assert!(root
+            .definitions
+            .contains_key("Event_for_Topic_and_ServerEvent"));
+        assert!(root.definitions.contains_key("PriorityEventResp"));
+    }
+
+    #[test]
+    fn test_typescript_definitions() {
+        let ts = typescript_definitions();
+        assert!(ts.contains("export type TxHash = string;"));
+        assert!(ts.contains("export type BigUintString = string;"));
+        assert!(ts.contains("export type AccountId = number;"));
+        assert!(ts.contains("export type TxResp = {\n    txHash: TxHash;\n    tx: ZkLinkTx;\n"));
+        // the tag of the txs and the state updates
+        assert!(ts.contains("type: \"Transfer\";"));
+        assert!(ts.contains("stateUpdateType: \"AccountUpdate\";"));
+        // the options could be missing or null
+        assert!(ts.contains("executedTimestamp?: number | null;"));
+        assert!(ts.contains("subAccountNonces: Record<string, Nonce>;"));
+        // the events of `getWebSocketEvents`
+        assert!(ts.contains("export type Topic = string;"));
+        assert!(ts.contains("topic: Topic;"));
+        assert!(ts.contains("data: ServerEvent;"));
+        assert!(ts.contains("type: \"TxExecuteResult\";"));
+    }
+}
@@ -0,0 +1,435 @@
+#[cfg(not(target_arch = "wasm32"))]
+use crate::error::ProviderError;
+use crate::response::{ChainResp, ChainTokenResp, EthPropertyResp, TokenResp};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::rpc::ZkLinkRpcClient;
+use std::collections::HashMap;
+use std::str::FromStr;
+use thiserror::Error;
+use zklink_sdk_types::basic_types::{BigUint, ChainId, TokenId};
+use zklink_sdk_types::params::TOKEN_MAX_PRECISION;
+use zklink_sdk_types::tx_type::format_units;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TokenRegistryError {
+    #[error("Token {0} not found")]
+    TokenNotFound(TokenId),
+    #[error("Token symbol {0} not found")]
+    SymbolNotFound(String),
+    #[error("Token {0} is not supported by chain {1}")]
+    ChainTokenNotFound(TokenId, ChainId),
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+    #[error("Amount {0} could not be represented exactly with {1} decimals")]
+    InexactAmount(String, u8),
+}
+
+/// How to round an amount that has more decimals than the target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// Truncate the extra decimals, e.g. the amount to withdraw to layer1.
+    Down,
+    /// Round up if any extra decimal is not zero, e.g. the amount to deposit to cover a debt.
+    Up,
+    /// Return [`TokenRegistryError::InexactAmount`] if any extra decimal is not zero.
+    Exact,
+}
+
+/// `TokenRegistry` keeps the metadata of the tokens and chains supported by the node.
+///
+/// The amounts of layer2 always have `TOKEN_MAX_PRECISION` decimals, and the amounts of
+/// layer1 have the decimals of the token on that chain.
+#[derive(Debug, Clone, Default)]
+pub struct TokenRegistry {
+    tokens: HashMap<TokenId, TokenResp>,
+    symbols: HashMap<String, TokenId>,
+    chains: HashMap<ChainId, ChainResp>,
+    /// the chain tokens merged from `getEthProperty`, kept across the refreshes
+    gateway_tokens: HashMap<(TokenId, ChainId), ChainTokenResp>,
+}
+
+impl TokenRegistry {
+    pub fn new(tokens: HashMap<TokenId, TokenResp>, chains: Vec<ChainResp>) -> Self {
+        let mut registry = Self::default();
+        registry.update(tokens, chains);
+        registry
+    }
+
+    /// Load the tokens and chains by `getSupportTokens` and `getSupportChains`.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn load<C: ZkLinkRpcClient + Sync>(client: &C) -> Result<Self, ProviderError> {
+        let mut registry = Self::default();
+        registry.refresh(client).await?;
+        Ok(registry)
+    }
+
+    /// Reload the tokens and chains from the node, e.g. when a token is not found. The loaded
+    /// ones are merged into the registry, see [`TokenRegistry::merge`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn refresh<C: ZkLinkRpcClient + Sync>(
+        &mut self,
+        client: &C,
+    ) -> Result<(), ProviderError> {
+        let tokens = client.tokens().await?;
+        let chains = client.get_support_chains().await?;
+        self.merge(tokens, chains);
+        Ok(())
+    }
+
+    /// Replace all the tokens and chains, the chain tokens of `getEthProperty` are kept.
+    pub fn update(&mut self, tokens: HashMap<TokenId, TokenResp>, chains: Vec<ChainResp>) {
+        self.tokens.clear();
+        self.symbols.clear();
+        self.chains.clear();
+        self.merge(tokens, chains);
+    }
+
+    /// Add or replace the tokens and chains, the others and the chain tokens of
+    /// `getEthProperty` are kept.
+    pub fn merge(&mut self, tokens: HashMap<TokenId, TokenResp>, chains: Vec<ChainResp>) {
+        for (token_id, token) in tokens {
+            if let Some(old) = self.tokens.get(&token_id) {
+                // the symbol could be taken by another token in the same merge
+                let old_symbol = old.symbol.to_uppercase();
+                if self.symbols.get(&old_symbol) == Some(&token_id) {
+                    self.symbols.remove(&old_symbol);
+                }
+            }
+            self.symbols.insert(token.symbol.to_uppercase(), token_id);
+            self.tokens.insert(token_id, token);
+        }
+        self.chains
+            .extend(chains.into_iter().map(|chain| (chain.chain_id, chain)));
+        self.apply_gateway_tokens();
+    }
+
+    /// Add or override the chain tokens, e.g. the layer1 decimals, by the gateways of
+    /// `getEthProperty`, they are kept across the refreshes.
+    pub fn merge_eth_property(&mut self, property: &EthPropertyResp) {
+        for gateway in &property.gateways {
+            for info in &gateway.tokens {
+                let chain_token = ChainTokenResp {
+                    chain_id: gateway.chain_id,
+                    address: info.token_address.clone(),
+                    decimals: info.decimal,
+                    fast_withdraw: info.fast_withdraw,
+                };
+                self.gateway_tokens
+                    .insert((info.token_id, gateway.chain_id), chain_token);
+            }
+        }
+        self.apply_gateway_tokens();
+    }
+
+    fn apply_gateway_tokens(&mut self) {
+        for (&(token_id, chain_id), chain_token) in &self.gateway_tokens {
+            if let Some(token) = self.tokens.get_mut(&token_id) {
+                token.chains.insert(chain_id, chain_token.clone());
+            }
+        }
+    }
+
+    pub fn token(&self, token_id: TokenId) -> Result<&TokenResp, TokenRegistryError> {
+        self.tokens
+            .get(&token_id)
+            .ok_or(TokenRegistryError::TokenNotFound(token_id))
+    }
+
+    /// Find the token by symbol, case insensitive.
+    pub fn token_by_symbol(&self, symbol: &str) -> Result<&TokenResp, TokenRegistryError> {
+        self.symbols
+            .get(&symbol.to_uppercase())
+            .and_then(|token_id| self.tokens.get(token_id))
+            .ok_or_else(|| TokenRegistryError::SymbolNotFound(symbol.to_string()))
+    }
+
+    pub fn symbol(&self, token_id: TokenId) -> Result<&str, TokenRegistryError> {
+        Ok(&self.token(token_id)?.symbol)
+    }
+
+    pub fn chain(&self, chain_id: ChainId) -> Option<&ChainResp> {
+        self.chains.get(&chain_id)
+    }
+
+    pub fn chains(&self) -> impl Iterator<Item = &ChainResp> {
+        self.chains.values()
+    }
+
+    /// The decimals of the token on the layer1 chain.
+    pub fn l1_decimals(
+        &self,
+        token_id: TokenId,
+        chain_id: ChainId,
+    ) -> Result<u8, TokenRegistryError> {
+        self.token(token_id)?
+            .chains
+            .get(&chain_id)
+            .map(|chain_token| chain_token.decimals)
+            .ok_or(TokenRegistryError::ChainTokenNotFound(token_id, chain_id))
+    }
+
+    /// Parse a decimal string, e.g. "1.5", into the layer2 amount.
+    pub fn parse_l2_amount(
+        &self,
+        amount: &str,
+        rounding: Rounding,
+    ) -> Result<BigUint, TokenRegistryError> {
+        parse_units(amount, TOKEN_MAX_PRECISION, rounding)
+    }
+
+    pub fn format_l2_amount(&self, amount: &BigUint) -> String {
+        format_units(amount, TOKEN_MAX_PRECISION)
+    }
+
+    /// Parse a decimal string into the layer1 amount of the token on the chain.
+    pub fn parse_l1_amount(
+        &self,
+        token_id: TokenId,
+        chain_id: ChainId,
+        amount: &str,
+        rounding: Rounding,
+    ) -> Result<BigUint, TokenRegistryError> {
+        parse_units(amount, self.l1_decimals(token_id, chain_id)?, rounding)
+    }
+
+    pub fn format_l1_amount(
+        &self,
+        token_id: TokenId,
+        chain_id: ChainId,
+        amount: &BigUint,
+    ) -> Result<String, TokenRegistryError> {
+        Ok(format_units(amount, self.l1_decimals(token_id, chain_id)?))
+    }
+
+    /// Convert the layer2 amount to the layer1 amount of the token on the chain.
+    pub fn l2_to_l1_amount(
+        &self,
+        token_id: TokenId,
+        chain_id: ChainId,
+        amount: &BigUint,
+        rounding: Rounding,
+    ) -> Result<BigUint, TokenRegistryError> {
+        let decimals = self.l1_decimals(token_id, chain_id)?;
+        scale(amount, TOKEN_MAX_PRECISION, decimals, rounding)
+    }
+
+    /// Convert the layer1 amount of the token on the chain to the layer2 amount.
+    pub fn l1_to_l2_amount(
+        &self,
+        token_id: TokenId,
+        chain_id: ChainId,
+        amount: &BigUint,
+        rounding: Rounding,
+    ) -> Result<BigUint, TokenRegistryError> {
+        let decimals = self.l1_decimals(token_id, chain_id)?;
+        scale(amount, decimals, TOKEN_MAX_PRECISION, rounding)
+    }
+}
+
+/// Parse a decimal string into the integer amount with `decimals`.
+/// Behaves like ethers.utils.parseUnits when `rounding` is `Exact`.
+pub fn parse_units(
+    amount: &str,
+    decimals: u8,
+    rounding: Rounding,
+) -> Result<BigUint, TokenRegistryError> {
+    let invalid = || TokenRegistryError::InvalidAmount(amount.to_string());
+    let (integer, fraction) = amount.trim().split_once('.').unwrap_or((amount.trim(), ""));
+    if integer.is_empty() && fraction.is_empty() {
+        return Err(invalid());
+    }
+    if !integer
+        .chars()
+        .chain(fraction.chars())
+        .all(|c| c.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    let digits = format!("{integer}{fraction}");
+    let digits = BigUint::from_str(&digits).map_err(|_| invalid())?;
+    let fraction_decimals = u8::try_from(fraction.len()).map_err(|_| invalid())?;
+    scale(&digits, fraction_decimals, decimals, rounding).map_err(|e| match e {
+        TokenRegistryError::InexactAmount(_, _) => {
+            TokenRegistryError::InexactAmount(amount.to_string(), decimals)
+        }
+        e => e,
+    })
+}
+
+/// Convert `amount` with `from` decimals to `to` decimals.
+fn scale(
+    amount: &BigUint,
+    from: u8,
+    to: u8,
+    rounding: Rounding,
+) -> Result<BigUint, TokenRegistryError> {
+    if from <= to {
+        return Ok(amount * BigUint::from(10u32).pow((to - from) as u32));
+    }
+    let divisor = BigUint::from(10u32).pow((from - to) as u32);
+    let quotient = amount / &divisor;
+    if amount % &divisor == BigUint::default() {
+        return Ok(quotient);
+    }
+    match rounding {
+        Rounding::Down => Ok(quotient),
+        Rounding::Up => Ok(quotient + 1u32),
+        Rounding::Exact => Err(TokenRegistryError::InexactAmount(
+            format_units(amount, from),
+            to,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::response::{GateWayInfo, TokenInfo};
+    use zklink_sdk_types::basic_types::ZkLinkAddress;
+
+    fn registry() -> TokenRegistry {
+        let usdc = TokenResp {
+            id: TokenId(17),
+            symbol: "USDC".to_string(),
+            usd_price: Default::default(),
+            chains: HashMap::from([(
+                ChainId(1),
+                ChainTokenResp {
+                    chain_id: ChainId(1),
+                    address: ZkLinkAddress::default(),
+                    decimals: 6,
+                    fast_withdraw: false,
+                },
+            )]),
+        };
+        TokenRegistry::new(HashMap::from([(usdc.id, usdc)]), vec![])
+    }
+
+    #[test]
+    fn test_token_registry_symbol() {
+        let registry = registry();
+        assert_eq!(registry.token_by_symbol("usdc").unwrap().id, TokenId(17));
+        assert_eq!(registry.symbol(TokenId(17)).unwrap(), "USDC");
+        assert_eq!(
+            registry.token_by_symbol("ETH").unwrap_err(),
+            TokenRegistryError::SymbolNotFound("ETH".to_string())
+        );
+        assert_eq!(
+            registry.l1_decimals(TokenId(17), ChainId(2)),
+            Err(TokenRegistryError::ChainTokenNotFound(
+                TokenId(17),
+                ChainId(2)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_token_registry_amount() {
+        let registry = registry();
+        let l2_amount = registry.parse_l2_amount("1.5", Rounding::Exact).unwrap();
+        assert_eq!(l2_amount, BigUint::from(1_500_000_000_000_000_000u64));
+        assert_eq!(registry.format_l2_amount(&l2_amount), "1.5");
+        let l1_amount = registry
+            .l2_to_l1_amount(TokenId(17), ChainId(1), &l2_amount, Rounding::Exact)
+            .unwrap();
+        assert_eq!(l1_amount, BigUint::from(1_500_000u32));
+        assert_eq!(
+            registry
+                .l1_to_l2_amount(TokenId(17), ChainId(1), &l1_amount, Rounding::Exact)
+                .unwrap(),
+            l2_amount
+        );
+
+        let l2_amount = registry
+            .parse_l2_amount("0.0000015000001", Rounding::Exact)
+            .unwrap();
+        let convert =
+            |rounding| registry.l2_to_l1_amount(TokenId(17), ChainId(1), &l2_amount, rounding);
+        assert_eq!(convert(Rounding::Down).unwrap(), BigUint::from(1u32));
+        assert_eq!(convert(Rounding::Up).unwrap(), BigUint::from(2u32));
+        assert!(matches!(
+            convert(Rounding::Exact),
+            Err(TokenRegistryError::InexactAmount(_, 6))
+        ));
+        assert_eq!(
+            registry
+                .parse_l1_amount(TokenId(17), ChainId(1), "2.0000001", Rounding::Down)
+                .unwrap(),
+            BigUint::from(2_000_000u32)
+        );
+    }
+
+    #[test]
+    fn test_token_registry_merge() {
+        let mut registry = registry();
+        let property = EthPropertyResp {
+            chain_id: ChainId(1),
+            layer_one_chain_id: Default::default(),
+            gateways: vec![GateWayInfo {
+                chain_id: ChainId(2),
+                l1_gateway_contract: ZkLinkAddress::default(),
+                l2_gateway_contract: ZkLinkAddress::default(),
+                tokens: vec![TokenInfo {
+                    token_id: TokenId(17),
+                    token_address: ZkLinkAddress::default(),
+                    decimal: 18,
+                    fast_withdraw: false,
+                }],
+            }],
+            gas_token_id: TokenId(1),
+            deposit_confirmation: 0,
+        };
+        registry.merge_eth_property(&property);
+        assert_eq!(registry.l1_decimals(TokenId(17), ChainId(2)), Ok(18));
+
+        // the tokens of the node don't have the chain 2
+        let tokens = registry().tokens;
+        registry.merge(tokens.clone(), vec![]);
+        assert_eq!(registry.l1_decimals(TokenId(17), ChainId(2)), Ok(18));
+        assert_eq!(registry.l1_decimals(TokenId(17), ChainId(1)), Ok(6));
+        registry.update(tokens, vec![]);
+        assert_eq!(registry.l1_decimals(TokenId(17), ChainId(2)), Ok(18));
+
+        // the other tokens are kept, the renamed symbol is replaced
+        let mut usdt = registry.token(TokenId(17)).unwrap().clone();
+        usdt.id = TokenId(18);
+        usdt.symbol = "USDT".to_string();
+        registry.merge(HashMap::from([(usdt.id, usdt.clone())]), vec![]);
+        assert_eq!(registry.token_by_symbol("usdc").unwrap().id, TokenId(17));
+        usdt.symbol = "USDT.e".to_string();
+        registry.merge(HashMap::from([(usdt.id, usdt)]), vec![]);
+        assert_eq!(registry.token_by_symbol("usdt.e").unwrap().id, TokenId(18));
+        assert!(registry.token_by_symbol("USDT").is_err());
+
+        // the tokens swap the symbols
+        let mut usdc = registry.token(TokenId(17)).unwrap().clone();
+        let mut usdt = registry.token(TokenId(18)).unwrap().clone();
+        std::mem::swap(&mut usdc.symbol, &mut usdt.symbol);
+        registry.merge(HashMap::from([(usdc.id, usdc), (usdt.id, usdt)]), vec![]);
+        assert_eq!(registry.token_by_symbol("usdt.e").unwrap().id, TokenId(17));
+        assert_eq!(registry.token_by_symbol("usdc").unwrap().id, TokenId(18));
+    }
+
+    #[test]
+    fn test_parse_units() {
+        assert_eq!(
+            parse_units("12", 2, Rounding::Exact).unwrap(),
+            BigUint::from(1200u32)
+        );
+        assert_eq!(
+            parse_units(".5", 1, Rounding::Exact).unwrap(),
+            BigUint::from(5u32)
+        );
+        assert_eq!(
+            parse_units("0.15", 1, Rounding::Up).unwrap(),
+            BigUint::from(2u32)
+        );
+        assert_eq!(
+            parse_units("0.15", 1, Rounding::Exact),
+            Err(TokenRegistryError::InexactAmount("0.15".to_string(), 1))
+        );
+        assert!(parse_units("1.2.3", 18, Rounding::Exact).is_err());
+        assert!(parse_units("-1", 18, Rounding::Exact).is_err());
+        assert!(parse_units(".", 18, Rounding::Exact).is_err());
+    }
+}
@@ -0,0 +1,189 @@
+use crate::error::ZkLinkNodeError;
+use crate::response::{BlockOnChainResp, TxResp};
+use crate::rpc::ZkLinkRpcClient;
+use crate::web_socket::client::Subscription;
+use crate::web_socket::ws_message::message::response::ServerEvent;
+use futures::StreamExt;
+use jsonrpsee::core::ClientError;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::time::{sleep, Instant};
+use zklink_sdk_types::basic_types::tx_hash::TxHash;
+use zklink_sdk_types::basic_types::BlockNumber;
+
+/// The interval between two polls of the node.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The stage of the L2 tx lifecycle to wait for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confirmation {
+    /// The tx is executed by the node, it could be failed.
+    Executed,
+    /// The block of the tx is committed to layer1.
+    Committed,
+    /// The block of the tx is verified on layer1.
+    Verified,
+}
+
+#[derive(Debug, Error)]
+pub enum WaitTxError {
+    #[error("Rpc error: {0}")]
+    RpcError(ClientError),
+    #[error("{0}")]
+    NodeError(#[from] ZkLinkNodeError),
+    #[error("Tx {0:?} failed: {1}")]
+    TxFailed(TxHash, ZkLinkNodeError),
+    #[error("Timeout when waiting for tx {0:?}")]
+    Timeout(TxHash),
+}
+
+impl From<ClientError> for WaitTxError {
+    fn from(error: ClientError) -> Self {
+        match ZkLinkNodeError::from_client_error(&error) {
+            Some(error) => Self::NodeError(error),
+            None => Self::RpcError(error),
+        }
+    }
+}
+
+/// The tx and its receipt when the expected confirmation is reached.
+#[derive(Debug, Clone)]
+pub struct TxConfirmation {
+    pub tx: TxResp,
+    /// The layer1 txs of the block, only fetched when waiting for `Committed` or `Verified`.
+    pub onchain: Option<BlockOnChainResp>,
+}
+
+/// Poll `getTransactionByHash` until the tx reaches `confirmation`, then poll
+/// `getLatestBlockNumber` and `getBlockOnChainByNumber` for the block of the tx if required.
+///
+/// A tx executed but failed returns [`WaitTxError::TxFailed`] with the `fail_reason`. The txs
+/// not known by the node yet are treated as pending until the timeout.
+pub async fn wait_for_tx<C: ZkLinkRpcClient + Sync>(
+    client: &C,
+    tx_hash: TxHash,
+    confirmation: Confirmation,
+    timeout: Duration,
+) -> Result<TxConfirmation, WaitTxError> {
+    let deadline = Instant::now() + timeout;
+    let tx = loop {
+        if let Some(tx) = executed_tx(client, tx_hash).await? {
+            break tx;
+        }
+        wait_until_next_poll(tx_hash, deadline).await?;
+    };
+    confirm(client, tx, confirmation, deadline).await
+}
+
+/// The same as [`wait_for_tx`], but the execution of the tx is received from `subscription`,
+/// which must subscribe the `TxExecuteResult` topic of the sub account of the tx.
+///
+/// The tx is checked once by the rpc after the subscription, in case it's executed before.
+pub async fn wait_for_tx_with_subscription<C: ZkLinkRpcClient + Sync>(
+    client: &C,
+    subscription: &mut Subscription,
+    tx_hash: TxHash,
+    confirmation: Confirmation,
+    timeout: Duration,
+) -> Result<TxConfirmation, WaitTxError> {
+    let deadline = Instant::now() + timeout;
+    let mut tx = executed_tx(client, tx_hash).await?;
+    while tx.is_none() {
+        let event = tokio::time::timeout_at(deadline, subscription.next())
+            .await
+            .map_err(|_| WaitTxError::Timeout(tx_hash))?;
+        match event {
+            Some(event) => match event.data {
+                ServerEvent::TxExecuteResult(resp) if resp.tx_hash == tx_hash => {
+                    check_success(&resp)?;
+                    tx = Some(resp);
+                }
+                _ => {}
+            },
+            // the connection is closed, fallback to poll
+            None => {
+                let timeout = deadline.saturating_duration_since(Instant::now());
+                return wait_for_tx(client, tx_hash, confirmation, timeout).await;
+            }
+        }
+    }
+    confirm(client, tx.unwrap(), confirmation, deadline).await
+}
+
+/// Return the tx if it's executed successfully, `None` if it's still pending.
+async fn executed_tx<C: ZkLinkRpcClient + Sync>(
+    client: &C,
+    tx_hash: TxHash,
+) -> Result<Option<TxResp>, WaitTxError> {
+    let tx = match client
+        .tx_info(tx_hash, false)
+        .await
+        .map_err(WaitTxError::from)
+    {
+        Ok(tx) => tx,
+        // the tx is not found before it's received by the node
+        Err(WaitTxError::NodeError(ZkLinkNodeError::TxNotFound(_))) => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    if !tx.receipt.executed {
+        return Ok(None);
+    }
+    check_success(&tx)?;
+    Ok(Some(tx))
+}
+
+fn check_success(tx: &TxResp) -> Result<(), WaitTxError> {
+    if tx.receipt.success {
+        Ok(())
+    } else {
+        let reason = tx.receipt.fail_reason.as_deref().unwrap_or_default();
+        let error = ZkLinkNodeError::from_fail_reason(reason);
+        Err(WaitTxError::TxFailed(tx.tx_hash, error))
+    }
+}
+
+async fn confirm<C: ZkLinkRpcClient + Sync>(
+    client: &C,
+    mut tx: TxResp,
+    confirmation: Confirmation,
+    deadline: Instant,
+) -> Result<TxConfirmation, WaitTxError> {
+    if confirmation == Confirmation::Executed {
+        return Ok(TxConfirmation { tx, onchain: None });
+    }
+    // the executed tx is packed into a block later
+    let block = loop {
+        if let Some(block) = tx.receipt.block {
+            break block;
+        }
+        wait_until_next_poll(tx.tx_hash, deadline).await?;
+        if let Some(packed) = executed_tx(client, tx.tx_hash).await? {
+            tx = packed;
+        }
+    };
+    loop {
+        let info = client.block_info().await?;
+        let confirmed = match confirmation {
+            Confirmation::Committed => info.committed,
+            _ => info.verified,
+        };
+        if BlockNumber(confirmed) >= block {
+            break;
+        }
+        wait_until_next_poll(tx.tx_hash, deadline).await?;
+    }
+    let onchain = client.block_onchain_detail(block).await?;
+    Ok(TxConfirmation {
+        tx,
+        onchain: Some(onchain),
+    })
+}
+
+async fn wait_until_next_poll(tx_hash: TxHash, deadline: Instant) -> Result<(), WaitTxError> {
+    let now = Instant::now();
+    if now >= deadline {
+        return Err(WaitTxError::Timeout(tx_hash));
+    }
+    sleep(POLL_INTERVAL.min(deadline - now)).await;
+    Ok(())
+}
@@ -0,0 +1,320 @@
+use crate::web_socket::proto::event::Event;
+use crate::web_socket::proto::request::{ClientMessage, TopicsRequest};
+use crate::web_socket::proto::response::WsResponse;
+use crate::web_socket::proto::topic::{TopicMethod, TopicTrait};
+use crate::web_socket::ws_message::message::request::QueryEvent;
+use crate::web_socket::ws_message::message::response::{ServerEvent, TxTopicEvent};
+use crate::web_socket::ws_message::topic::Topic;
+use futures::channel::mpsc as event_channel;
+use futures::{SinkExt, Stream, StreamExt};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use thiserror::Error;
+use tokio::sync::{mpsc, oneshot};
+use tokio_tungstenite::tungstenite::{self, Message};
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+
+type WsStream = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+type ClientRequest = ClientMessage<QueryEvent, Topic>;
+
+#[derive(Debug, Error)]
+pub enum WsClientError {
+    #[error("WebSocket connection error: {0}")]
+    ConnectionError(#[from] tungstenite::Error),
+    #[error("Serialize request error: {0}")]
+    SerializeError(#[from] serde_json::Error),
+    #[error("WebSocket connection closed")]
+    ConnectionClosed,
+    #[error("Server returned error {code}: {msg}")]
+    ServerError { code: usize, msg: String },
+}
+
+enum Command {
+    Subscribe {
+        id: usize,
+        topics: Vec<Topic>,
+        events: event_channel::UnboundedSender<TxTopicEvent>,
+        ack: oneshot::Sender<Result<(), WsClientError>>,
+    },
+    Unsubscribe {
+        id: usize,
+        subscription_id: usize,
+        ack: oneshot::Sender<Result<(), WsClientError>>,
+    },
+}
+
+/// `WsClient` maintains a single WebSocket connection to the zkLink gateway and
+/// multiplexes topic subscriptions over it.
+///
+/// The connection is driven by a background tokio task, so the client is cheap to clone
+/// and can be shared between tasks. Every subscription gets its own event stream.
+#[derive(Debug, Clone)]
+pub struct WsClient {
+    commands: mpsc::UnboundedSender<Command>,
+    next_id: Arc<AtomicUsize>,
+}
+
+impl WsClient {
+    /// Connect to the WebSocket endpoint of the gateway, e.g. `wss://api-v1.zk.link/ws`.
+    pub async fn connect(url: &str) -> Result<Self, WsClientError> {
+        let (ws, _) = connect_async(url).await?;
+        Ok(Self::from_stream(ws))
+    }
+
+    fn from_stream(ws: WsStream) -> Self {
+        let (commands, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run_connection(ws, receiver));
+        Self {
+            commands,
+            next_id: Arc::new(AtomicUsize::new(1)),
+        }
+    }
+
+    /// Subscribe the `topics`, the returned stream yields every event of these topics
+    /// until it is dropped, unsubscribed or the connection is closed.
+    pub async fn subscribe(&self, topics: Vec<Topic>) -> Result<Subscription, WsClientError> {
+        let id = self.next_id();
+        let (events, receiver) = event_channel::unbounded();
+        let (ack, response) = oneshot::channel();
+        self.send_command(Command::Subscribe {
+            id,
+            topics: topics.clone(),
+            events,
+            ack,
+        })?;
+        response
+            .await
+            .map_err(|_| WsClientError::ConnectionClosed)??;
+        Ok(Subscription {
+            id,
+            topics,
+            client: self.clone(),
+            receiver,
+        })
+    }
+
+    async fn unsubscribe(&self, subscription_id: usize) -> Result<(), WsClientError> {
+        let (ack, response) = oneshot::channel();
+        self.send_command(Command::Unsubscribe {
+            id: self.next_id(),
+            subscription_id,
+            ack,
+        })?;
+        response
+            .await
+            .map_err(|_| WsClientError::ConnectionClosed)?
+    }
+
+    /// Return true if the background connection has been closed.
+    pub fn is_closed(&self) -> bool {
+        self.commands.is_closed()
+    }
+
+    fn next_id(&self) -> usize {
+        self.next_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn send_command(&self, command: Command) -> Result<(), WsClientError> {
+        self.commands
+            .send(command)
+            .map_err(|_| WsClientError::ConnectionClosed)
+    }
+}
+
+/// The event stream of the subscribed topics.
+#[derive(Debug)]
+pub struct Subscription {
+    id: usize,
+    topics: Vec<Topic>,
+    client: WsClient,
+    receiver: event_channel::UnboundedReceiver<TxTopicEvent>,
+}
+
+impl Subscription {
+    pub fn topics(&self) -> &[Topic] {
+        &self.topics
+    }
+
+    /// Unsubscribe the topics that are not used by other subscriptions of the same client.
+    pub async fn unsubscribe(self) -> Result<(), WsClientError> {
+        self.client.unsubscribe(self.id).await
+    }
+}
+
+impl Stream for Subscription {
+    type Item = TxTopicEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_next_unpin(cx)
+    }
+}
+
+struct PendingRequest {
+    ack: oneshot::Sender<Result<(), WsClientError>>,
+    /// the subscription to be removed if the subscribe request failed
+    subscription_id: Option<usize>,
+}
+
+#[derive(Default)]
+struct ConnectionState {
+    pending: HashMap<usize, PendingRequest>,
+    subscriptions: HashMap<usize, (Vec<Topic>, event_channel::UnboundedSender<TxTopicEvent>)>,
+}
+
+impl ConnectionState {
+    fn topics_request(method: TopicMethod, topics: Vec<Topic>, id: usize) -> ClientRequest {
+        ClientMessage::TopicsRequest(TopicsRequest { method, topics, id })
+    }
+
+    /// Return the request should be sent to server, the `ack` is replied directly if not needed.
+    fn handle_command(&mut self, command: Command) -> Option<(usize, ClientRequest)> {
+        match command {
+            Command::Subscribe {
+                id,
+                topics,
+                events,
+                ack,
+            } => {
+                // register the subscription before sending request, so that none of the events
+                // pushed in front of the response would be lost.
+                self.subscriptions.insert(id, (topics.clone(), events));
+                self.pending.insert(
+                    id,
+                    PendingRequest {
+                        ack,
+                        subscription_id: Some(id),
+                    },
+                );
+                Some((id, Self::topics_request(TopicMethod::Subscribe, topics, id)))
+            }
+            Command::Unsubscribe {
+                id,
+                subscription_id,
+                ack,
+            } => {
+                let topics = self
+                    .subscriptions
+                    .remove(&subscription_id)
+                    .map(|(topics, _)| topics)
+                    .unwrap_or_default();
+                let unused = self.unused_topics(topics);
+                if unused.is_empty() {
+                    let _ = ack.send(Ok(()));
+                    return None;
+                }
+                self.pending.insert(
+                    id,
+                    PendingRequest {
+                        ack,
+                        subscription_id: None,
+                    },
+                );
+                Some((
+                    id,
+                    Self::topics_request(TopicMethod::UnSubscribe, unused, id),
+                ))
+            }
+        }
+    }
+
+    fn unused_topics(&self, mut topics: Vec<Topic>) -> Vec<Topic> {
+        topics.retain(|topic| {
+            !self
+                .subscriptions
+                .values()
+                .any(|(subscribed, _)| topic.matched(subscribed))
+        });
+        topics
+    }
+
+    fn fail_request(&mut self, id: usize, error: WsClientError) {
+        if let Some(request) = self.pending.remove(&id) {
+            if let Some(subscription_id) = request.subscription_id {
+                self.subscriptions.remove(&subscription_id);
+            }
+            let _ = request.ack.send(Err(error));
+        }
+    }
+
+    fn handle_text(&mut self, text: &str) {
+        let Ok(value) = serde_json::from_str::<Value>(text) else {
+            return;
+        };
+        if value.get("id").is_some() {
+            if let Ok(response) = serde_json::from_value::<WsResponse<Value>>(value) {
+                self.handle_response(response);
+            }
+        } else if let Ok(event) = serde_json::from_value::<Event<Topic, Value>>(value) {
+            self.handle_event(event);
+        }
+    }
+
+    fn handle_response(&mut self, response: WsResponse<Value>) {
+        if response.error_code != 0 {
+            let error = WsClientError::ServerError {
+                code: response.error_code,
+                msg: response.error_msg,
+            };
+            self.fail_request(response.id, error);
+        } else if let Some(request) = self.pending.remove(&response.id) {
+            let _ = request.ack.send(Ok(()));
+        }
+    }
+
+    fn handle_event(&mut self, event: Event<Topic, Value>) {
+        let Ok(data) = ServerEvent::from_topic_msg(event.topic.get_type(), event.data) else {
+            return;
+        };
+        let event = TxTopicEvent {
+            topic: event.topic,
+            topic_index: event.topic_index,
+            data,
+            timestamp: event.timestamp,
+        };
+        // the subscriptions whose stream has been dropped are removed
+        self.subscriptions.retain(|_, (topics, events)| {
+            !event.topic.matched(topics) || events.unbounded_send(event.clone()).is_ok()
+        });
+    }
+}
+
+async fn run_connection(ws: WsStream, mut commands: mpsc::UnboundedReceiver<Command>) {
+    let (mut sink, mut stream) = ws.split();
+    let mut state = ConnectionState::default();
+    loop {
+        tokio::select! {
+            command = commands.recv() => {
+                let Some(command) = command else {
+                    // all the clients are dropped
+                    let _ = sink.close().await;
+                    break;
+                };
+                let Some((id, request)) = state.handle_command(command) else {
+                    continue;
+                };
+                let sent = match request.to_message() {
+                    Ok(msg) => sink.send(msg).await.map_err(WsClientError::from),
+                    Err(e) => Err(e.into()),
+                };
+                if let Err(e) = sent {
+                    state.fail_request(id, e);
+                }
+            }
+            msg = stream.next() => match msg {
+                Some(Ok(Message::Text(text))) => state.handle_text(&text),
+                Some(Ok(Message::Ping(payload))) => {
+                    if sink.send(Message::Pong(payload)).await.is_err() {
+                        break;
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            }
+        }
+    }
+    // dropping the state closes all the pending requests and event streams
+}
@@ -1,2 +1,6 @@
+#[cfg(not(target_arch = "wasm32"))]
+pub mod client;
 pub mod proto;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod resumable;
 pub mod ws_message;
@@ -0,0 +1,177 @@
+use crate::rpc::ZkLinkRpcClient;
+use crate::web_socket::client::{Subscription, WsClient, WsClientError};
+use crate::web_socket::ws_message::message::request::ClientOffset;
+use crate::web_socket::ws_message::message::response::TxTopicEvent;
+use crate::web_socket::ws_message::topic::Topic;
+use futures::{Stream, StreamExt};
+use jsonrpsee::core::ClientError;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// The max events of one `getWebSocketEvents` request.
+pub const DEFAULT_REPLAY_LIMIT: usize = 100;
+
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("{0}")]
+    WsError(#[from] WsClientError),
+    #[error("Replay events error: {0}")]
+    RpcError(#[from] ClientError),
+}
+
+/// Persist the `topic_index` of the last event delivered for every topic, so that a
+/// [`ResumableSubscription`] could be resumed from it, even after a restart.
+pub trait TopicOffsetStore: Send + Sync {
+    /// The `topic_index` of the last delivered event, `None` if no event has been delivered.
+    fn last_index(&self, topic: &Topic) -> Option<i64>;
+    fn save_index(&self, topic: &Topic, topic_index: i64);
+}
+
+/// Keep the offsets in memory, which survives reconnections but not restarts.
+#[derive(Debug, Default)]
+pub struct MemoryOffsetStore(Mutex<HashMap<Topic, i64>>);
+
+impl MemoryOffsetStore {
+    pub fn new(offsets: HashMap<Topic, i64>) -> Self {
+        Self(Mutex::new(offsets))
+    }
+}
+
+impl TopicOffsetStore for MemoryOffsetStore {
+    fn last_index(&self, topic: &Topic) -> Option<i64> {
+        self.0.lock().unwrap().get(topic).copied()
+    }
+
+    fn save_index(&self, topic: &Topic, topic_index: i64) {
+        self.0.lock().unwrap().insert(topic.clone(), topic_index);
+    }
+}
+
+/// `ResumableSubscription` delivers the events of topics in `topic_index` order without
+/// gaps or duplicates across disconnections.
+///
+/// After every (re)connection it subscribes the live topics first, then backfills the
+/// events after the stored offsets by `getWebSocketEvents`, and drops the live events
+/// that have been replayed. A gap in the live stream is backfilled in the same way.
+pub struct ResumableSubscription<C, S> {
+    ws_url: String,
+    rpc: C,
+    store: S,
+    topics: Vec<Topic>,
+    limit: usize,
+    /// the `topic_index` of the last event queued for every topic
+    cursors: HashMap<Topic, i64>,
+    queued: VecDeque<TxTopicEvent>,
+    live: Option<Subscription>,
+}
+
+impl<C, S> ResumableSubscription<C, S>
+where
+    C: ZkLinkRpcClient + Send + Sync,
+    S: TopicOffsetStore,
+{
+    /// Nothing is connected until the first event is polled. The `limit` is at least 1.
+    ///
+    /// The topics without a stored offset start from the first live event: the events
+    /// published before it, including those published while disconnected before any event
+    /// of the topic has been delivered, are not replayed. Store an offset of 0 to replay a
+    /// topic from its first event.
+    pub fn new(ws_url: String, rpc: C, store: S, topics: Vec<Topic>, limit: Option<usize>) -> Self {
+        Self {
+            ws_url,
+            rpc,
+            store,
+            topics,
+            limit: limit.unwrap_or(DEFAULT_REPLAY_LIMIT).max(1),
+            cursors: HashMap::new(),
+            queued: VecDeque::new(),
+            live: None,
+        }
+    }
+
+    /// Return the next event, reconnect if the connection has been dropped.
+    ///
+    /// The offset of the event is saved into the store before it's returned. If an error is
+    /// returned, calling it again will retry from the last delivered event.
+    pub async fn next_event(&mut self) -> Result<TxTopicEvent, ReplayError> {
+        loop {
+            if let Some(event) = self.queued.pop_front() {
+                self.store.save_index(&event.topic, event.topic_index);
+                return Ok(event);
+            }
+            match self.live.as_mut() {
+                Some(live) => match live.next().await {
+                    Some(event) => self.accept_live(event).await?,
+                    None => self.live = None,
+                },
+                None => self.resume().await?,
+            }
+        }
+    }
+
+    /// Convert into a stream, which never ends, the errors are yielded as items.
+    pub fn into_stream(self) -> impl Stream<Item = Result<TxTopicEvent, ReplayError>> {
+        futures::stream::unfold(self, |mut subscription| async move {
+            let event = subscription.next_event().await;
+            Some((event, subscription))
+        })
+    }
+
+    async fn resume(&mut self) -> Result<(), ReplayError> {
+        let client = WsClient::connect(&self.ws_url).await?;
+        let live = client.subscribe(self.topics.clone()).await?;
+        // the queue is always empty here, so the stored offsets are the cursors
+        self.cursors = self
+            .topics
+            .iter()
+            .filter_map(|topic| Some((topic.clone(), self.store.last_index(topic)?)))
+            .collect();
+        for topic in self.topics.clone() {
+            if self.cursors.contains_key(&topic) {
+                self.backfill(&topic).await?;
+            }
+        }
+        self.live = Some(live);
+        Ok(())
+    }
+
+    /// Queue the events after the cursor of `topic` until the latest one.
+    async fn backfill(&mut self, topic: &Topic) -> Result<(), ReplayError> {
+        loop {
+            let cursor = self.cursors[topic];
+            let offset = ClientOffset {
+                from_topic_index_included: cursor + 1,
+                limit: Some(self.limit),
+            };
+            let events = self.rpc.get_websocket_events(topic.clone(), offset).await?;
+            let is_last_page = events.len() < self.limit;
+            for event in events {
+                self.enqueue(event);
+            }
+            // stop if the node returns the events which have been queued
+            if is_last_page || self.cursors[topic] == cursor {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn accept_live(&mut self, event: TxTopicEvent) -> Result<(), ReplayError> {
+        if let Some(&cursor) = self.cursors.get(&event.topic) {
+            if event.topic_index > cursor + 1 {
+                let topic = event.topic.clone();
+                self.backfill(&topic).await?;
+            }
+        }
+        self.enqueue(event);
+        Ok(())
+    }
+
+    fn enqueue(&mut self, event: TxTopicEvent) {
+        let cursor = self.cursors.get(&event.topic).copied();
+        if cursor.map_or(true, |cursor| event.topic_index > cursor) {
+            self.cursors.insert(event.topic.clone(), event.topic_index);
+            self.queued.push_back(event);
+        }
+    }
+}
@@ -12,6 +12,7 @@ use zklink_sdk_types::tx_type::zklink_tx::ZkLinkTx;
 pub type TxTopicEvent = Event<Topic, ServerEvent>;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum ServerEvent {
     PriorityEvent(PriorityEventResp),
@@ -19,6 +20,7 @@ pub enum ServerEvent {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct PriorityEventResp {
     pub tx_hash: TxHash,
@@ -0,0 +1,431 @@
+#[cfg(feature = "mock")]
+#[cfg(test)]
+mod test {
+    use futures::StreamExt;
+    use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::net::TcpListener;
+    use zklink_sdk_provider::error::{ProviderError, ZkLinkNodeError};
+    use zklink_sdk_provider::mock_node::{MockAccount, MockWithdrawal, MockZkLinkNode};
+    use zklink_sdk_provider::nonce_manager::NonceManager;
+    use zklink_sdk_provider::response::AccountQuery;
+    use zklink_sdk_provider::tx_tracker::{
+        wait_for_tx, wait_for_tx_with_subscription, Confirmation, WaitTxError,
+    };
+    use zklink_sdk_provider::web_socket::client::WsClient;
+    use zklink_sdk_provider::web_socket::ws_message::message::request::ClientOffset;
+    use zklink_sdk_provider::web_socket::ws_message::message::response::ServerEvent;
+    use zklink_sdk_provider::web_socket::ws_message::topic::Topic;
+    use zklink_sdk_provider::ZkLinkRpcClient;
+    use zklink_sdk_signers::zklink_signer::ZkLinkSigner;
+    use zklink_sdk_types::basic_types::tx_hash::TxHash;
+    use zklink_sdk_types::basic_types::{
+        AccountId, BigUint, ChainId, Nonce, SubAccountId, TimeStamp, TokenId, ZkLinkAddress,
+    };
+    use zklink_sdk_types::tx_builder::{ForcedExitBuilder, TransferBuilder};
+    use zklink_sdk_types::tx_type::transfer::Transfer;
+    use zklink_sdk_types::tx_type::zklink_tx::ZkLinkTx;
+    use zklink_sdk_types::tx_type::ZkSignatureTrait;
+
+    const PRIVATE_KEY: &str = "be725250b123a39dab5b7579334d5888987c72a58f4508062545fe6e08ca94f4";
+    const TO_ADDRESS: &str = "0xAFAFf3aD1a0425D792432D9eCD1c3e26Ef2C42E9";
+
+    fn signed_transfer(signer: &ZkLinkSigner, nonce: u32, amount: u32) -> Transfer {
+        let mut tx = TransferBuilder {
+            account_id: AccountId(10),
+            to_address: ZkLinkAddress::from_str(TO_ADDRESS).unwrap(),
+            from_sub_account_id: SubAccountId(1),
+            to_sub_account_id: SubAccountId(1),
+            token: TokenId(18),
+            amount: BigUint::from(amount),
+            fee: BigUint::from(3u32),
+            nonce: Nonce(nonce),
+            timestamp: TimeStamp(1693472232),
+        }
+        .build();
+        tx.sign(signer).unwrap();
+        tx
+    }
+
+    async fn start_node(signer: &ZkLinkSigner) -> (MockZkLinkNode, HttpClient) {
+        let node = MockZkLinkNode::new();
+        let mut account = MockAccount::new(
+            AccountId(10),
+            ZkLinkAddress::from_str("0x3498F456645270eE003441df82C718b56c0e6666").unwrap(),
+        );
+        account.pub_key_hash = signer.public_key().public_key_hash();
+        node.add_account(account);
+        node.set_balance(
+            AccountId(10),
+            SubAccountId(1),
+            TokenId(18),
+            BigUint::from(10000u32),
+        );
+        let (addr, handle) = node.start_rpc_server("127.0.0.1:0").await.unwrap();
+        tokio::spawn(handle.stopped());
+        let client = HttpClientBuilder::default()
+            .build(format!("http://{addr}"))
+            .unwrap();
+        (node, client)
+    }
+
+    #[tokio::test]
+    async fn test_mock_node_transfer() {
+        let signer = ZkLinkSigner::new_from_hex_eth_signer(PRIVATE_KEY).unwrap();
+        let (node, client) = start_node(&signer).await;
+
+        let tx = signed_transfer(&signer, 0, 1000);
+        let tx_hash = client.tx_submit(tx.into(), None, None).await.unwrap();
+        let tx_resp = client.tx_info(tx_hash, false).await.unwrap();
+        assert!(tx_resp.receipt.success);
+
+        let account = client
+            .account_info(AccountQuery::Id(AccountId(10)))
+            .await
+            .unwrap();
+        assert_eq!(account.nonce, Nonce(1));
+        let account = node.account(AccountId(10)).unwrap();
+        assert_eq!(
+            account.balance(SubAccountId(1), TokenId(18)),
+            BigUint::from(8997u32)
+        );
+        let to = client
+            .account_info(AccountQuery::Address(
+                ZkLinkAddress::from_str(TO_ADDRESS).unwrap(),
+            ))
+            .await
+            .unwrap();
+        let to = node.account(to.id).unwrap();
+        assert_eq!(
+            to.balance(SubAccountId(1), TokenId(18)),
+            BigUint::from(1000u32)
+        );
+
+        // the nonce has been used
+        let tx = signed_transfer(&signer, 0, 1000);
+        let error = client.tx_submit(tx.into(), None, None).await.unwrap_err();
+        assert!(ZkLinkNodeError::from_client_error(&error)
+            .unwrap()
+            .is_nonce_error());
+        // not signed by the account
+        let other_signer = ZkLinkSigner::new().unwrap();
+        let tx = signed_transfer(&other_signer, 1, 1000);
+        let error = client.tx_submit(tx.into(), None, None).await.unwrap_err();
+        assert!(matches!(
+            ZkLinkNodeError::from_client_error(&error),
+            Some(ZkLinkNodeError::InvalidSignature(_))
+        ));
+        // executed but failed
+        let tx = signed_transfer(&signer, 1, 100000);
+        let tx_hash = client.tx_submit(tx.into(), None, None).await.unwrap();
+        let tx_resp = client.tx_info(tx_hash, false).await.unwrap();
+        assert!(!tx_resp.receipt.success);
+        assert_eq!(node.txs().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_mock_node_forced_exit() {
+        let signer = ZkLinkSigner::new_from_hex_eth_signer(PRIVATE_KEY).unwrap();
+        let (node, client) = start_node(&signer).await;
+        let target = ZkLinkAddress::from_str(TO_ADDRESS).unwrap();
+        let forced_exit = |nonce: u32| {
+            let mut tx = ForcedExitBuilder {
+                to_chain_id: ChainId(1),
+                initiator_account_id: AccountId(10),
+                initiator_sub_account_id: SubAccountId(1),
+                target: target.clone(),
+                target_sub_account_id: SubAccountId(1),
+                l2_source_token: TokenId(18),
+                l1_target_token: TokenId(18),
+                initiator_nonce: Nonce(nonce),
+                exit_amount: BigUint::from(600u32),
+                withdraw_to_l1: true,
+                timestamp: TimeStamp(1693472232),
+            }
+            .build();
+            tx.sign(&signer).unwrap();
+            ZkLinkTx::from(tx)
+        };
+
+        // the target is not created by the forced exit
+        let error = client
+            .tx_submit(forced_exit(0), None, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            ZkLinkNodeError::from_client_error(&error),
+            Some(ZkLinkNodeError::AccountNotFound(_))
+        ));
+        assert!(client
+            .account_info(AccountQuery::Address(target.clone()))
+            .await
+            .is_err());
+
+        node.add_account(MockAccount::new(AccountId(20), target.clone()));
+        node.set_balance(
+            AccountId(20),
+            SubAccountId(1),
+            TokenId(18),
+            BigUint::from(1000u32),
+        );
+        let tx_hash = client.tx_submit(forced_exit(0), None, None).await.unwrap();
+        assert!(
+            client
+                .tx_info(tx_hash, false)
+                .await
+                .unwrap()
+                .receipt
+                .success
+        );
+        let target_account = node.account(AccountId(20)).unwrap();
+        assert_eq!(
+            target_account.balance(SubAccountId(1), TokenId(18)),
+            BigUint::from(400u32)
+        );
+        // the initiator pays nothing
+        let initiator = node.account(AccountId(10)).unwrap();
+        assert_eq!(
+            initiator.balance(SubAccountId(1), TokenId(18)),
+            BigUint::from(10000u32)
+        );
+        assert_eq!(
+            node.withdrawals(),
+            vec![MockWithdrawal {
+                chain_id: ChainId(1),
+                to: target,
+                token: TokenId(18),
+                amount: BigUint::from(600u32),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mock_node_events() {
+        let signer = ZkLinkSigner::new_from_hex_eth_signer(PRIVATE_KEY).unwrap();
+        let (node, client) = start_node(&signer).await;
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_url = format!("ws://{}", listener.local_addr().unwrap());
+        let ws_node = node.clone();
+        tokio::spawn(async move { ws_node.serve_ws(listener).await });
+
+        let topic = Topic::TxExecuteResult {
+            sub_account_id: SubAccountId(1),
+        };
+        let ws_client = WsClient::connect(&ws_url).await.unwrap();
+        let mut subscription = ws_client.subscribe(vec![topic.clone()]).await.unwrap();
+        let tx = signed_transfer(&signer, 0, 1000);
+        let tx_hash = client.tx_submit(tx.into(), None, None).await.unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), subscription.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.topic_index, 1);
+        match event.data {
+            ServerEvent::TxExecuteResult(tx_resp) => assert_eq!(tx_resp.tx_hash, tx_hash),
+            _ => panic!("unexpected event"),
+        }
+        let offset = ClientOffset {
+            from_topic_index_included: 1,
+            limit: None,
+        };
+        let events = client.get_websocket_events(topic, offset).await.unwrap();
+        assert_eq!(events.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_nonce_manager() {
+        let signer = ZkLinkSigner::new_from_hex_eth_signer(PRIVATE_KEY).unwrap();
+        let (_node, client) = start_node(&signer).await;
+        let manager = Arc::new(NonceManager::new(client.clone()));
+
+        let tasks: Vec<_> = (0..10)
+            .map(|_| {
+                let manager = manager.clone();
+                tokio::spawn(async move { manager.next_nonce(AccountId(10)).await.unwrap() })
+            })
+            .collect();
+        let mut nonces = vec![];
+        for task in tasks {
+            nonces.push(task.await.unwrap().0);
+        }
+        nonces.sort();
+        assert_eq!(nonces, (0..10).collect::<Vec<_>>());
+
+        // only the nonce 0 is used, the nonces handed out are invalid now
+        let tx = signed_transfer(&signer, 0, 1000);
+        client.tx_submit(tx.into(), None, None).await.unwrap();
+        let tx = signed_transfer(&signer, 2, 1000);
+        let error = client
+            .tx_submit(tx.into(), None, None)
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(manager
+            .handle_rejection(AccountId(10), &error)
+            .await
+            .unwrap());
+        assert_eq!(manager.next_nonce(AccountId(10)).await.unwrap(), Nonce(1));
+        // the error is decoded by the code
+        let tx = signed_transfer(&signer, 3, 1000);
+        let error = client.tx_submit(tx.into(), None, None).await.unwrap_err();
+        let ProviderError::NodeError(error) = ProviderError::from(error) else {
+            panic!("not a node error");
+        };
+        assert!(error.is_nonce_error());
+        assert_eq!(
+            manager
+                .next_sub_account_nonce(AccountId(10), SubAccountId(1))
+                .await
+                .unwrap(),
+            Nonce(0)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_nonce_manager_release() {
+        let signer = ZkLinkSigner::new_from_hex_eth_signer(PRIVATE_KEY).unwrap();
+        let (_node, client) = start_node(&signer).await;
+        let manager = NonceManager::new(client);
+
+        assert_eq!(manager.next_nonce(AccountId(10)).await.unwrap(), Nonce(0));
+        assert_eq!(manager.next_nonce(AccountId(10)).await.unwrap(), Nonce(1));
+        // the last nonce is handed out again
+        manager.release_nonce(AccountId(10), Nonce(1)).await;
+        assert_eq!(manager.next_nonce(AccountId(10)).await.unwrap(), Nonce(1));
+        // a later nonce has been handed out, the nonces are fetched from the node
+        manager.release_nonce(AccountId(10), Nonce(0)).await;
+        assert_eq!(manager.next_nonce(AccountId(10)).await.unwrap(), Nonce(0));
+    }
+
+    #[tokio::test]
+    async fn test_nonce_manager_release_sub_account_nonce() {
+        let signer = ZkLinkSigner::new_from_hex_eth_signer(PRIVATE_KEY).unwrap();
+        let (node, client) = start_node(&signer).await;
+        let mut account = node.account(AccountId(10)).unwrap();
+        account.sub_account_nonces.insert(SubAccountId(1), Nonce(5));
+        node.add_account(account);
+        let manager = NonceManager::new(client);
+
+        let next = |sub_account_id| manager.next_sub_account_nonce(AccountId(10), sub_account_id);
+        assert_eq!(next(SubAccountId(1)).await.unwrap(), Nonce(5));
+        assert_eq!(next(SubAccountId(1)).await.unwrap(), Nonce(6));
+        assert_eq!(next(SubAccountId(2)).await.unwrap(), Nonce(0));
+        // the last nonce of the sub account is handed out again, the others are kept
+        manager
+            .release_sub_account_nonce(AccountId(10), SubAccountId(1), Nonce(6))
+            .await;
+        assert_eq!(next(SubAccountId(1)).await.unwrap(), Nonce(6));
+        assert_eq!(next(SubAccountId(2)).await.unwrap(), Nonce(1));
+        // a sub account without handed out nonces is ignored
+        manager
+            .release_sub_account_nonce(AccountId(10), SubAccountId(3), Nonce(0))
+            .await;
+        assert_eq!(next(SubAccountId(2)).await.unwrap(), Nonce(2));
+        // a later nonce has been handed out, the nonces are fetched from the node
+        manager
+            .release_sub_account_nonce(AccountId(10), SubAccountId(1), Nonce(5))
+            .await;
+        assert_eq!(next(SubAccountId(1)).await.unwrap(), Nonce(5));
+        assert_eq!(next(SubAccountId(2)).await.unwrap(), Nonce(0));
+    }
+
+    #[tokio::test]
+    async fn test_nonce_manager_resync() {
+        let signer = ZkLinkSigner::new_from_hex_eth_signer(PRIVATE_KEY).unwrap();
+        let (_node, client) = start_node(&signer).await;
+        let manager = NonceManager::new(client.clone());
+
+        for expected in 0..3 {
+            let nonce = manager.next_nonce(AccountId(10)).await.unwrap();
+            assert_eq!(nonce, Nonce(expected));
+        }
+        let tx = signed_transfer(&signer, 0, 1000);
+        client.tx_submit(tx.into(), None, None).await.unwrap();
+        // not caused by the nonce, the local nonces are kept
+        assert!(!manager
+            .handle_rejection(AccountId(10), "Insufficient balance")
+            .await
+            .unwrap());
+        assert_eq!(manager.next_nonce(AccountId(10)).await.unwrap(), Nonce(3));
+        // the nonces handed out after the executed tx are dropped
+        manager.resync(AccountId(10)).await.unwrap();
+        assert_eq!(manager.next_nonce(AccountId(10)).await.unwrap(), Nonce(1));
+        // the unknown account is not cached, it's fetched again when used next time
+        assert!(manager.resync(AccountId(11)).await.is_err());
+        assert!(manager.next_nonce(AccountId(11)).await.is_err());
+        assert_eq!(manager.next_nonce(AccountId(10)).await.unwrap(), Nonce(2));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_tx() {
+        let signer = ZkLinkSigner::new_from_hex_eth_signer(PRIVATE_KEY).unwrap();
+        let (_node, client) = start_node(&signer).await;
+        let timeout = Duration::from_secs(3);
+
+        let tx = signed_transfer(&signer, 0, 1000);
+        let tx_hash = client.tx_submit(tx.into(), None, None).await.unwrap();
+        let confirmation = wait_for_tx(&client, tx_hash, Confirmation::Verified, timeout)
+            .await
+            .unwrap();
+        assert_eq!(confirmation.tx.tx_hash, tx_hash);
+        assert!(confirmation.onchain.is_some());
+
+        let tx = signed_transfer(&signer, 1, 100000);
+        let tx_hash = client.tx_submit(tx.into(), None, None).await.unwrap();
+        let error = wait_for_tx(&client, tx_hash, Confirmation::Executed, timeout)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            WaitTxError::TxFailed(hash, ZkLinkNodeError::InsufficientBalance(_)) if hash == tx_hash
+        ));
+
+        // never submitted
+        let error = wait_for_tx(
+            &client,
+            TxHash::default(),
+            Confirmation::Executed,
+            Duration::from_millis(100),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(error, WaitTxError::Timeout(_)));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_tx_with_subscription() {
+        let signer = ZkLinkSigner::new_from_hex_eth_signer(PRIVATE_KEY).unwrap();
+        let (node, client) = start_node(&signer).await;
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_url = format!("ws://{}", listener.local_addr().unwrap());
+        let ws_node = node.clone();
+        tokio::spawn(async move { ws_node.serve_ws(listener).await });
+
+        let topic = Topic::TxExecuteResult {
+            sub_account_id: SubAccountId(1),
+        };
+        let ws_client = WsClient::connect(&ws_url).await.unwrap();
+        let mut subscription = ws_client.subscribe(vec![topic]).await.unwrap();
+        let tx: ZkLinkTx = signed_transfer(&signer, 0, 1000).into();
+        let tx_hash = tx.tx_hash();
+        let submit_client = client.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            submit_client.tx_submit(tx, None, None).await
+        });
+        let confirmation = wait_for_tx_with_subscription(
+            &client,
+            &mut subscription,
+            tx_hash,
+            Confirmation::Executed,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+        assert!(confirmation.tx.receipt.success);
+        assert!(confirmation.onchain.is_none());
+    }
+}
@@ -0,0 +1,341 @@
+#[cfg(test)]
+mod test {
+    use futures::{SinkExt, StreamExt};
+    use jsonrpsee::http_client::HttpClientBuilder;
+    use jsonrpsee::server::{RpcModule, ServerBuilder};
+    use std::collections::HashMap;
+    use std::str::FromStr;
+    use std::sync::atomic::{AtomicI64, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+    use tokio_tungstenite::tungstenite::Message;
+    use zklink_sdk_provider::response::{TxReceiptResp, TxResp};
+    use zklink_sdk_provider::web_socket::client::{WsClient, WsClientError};
+    use zklink_sdk_provider::web_socket::proto::event::Event;
+    use zklink_sdk_provider::web_socket::proto::request::TopicsRequest;
+    use zklink_sdk_provider::web_socket::proto::response::{TopicsResponse, WsResponse};
+    use zklink_sdk_provider::web_socket::proto::topic::TopicMethod;
+    use zklink_sdk_provider::web_socket::resumable::{MemoryOffsetStore, ResumableSubscription};
+    use zklink_sdk_provider::web_socket::ws_message::message::request::ClientOffset;
+    use zklink_sdk_provider::web_socket::ws_message::message::response::ServerEvent;
+    use zklink_sdk_provider::web_socket::ws_message::topic::Topic;
+    use zklink_sdk_types::basic_types::{
+        AccountId, BigUint, Nonce, SubAccountId, TimeStamp, TokenId, ZkLinkAddress,
+    };
+    use zklink_sdk_types::tx_builder::TransferBuilder;
+    use zklink_sdk_types::tx_type::zklink_tx::ZkLinkTx;
+
+    fn tx_result(nonce: u32) -> ServerEvent {
+        let tx: ZkLinkTx = TransferBuilder {
+            account_id: AccountId(10),
+            to_address: ZkLinkAddress::from_str("0xAFAFf3aD1a0425D792432D9eCD1c3e26Ef2C42E9")
+                .unwrap(),
+            from_sub_account_id: SubAccountId(1),
+            to_sub_account_id: SubAccountId(1),
+            token: TokenId(18),
+            amount: BigUint::from(10000u32),
+            fee: BigUint::from(3u32),
+            nonce: Nonce(nonce),
+            timestamp: TimeStamp(1693472232),
+        }
+        .build()
+        .into();
+        ServerEvent::TxExecuteResult(TxResp {
+            tx_hash: tx.tx_hash(),
+            tx,
+            receipt: TxReceiptResp {
+                executed: true,
+                executed_timestamp: None,
+                success: true,
+                fail_reason: None,
+                block: None,
+                index: None,
+            },
+            updates: vec![],
+        })
+    }
+
+    /// A gateway stand-in that acks every topics request, forwards it to the test and pushes
+    /// the events of `topic_indexes` for each subscribed topic.
+    async fn start_server(
+        topic_indexes: Vec<i64>,
+    ) -> (String, mpsc::UnboundedReceiver<TopicsRequest<Topic>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            while let Some(Ok(Message::Text(text))) = ws.next().await {
+                let request: TopicsRequest<Topic> = serde_json::from_str(&text).unwrap();
+                let (error_code, error_msg) = if request.topics.is_empty() {
+                    (1, "empty topics".to_string())
+                } else {
+                    (0, String::new())
+                };
+                let response = WsResponse {
+                    result: TopicsResponse {
+                        topics: request.topics.clone(),
+                    },
+                    error_code,
+                    error_msg,
+                    id: request.id,
+                };
+                let response = serde_json::to_string(&response).unwrap();
+                ws.send(Message::Text(response)).await.unwrap();
+                if request.method == TopicMethod::Subscribe {
+                    for topic in &request.topics {
+                        for index in &topic_indexes {
+                            let event = Event::new(topic.clone(), *index, tx_result(1), None);
+                            let event = serde_json::to_string(&event).unwrap();
+                            ws.send(Message::Text(event)).await.unwrap();
+                        }
+                    }
+                }
+                let _ = sender.send(request);
+            }
+        });
+        (url, receiver)
+    }
+
+    #[tokio::test]
+    async fn test_ws_subscribe() {
+        let (url, mut requests) = start_server(vec![1]).await;
+        let client = WsClient::connect(&url).await.unwrap();
+        let topic = Topic::TxExecuteResult {
+            sub_account_id: SubAccountId(1),
+        };
+        let mut subscription = client.subscribe(vec![topic.clone()]).await.unwrap();
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, TopicMethod::Subscribe);
+        assert_eq!(request.topics, vec![topic.clone()]);
+
+        let event = tokio::time::timeout(Duration::from_secs(5), subscription.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.topic, topic);
+        assert_eq!(event.topic_index, 1);
+        assert!(matches!(event.data, ServerEvent::TxExecuteResult(_)));
+
+        subscription.unsubscribe().await.unwrap();
+        let request = requests.recv().await.unwrap();
+        assert_eq!(request.method, TopicMethod::UnSubscribe);
+        assert_eq!(request.topics, vec![topic]);
+    }
+
+    #[tokio::test]
+    async fn test_ws_subscribe_error() {
+        let (url, _requests) = start_server(vec![]).await;
+        let client = WsClient::connect(&url).await.unwrap();
+        let ret = client.subscribe(vec![]).await;
+        assert!(matches!(
+            ret,
+            Err(WsClientError::ServerError { code: 1, .. })
+        ));
+    }
+
+    /// A rpc server that replays the events `1..=history` of any topic.
+    async fn start_rpc_server(history: i64) -> String {
+        start_growing_rpc_server(Arc::new(AtomicI64::new(history))).await
+    }
+
+    /// Same as [`start_rpc_server`], but the history could grow while the server is running.
+    async fn start_growing_rpc_server(history: Arc<AtomicI64>) -> String {
+        let mut module = RpcModule::new(());
+        module
+            .register_method("getWebSocketEvents", move |params, _| {
+                let (topic, offset): (Topic, ClientOffset) = params.parse()?;
+                let events: Vec<_> = (offset.from_topic_index_included
+                    ..=history.load(Ordering::SeqCst))
+                    .take(offset.limit.unwrap_or(100))
+                    .map(|index| Event::new(topic.clone(), index, tx_result(1), None))
+                    .collect();
+                Ok::<_, jsonrpsee::types::ErrorObjectOwned>(events)
+            })
+            .unwrap();
+        let server = ServerBuilder::default().build("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", server.local_addr().unwrap());
+        let handle = server.start(module);
+        tokio::spawn(handle.stopped());
+        url
+    }
+
+    /// A gateway stand-in that accepts one connection for every session. Each connection acks
+    /// the subscription, pushes the events of its session and, except the last one, is closed
+    /// when the test sends a signal into the returned sender.
+    async fn start_reconnect_server(
+        sessions: Vec<Vec<i64>>,
+    ) -> (
+        String,
+        mpsc::UnboundedSender<()>,
+        mpsc::UnboundedReceiver<TopicsRequest<Topic>>,
+    ) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let (drop_sender, mut drop_signals) = mpsc::unbounded_channel();
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let last_session = sessions.len() - 1;
+            for (session, topic_indexes) in sessions.into_iter().enumerate() {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                let Some(Ok(Message::Text(text))) = ws.next().await else {
+                    return;
+                };
+                let request: TopicsRequest<Topic> = serde_json::from_str(&text).unwrap();
+                let response = WsResponse {
+                    result: TopicsResponse {
+                        topics: request.topics.clone(),
+                    },
+                    error_code: 0,
+                    error_msg: String::new(),
+                    id: request.id,
+                };
+                let response = serde_json::to_string(&response).unwrap();
+                ws.send(Message::Text(response)).await.unwrap();
+                for topic in &request.topics {
+                    for index in &topic_indexes {
+                        let event = Event::new(topic.clone(), *index, tx_result(1), None);
+                        let event = serde_json::to_string(&event).unwrap();
+                        ws.send(Message::Text(event)).await.unwrap();
+                    }
+                }
+                let _ = sender.send(request);
+                if session == last_session {
+                    while let Some(Ok(_)) = ws.next().await {}
+                } else {
+                    drop_signals.recv().await;
+                    let _ = ws.close(None).await;
+                }
+            }
+        });
+        (url, drop_sender, receiver)
+    }
+
+    #[tokio::test]
+    async fn test_resumable_subscription() {
+        let topic = Topic::TxExecuteResult {
+            sub_account_id: SubAccountId(1),
+        };
+        // 3..=8 are replayed in pages of 2, the live events 5, 6 and 8 must be dropped
+        let (ws_url, _requests) = start_server(vec![5, 6, 8]).await;
+        let rpc_url = start_rpc_server(8).await;
+        let rpc = HttpClientBuilder::default().build(rpc_url).unwrap();
+        let store = MemoryOffsetStore::new(HashMap::from([(topic.clone(), 2)]));
+        let mut subscription =
+            ResumableSubscription::new(ws_url, rpc, store, vec![topic.clone()], Some(2));
+        for expected in 3..=8 {
+            let event = tokio::time::timeout(Duration::from_secs(5), subscription.next_event())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(event.topic, topic);
+            assert_eq!(event.topic_index, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resumable_subscription_zero_limit() {
+        let topic = Topic::TxExecuteResult {
+            sub_account_id: SubAccountId(1),
+        };
+        // the limit is clamped to 1, so 3..=5 are replayed one by one
+        let (ws_url, _requests) = start_server(vec![5]).await;
+        let rpc_url = start_rpc_server(5).await;
+        let rpc = HttpClientBuilder::default().build(rpc_url).unwrap();
+        let store = MemoryOffsetStore::new(HashMap::from([(topic.clone(), 2)]));
+        let mut subscription =
+            ResumableSubscription::new(ws_url, rpc, store, vec![topic.clone()], Some(0));
+        for expected in 3..=5 {
+            let event = tokio::time::timeout(Duration::from_secs(5), subscription.next_event())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(event.topic_index, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resumable_subscription_reconnect() {
+        let topic = Topic::TxExecuteResult {
+            sub_account_id: SubAccountId(1),
+        };
+        // 4, 5 and 6 are published while the socket is down, the second connection pushes
+        // the live events 5 and 7, which overlap the replayed ones
+        let (ws_url, drop_connection, mut requests) =
+            start_reconnect_server(vec![vec![1, 2, 3], vec![5, 7]]).await;
+        let history = Arc::new(AtomicI64::new(3));
+        let rpc_url = start_growing_rpc_server(history.clone()).await;
+        let rpc = HttpClientBuilder::default().build(rpc_url).unwrap();
+        let store = MemoryOffsetStore::new(HashMap::from([(topic.clone(), 0)]));
+        let mut subscription =
+            ResumableSubscription::new(ws_url, rpc, store, vec![topic.clone()], Some(2));
+
+        let mut delivered = vec![];
+        for _ in 1..=3 {
+            let event = tokio::time::timeout(Duration::from_secs(5), subscription.next_event())
+                .await
+                .unwrap()
+                .unwrap();
+            delivered.push(event.topic_index);
+        }
+        history.store(7, Ordering::SeqCst);
+        drop_connection.send(()).unwrap();
+        for _ in 4..=7 {
+            let event = tokio::time::timeout(Duration::from_secs(5), subscription.next_event())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(event.topic, topic);
+            delivered.push(event.topic_index);
+        }
+        assert_eq!(delivered, (1..=7).collect::<Vec<_>>());
+        // every event has been delivered exactly once, nothing is left to deliver
+        let ret = tokio::time::timeout(Duration::from_millis(500), subscription.next_event()).await;
+        assert!(ret.is_err());
+        // the topic has been subscribed once for every connection
+        for _ in 0..2 {
+            let request = requests.recv().await.unwrap();
+            assert_eq!(request.method, TopicMethod::Subscribe);
+            assert_eq!(request.topics, vec![topic.clone()]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resumable_subscription_without_offset() {
+        let topic = Topic::TxExecuteResult {
+            sub_account_id: SubAccountId(1),
+        };
+        // without a stored offset, 1 and 2 published before the first connection are not
+        // replayed, but 5 published while the socket is down is replayed after 3 and 4 have
+        // been delivered
+        let (ws_url, drop_connection, _requests) =
+            start_reconnect_server(vec![vec![3, 4], vec![6]]).await;
+        let history = Arc::new(AtomicI64::new(4));
+        let rpc_url = start_growing_rpc_server(history.clone()).await;
+        let rpc = HttpClientBuilder::default().build(rpc_url).unwrap();
+        let store = MemoryOffsetStore::default();
+        let mut subscription =
+            ResumableSubscription::new(ws_url, rpc, store, vec![topic.clone()], None);
+
+        for expected in 3..=4 {
+            let event = tokio::time::timeout(Duration::from_secs(5), subscription.next_event())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(event.topic_index, expected);
+        }
+        history.store(6, Ordering::SeqCst);
+        drop_connection.send(()).unwrap();
+        for expected in 5..=6 {
+            let event = tokio::time::timeout(Duration::from_secs(5), subscription.next_event())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(event.topic_index, expected);
+        }
+    }
+}
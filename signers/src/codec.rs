@@ -0,0 +1,82 @@
+//! The borsh encoding of the signatures, they are encoded as the same bytes of `from_bytes`.
+use crate::eth_signer::eip1271_signature::EIP1271Signature;
+use crate::eth_signer::packed_eth_signature::PackedEthSignature;
+use crate::starknet_signer::ecdsa_signature::StarkEip712Signature;
+use crate::zklink_signer::pubkey_hash::PubKeyHash;
+use crate::zklink_signer::signature::ZkLinkSignature;
+use crate::zklink_signer::{NEW_PUBKEY_HASH_BYTES_LEN, SIGNATURE_SIZE};
+use borsh::io::{Error, ErrorKind, Read, Result, Write};
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// The packed ethereum signature is `r | s | v`.
+const PACKED_ETH_SIGNATURE_SIZE: usize = 65;
+/// The starknet signature is `pub_key | r | s`.
+const STARK_SIGNATURE_SIZE: usize = 96;
+
+fn invalid_data(error: impl ToString) -> Error {
+    Error::new(ErrorKind::InvalidData, error.to_string())
+}
+
+impl BorshSerialize for ZkLinkSignature {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.as_bytes())
+    }
+}
+
+impl BorshDeserialize for ZkLinkSignature {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let bytes = <[u8; SIGNATURE_SIZE]>::deserialize_reader(reader)?;
+        ZkLinkSignature::from_bytes(&bytes).map_err(invalid_data)
+    }
+}
+
+impl BorshSerialize for PubKeyHash {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.data)
+    }
+}
+
+impl BorshDeserialize for PubKeyHash {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let data = <[u8; NEW_PUBKEY_HASH_BYTES_LEN]>::deserialize_reader(reader)?;
+        Ok(PubKeyHash { data })
+    }
+}
+
+impl BorshSerialize for PackedEthSignature {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.serialize_packed())
+    }
+}
+
+impl BorshDeserialize for PackedEthSignature {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let bytes = <[u8; PACKED_ETH_SIGNATURE_SIZE]>::deserialize_reader(reader)?;
+        PackedEthSignature::deserialize_packed(&bytes).map_err(invalid_data)
+    }
+}
+
+impl BorshSerialize for EIP1271Signature {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        BorshSerialize::serialize(&self.0, writer)
+    }
+}
+
+impl BorshDeserialize for EIP1271Signature {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        Ok(EIP1271Signature(Vec::deserialize_reader(reader)?))
+    }
+}
+
+impl BorshSerialize for StarkEip712Signature {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writer.write_all(&self.to_bytes_be())
+    }
+}
+
+impl BorshDeserialize for StarkEip712Signature {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let bytes = <[u8; STARK_SIGNATURE_SIZE]>::deserialize_reader(reader)?;
+        StarkEip712Signature::from_bytes_be(&bytes).map_err(invalid_data)
+    }
+}
@@ -1,3 +1,4 @@
+use crate::keystore::KeystoreError;
 use crate::RpcErr;
 use thiserror::Error;
 #[cfg(target_arch = "wasm32")]
@@ -35,6 +36,8 @@ pub enum EthSignerError {
     CustomError(String),
     #[error("{0}")]
     RpcSignError(RpcErr),
+    #[error("{0}")]
+    KeystoreError(#[from] KeystoreError),
 }
 
 #[cfg(target_arch = "wasm32")]
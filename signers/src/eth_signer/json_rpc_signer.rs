@@ -1,6 +1,7 @@
-use crate::eth_signer::{EthSignerError, PackedEthSignature};
+use crate::eth_signer::{EthSignerError, EthTypedData, PackedEthSignature};
 use crate::RpcErr;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -21,6 +22,9 @@ extern "C" {
     #[wasm_bindgen(structural,catch, method)]
     async fn signMessage(_: &Signer, msg: JsValue) -> Result<JsValue, JsValue>;
 
+    #[wasm_bindgen(structural,catch, method)]
+    async fn signTypedData(_: &Signer, domain: JsValue, types: JsValue, value: JsValue) -> Result<JsValue, JsValue>;
+
     #[wasm_bindgen(method,getter)]
     fn getAddress(this: &Signer) -> Option<String>;
 }
@@ -45,11 +49,46 @@ impl JsonRpcSigner {
             .signer
             .signMessage(JsValue::from_str(msg_str))
             .await
-            .map_err(|e| {
-                EthSignerError::RpcSignError(serde_wasm_bindgen::from_value::<RpcErr>(e).unwrap())
-            })?;
-        let signature = serde_wasm_bindgen::from_value::<String>(signature)
-            .map_err(|e| EthSignerError::SigningFailed(e.to_string()))?;
-        PackedEthSignature::from_hex(&signature)
+            .map_err(rpc_sign_error)?;
+        decode_signature(signature)
     }
+
+    /// Sign the EIP-712 typed data by `signTypedData(domain, types, value)` of the wallet, the
+    /// `EIP712Domain` type is derived by the wallet from the domain.
+    pub async fn sign_typed_data(
+        &self,
+        typed_data: &EthTypedData,
+    ) -> Result<PackedEthSignature, EthSignerError> {
+        let mut data: Value = serde_json::from_str(&typed_data.raw_data)
+            .map_err(|e| EthSignerError::Eip712Failed(e.to_string()))?;
+        if let Some(types) = data["types"].as_object_mut() {
+            types.remove("EIP712Domain");
+        }
+        let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+        let to_js = |value: &Value| {
+            value
+                .serialize(&serializer)
+                .map_err(|e| EthSignerError::Eip712Failed(e.to_string()))
+        };
+        let signature = self
+            .signer
+            .signTypedData(
+                to_js(&data["domain"])?,
+                to_js(&data["types"])?,
+                to_js(&data["message"])?,
+            )
+            .await
+            .map_err(rpc_sign_error)?;
+        decode_signature(signature)
+    }
+}
+
+fn rpc_sign_error(error: JsValue) -> EthSignerError {
+    EthSignerError::RpcSignError(serde_wasm_bindgen::from_value::<RpcErr>(error).unwrap())
+}
+
+fn decode_signature(signature: JsValue) -> Result<PackedEthSignature, EthSignerError> {
+    let signature = serde_wasm_bindgen::from_value::<String>(signature)
+        .map_err(|e| EthSignerError::SigningFailed(e.to_string()))?;
+    PackedEthSignature::from_hex(&signature)
 }
@@ -2,6 +2,7 @@ use super::packed_eth_signature::PackedEthSignature;
 use super::EthSignerError;
 
 use crate::eth_signer::{Address, H256};
+use crate::keystore::{self, Kdf, KeystoreError};
 use ethers::signers::{LocalWallet, Signer};
 use ethers::types::transaction::eip2718::TypedTransaction;
 use ethers::types::TxHash;
@@ -31,6 +32,36 @@ impl EthSigner {
         }
     }
 
+    /// Decrypt the private key from the Web3 Secret Storage v3 keystore json, the address of the
+    /// keystore is checked if it's present.
+    pub fn from_keystore(keystore: &str, password: &str) -> Result<Self, EthSignerError> {
+        let (private_key, address) = keystore::decrypt_with_address(keystore, password)?;
+        // a zero or out of the curve order key can't sign
+        if private_key.len() != 32 || SigningKey::from_slice(&private_key).is_err() {
+            return Err(EthSignerError::InvalidEthSigner);
+        }
+        let signer = Self::from(H256::from_slice(&private_key));
+        if let Some(address) = address {
+            let address = address.strip_prefix("0x").unwrap_or(&address);
+            if !address.eq_ignore_ascii_case(&hex::encode(signer.get_address())) {
+                return Err(KeystoreError::AddressMismatch.into());
+            }
+        }
+        Ok(signer)
+    }
+
+    /// Encrypt the private key into the Web3 Secret Storage v3 keystore json.
+    pub fn to_keystore(&self, password: &str) -> Result<String, EthSignerError> {
+        let address = hex::encode(self.get_address());
+        let keystore = keystore::encrypt_with_address(
+            self.private_key.as_bytes(),
+            password,
+            Kdf::default(),
+            Some(address),
+        )?;
+        Ok(keystore)
+    }
+
     /// Get Ethereum address that matches the private key.
     pub fn get_address(&self) -> Address {
         let key = SigningKey::from_slice(self.private_key.as_bytes()).unwrap();
@@ -178,4 +209,39 @@ mod test {
 
         assert_eq!(signature.as_hex(), "0xbf24877c59766e95717686e71a0402ba12f5db4a8aa93ac6c30b5742925ebfc26c91d6b6bb949a2b0578c397e296830dde9cc3531adbb259c4b4b06441b1a9c51b");
     }
+
+    #[test]
+    fn test_eth_signer_keystore() {
+        let private_key = "0xb32593e347bf09436b058fbeabc17ebd2c7c1fa42e542f5f78fc3580faef83b7";
+        let signer = EthSigner::try_from(private_key).unwrap();
+        let keystore = signer.to_keystore("password").unwrap();
+        assert!(keystore.contains("9e372368c25056d44045e445d72d7b91ce3ee3b1"));
+        let recovered = EthSigner::from_keystore(&keystore, "password").unwrap();
+        assert_eq!(recovered.get_address(), signer.get_address());
+        assert_eq!(
+            EthSigner::from_keystore(&keystore, "wrong").unwrap_err(),
+            EthSignerError::KeystoreError(KeystoreError::InvalidPassword)
+        );
+
+        // the keystore of another address
+        let keystore = keystore.replace(
+            "9e372368c25056d44045e445d72d7b91ce3ee3b1",
+            "0000000000000000000000000000000000000000",
+        );
+        assert_eq!(
+            EthSigner::from_keystore(&keystore, "password").unwrap_err(),
+            EthSignerError::KeystoreError(KeystoreError::AddressMismatch)
+        );
+    }
+
+    #[test]
+    fn test_eth_signer_keystore_invalid_key() {
+        for bytes in [[0u8; 32], [0xffu8; 32]] {
+            let keystore = keystore::encrypt(&bytes, "password", Kdf::default()).unwrap();
+            assert_eq!(
+                EthSigner::from_keystore(&keystore, "password").unwrap_err(),
+                EthSignerError::InvalidEthSigner
+            );
+        }
+    }
 }
@@ -0,0 +1,359 @@
+//! The Web3 Secret Storage v3 keystore, the private keys are encrypted by aes-128-ctr with the
+//! key derived from the password by scrypt or pbkdf2.
+use crate::eth_signer::H256;
+use aes::Aes128;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use ctr::Ctr128BE;
+use ethers::utils::keccak256;
+use hmac::Hmac;
+use primitive_types::H128;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use thiserror::Error;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::JsValue;
+use zklink_sdk_utils::serde::NoPrefixHexSerde;
+
+const KEYSTORE_VERSION: u8 = 3;
+const CIPHER: &str = "aes-128-ctr";
+const PRF: &str = "hmac-sha256";
+const DKLEN: u8 = 32;
+/// The upper bounds of the kdf params, the keystore files are untrusted and the unbounded params
+/// could exhaust the memory or the CPU.
+const MAX_SCRYPT_LOG_N: u32 = 20;
+const MAX_SCRYPT_R: u32 = 16;
+const MAX_SCRYPT_P: u32 = 4;
+const MAX_PBKDF2_C: u32 = 10_000_000;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum KeystoreError {
+    #[error("Invalid keystore: {0}")]
+    InvalidKeystore(String),
+    #[error("Invalid keystore password")]
+    InvalidPassword,
+    #[error("Invalid kdf params: {0}")]
+    InvalidKdfParams(String),
+    #[error("The address of keystore mismatch")]
+    AddressMismatch,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl From<KeystoreError> for JsValue {
+    fn from(error: KeystoreError) -> Self {
+        JsValue::from_str(&format!("error: {error}"))
+    }
+}
+
+/// The key derivation function used to encrypt the keystore.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kdf {
+    Scrypt { log_n: u8, r: u32, p: u32 },
+    Pbkdf2 { c: u32 },
+}
+
+impl Default for Kdf {
+    /// The same as the light scrypt params of `eth-keystore`.
+    fn default() -> Self {
+        Kdf::Scrypt {
+            log_n: 13,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Keystore {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+    #[serde(alias = "Crypto")]
+    crypto: KeystoreCrypto,
+    id: String,
+    version: u8,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct KeystoreCrypto {
+    cipher: String,
+    cipherparams: CipherParams,
+    #[serde(with = "NoPrefixHexSerde")]
+    ciphertext: Vec<u8>,
+    kdf: String,
+    kdfparams: KdfParams,
+    #[serde(with = "NoPrefixHexSerde")]
+    mac: Vec<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CipherParams {
+    #[serde(with = "NoPrefixHexSerde")]
+    iv: Vec<u8>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum KdfParams {
+    Pbkdf2 {
+        c: u32,
+        dklen: u8,
+        prf: String,
+        #[serde(with = "NoPrefixHexSerde")]
+        salt: Vec<u8>,
+    },
+    Scrypt {
+        dklen: u8,
+        n: u32,
+        p: u32,
+        r: u32,
+        #[serde(with = "NoPrefixHexSerde")]
+        salt: Vec<u8>,
+    },
+}
+
+impl KdfParams {
+    fn derive_key(&self, password: &str) -> Result<Vec<u8>, KeystoreError> {
+        match self {
+            KdfParams::Pbkdf2 {
+                c,
+                dklen,
+                prf,
+                salt,
+            } => {
+                if prf != PRF {
+                    return Err(KeystoreError::InvalidKdfParams(format!(
+                        "unsupported prf {prf}"
+                    )));
+                }
+                if *c > MAX_PBKDF2_C {
+                    return Err(KeystoreError::InvalidKdfParams(format!(
+                        "c {c} is larger than {MAX_PBKDF2_C}"
+                    )));
+                }
+                let mut key = vec![0u8; Self::check_dklen(*dklen)?];
+                pbkdf2::pbkdf2::<Hmac<Sha256>>(password.as_bytes(), salt, *c, &mut key);
+                Ok(key)
+            }
+            KdfParams::Scrypt {
+                dklen,
+                n,
+                p,
+                r,
+                salt,
+            } => {
+                if !n.is_power_of_two() {
+                    return Err(KeystoreError::InvalidKdfParams(format!(
+                        "n {n} is not a power of 2"
+                    )));
+                }
+                if n.trailing_zeros() > MAX_SCRYPT_LOG_N || *r > MAX_SCRYPT_R || *p > MAX_SCRYPT_P {
+                    return Err(KeystoreError::InvalidKdfParams(format!(
+                        "n {n}, r {r} or p {p} is larger than 2^{MAX_SCRYPT_LOG_N}, {MAX_SCRYPT_R} or {MAX_SCRYPT_P}"
+                    )));
+                }
+                let params = scrypt::Params::new(n.trailing_zeros() as u8, *r, *p)
+                    .map_err(|e| KeystoreError::InvalidKdfParams(e.to_string()))?;
+                let mut key = vec![0u8; Self::check_dklen(*dklen)?];
+                scrypt::scrypt(password.as_bytes(), salt, &params, &mut key)
+                    .map_err(|e| KeystoreError::InvalidKdfParams(e.to_string()))?;
+                Ok(key)
+            }
+        }
+    }
+
+    /// The first 16 bytes is the aes key, and the next 16 bytes is used for the mac.
+    fn check_dklen(dklen: u8) -> Result<usize, KeystoreError> {
+        if dklen < DKLEN {
+            Err(KeystoreError::InvalidKdfParams(format!(
+                "dklen {dklen} is too short"
+            )))
+        } else {
+            Ok(dklen as usize)
+        }
+    }
+}
+
+/// Encrypt the `secret` into the keystore json.
+pub fn encrypt(secret: &[u8], password: &str, kdf: Kdf) -> Result<String, KeystoreError> {
+    encrypt_with_address(secret, password, kdf, None)
+}
+
+/// Decrypt the secret from the keystore json.
+pub fn decrypt(keystore: &str, password: &str) -> Result<Vec<u8>, KeystoreError> {
+    decrypt_with_address(keystore, password).map(|(secret, _)| secret)
+}
+
+pub(crate) fn encrypt_with_address(
+    secret: &[u8],
+    password: &str,
+    kdf: Kdf,
+    address: Option<String>,
+) -> Result<String, KeystoreError> {
+    let salt = H256::random().as_bytes().to_vec();
+    let kdfparams = match kdf {
+        Kdf::Scrypt { log_n, r, p } => KdfParams::Scrypt {
+            dklen: DKLEN,
+            n: 1u32
+                .checked_shl(log_n as u32)
+                .ok_or_else(|| KeystoreError::InvalidKdfParams(format!("log_n {log_n}")))?,
+            p,
+            r,
+            salt,
+        },
+        Kdf::Pbkdf2 { c } => KdfParams::Pbkdf2 {
+            c,
+            dklen: DKLEN,
+            prf: PRF.to_string(),
+            salt,
+        },
+    };
+    let key = kdfparams.derive_key(password)?;
+
+    let iv = H128::random().as_bytes().to_vec();
+    let mut ciphertext = secret.to_vec();
+    let mut cipher = Ctr128BE::<Aes128>::new_from_slices(&key[..16], &iv)
+        .map_err(|e| KeystoreError::InvalidKeystore(e.to_string()))?;
+    cipher.apply_keystream(&mut ciphertext);
+    let mac = keccak256([&key[16..32], ciphertext.as_slice()].concat()).to_vec();
+
+    let id = uuid::Builder::from_bytes(H128::random().to_fixed_bytes())
+        .set_variant(uuid::Variant::RFC4122)
+        .set_version(uuid::Version::Random)
+        .build();
+    let keystore = Keystore {
+        address,
+        crypto: KeystoreCrypto {
+            cipher: CIPHER.to_string(),
+            cipherparams: CipherParams { iv },
+            ciphertext,
+            kdf: match kdf {
+                Kdf::Scrypt { .. } => "scrypt".to_string(),
+                Kdf::Pbkdf2 { .. } => "pbkdf2".to_string(),
+            },
+            kdfparams,
+            mac,
+        },
+        id: id.to_string(),
+        version: KEYSTORE_VERSION,
+    };
+    serde_json::to_string(&keystore).map_err(|e| KeystoreError::InvalidKeystore(e.to_string()))
+}
+
+pub(crate) fn decrypt_with_address(
+    keystore: &str,
+    password: &str,
+) -> Result<(Vec<u8>, Option<String>), KeystoreError> {
+    let keystore: Keystore = serde_json::from_str(keystore)
+        .map_err(|e| KeystoreError::InvalidKeystore(e.to_string()))?;
+    if keystore.version != KEYSTORE_VERSION {
+        return Err(KeystoreError::InvalidKeystore(format!(
+            "unsupported version {}",
+            keystore.version
+        )));
+    }
+    let crypto = keystore.crypto;
+    if crypto.cipher != CIPHER {
+        return Err(KeystoreError::InvalidKeystore(format!(
+            "unsupported cipher {}",
+            crypto.cipher
+        )));
+    }
+    let key = crypto.kdfparams.derive_key(password)?;
+    let mac = keccak256([&key[16..32], crypto.ciphertext.as_slice()].concat());
+    if mac.as_slice() != crypto.mac.as_slice() {
+        return Err(KeystoreError::InvalidPassword);
+    }
+
+    let mut secret = crypto.ciphertext;
+    let mut cipher = Ctr128BE::<Aes128>::new_from_slices(&key[..16], &crypto.cipherparams.iv)
+        .map_err(|e| KeystoreError::InvalidKeystore(e.to_string()))?;
+    cipher.apply_keystream(&mut secret);
+    Ok((secret, keystore.address))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_keystore_pbkdf2_vector() {
+        // the test vector of Web3 Secret Storage Definition
+        let keystore = r#"{
+            "crypto" : {
+                "cipher" : "aes-128-ctr",
+                "cipherparams" : {
+                    "iv" : "6087dab2f9fdbbfaddc31a909735c1e6"
+                },
+                "ciphertext" : "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+                "kdf" : "pbkdf2",
+                "kdfparams" : {
+                    "c" : 262144,
+                    "dklen" : 32,
+                    "prf" : "hmac-sha256",
+                    "salt" : "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+                },
+                "mac" : "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+            },
+            "id" : "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+            "version" : 3
+        }"#;
+        let secret = decrypt(keystore, "testpassword").unwrap();
+        assert_eq!(
+            hex::encode(secret),
+            "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d"
+        );
+        assert_eq!(
+            decrypt(keystore, "wrongpassword"),
+            Err(KeystoreError::InvalidPassword)
+        );
+    }
+
+    #[test]
+    fn test_keystore_encrypt() {
+        let secret = [7u8; 32];
+        for kdf in [Kdf::default(), Kdf::Pbkdf2 { c: 1024 }] {
+            let keystore = encrypt(&secret, "password", kdf).unwrap();
+            assert_eq!(decrypt(&keystore, "password").unwrap(), secret);
+            assert_eq!(
+                decrypt(&keystore, "Password"),
+                Err(KeystoreError::InvalidPassword)
+            );
+        }
+    }
+
+    #[test]
+    fn test_keystore_kdf_params_bounds() {
+        let secret = [7u8; 32];
+        let keystore = encrypt(&secret, "password", Kdf::Pbkdf2 { c: 1024 }).unwrap();
+        let keystore = keystore.replace("\"c\":1024", "\"c\":4294967295");
+        assert!(matches!(
+            decrypt(&keystore, "password"),
+            Err(KeystoreError::InvalidKdfParams(_))
+        ));
+
+        let keystore = encrypt(&secret, "password", Kdf::default()).unwrap();
+        for (from, to) in [
+            ("\"n\":8192", "\"n\":2147483648"),
+            ("\"r\":8", "\"r\":1024"),
+            ("\"p\":1", "\"p\":64"),
+        ] {
+            assert!(keystore.contains(from));
+            assert!(matches!(
+                decrypt(&keystore.replace(from, to), "password"),
+                Err(KeystoreError::InvalidKdfParams(_))
+            ));
+        }
+        // the params above the bounds are rejected before the derivation
+        assert!(matches!(
+            encrypt(
+                &secret,
+                "password",
+                Kdf::Scrypt {
+                    log_n: 21,
+                    r: 8,
+                    p: 1
+                }
+            ),
+            Err(KeystoreError::InvalidKdfParams(_))
+        ));
+    }
+}
@@ -3,7 +3,13 @@
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "borsh")]
+mod codec;
 pub mod eth_signer;
+pub mod keystore;
+pub mod mnemonic;
+#[cfg(feature = "schemars")]
+mod schema;
 pub mod starknet_signer;
 pub mod zklink_signer;
 
@@ -0,0 +1,226 @@
+//! Derive the layer1 signers and the matching zklink signers from a BIP-39 mnemonic.
+use crate::eth_signer::pk_signer::EthSigner;
+use crate::eth_signer::H256;
+use crate::starknet_signer::StarkSigner;
+use crate::zklink_signer::error::ZkSignerError;
+use crate::zklink_signer::ZkLinkSigner;
+use coins_bip32::xkeys::XPriv;
+use coins_bip39::{English, Mnemonic as Bip39Mnemonic};
+use num::BigUint;
+use sha2::{Digest, Sha256};
+use starknet_core::types::FieldElement;
+use starknet_signers::SigningKey;
+use std::fmt;
+use thiserror::Error;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::JsValue;
+
+/// The BIP-44 path of the ethereum accounts, the account index is appended.
+pub const ETH_DERIVATION_PATH: &str = "m/44'/60'/0'/0";
+/// The path used by Argent and Braavos to derive the starknet keys, the account index is appended.
+pub const STARKNET_DERIVATION_PATH: &str = "m/44'/9004'/0'/0";
+/// The order of the stark curve.
+const STARK_CURVE_ORDER: &str = "0800000000000010ffffffffffffffffb781126dcae7b2321e66a241adc64d2f";
+
+#[derive(Debug, Error)]
+pub enum MnemonicError {
+    #[error("Invalid mnemonic: {0}")]
+    InvalidMnemonic(String),
+    #[error("Invalid derivation path: {0}")]
+    InvalidDerivationPath(String),
+    #[error("{0}")]
+    ZkSignerError(#[from] ZkSignerError),
+}
+
+#[cfg(target_arch = "wasm32")]
+impl From<MnemonicError> for JsValue {
+    fn from(error: MnemonicError) -> Self {
+        JsValue::from_str(&format!("error: {error}"))
+    }
+}
+
+/// The starknet wallets derive the keys from the mnemonic in different ways.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StarknetWallet {
+    /// The BIP-32 root is built from the ethereum private key at `m/44'/60'/0'/0/0`.
+    Argent,
+    /// The BIP-32 root is built from the seed of the mnemonic.
+    Braavos,
+}
+
+/// The BIP-32 root of a BIP-39 mnemonic, all the signers of the accounts are derived from it.
+#[derive(Clone)]
+pub struct Mnemonic {
+    root: XPriv,
+}
+
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "**Mnemonic**")
+    }
+}
+
+impl Mnemonic {
+    /// Create from the english mnemonic phrase and the optional BIP-39 password.
+    pub fn new(phrase: &str, password: Option<&str>) -> Result<Self, MnemonicError> {
+        let phrase = phrase.split_whitespace().collect::<Vec<_>>().join(" ");
+        let root = Bip39Mnemonic::<English>::new_from_phrase(&phrase)
+            .and_then(|mnemonic| mnemonic.master_key(password))
+            .map_err(|e| MnemonicError::InvalidMnemonic(e.to_string()))?;
+        Ok(Self { root })
+    }
+
+    /// Derive the ethereum signer at the BIP-32 `path`, e.g. `m/44'/60'/0'/0/0`.
+    pub fn eth_signer(&self, path: &str) -> Result<EthSigner, MnemonicError> {
+        let private_key = derive_private_key(&self.root, path)?;
+        Ok(EthSigner::from(H256::from(private_key)))
+    }
+
+    /// Derive the ethereum signer of the `index`th account, the same as MetaMask.
+    pub fn eth_signer_at(&self, index: u32) -> Result<EthSigner, MnemonicError> {
+        self.eth_signer(&format!("{ETH_DERIVATION_PATH}/{index}"))
+    }
+
+    /// Derive the starknet signer of the `index`th account of the starknet `wallet`.
+    pub fn starknet_signer_at(
+        &self,
+        wallet: StarknetWallet,
+        index: u32,
+    ) -> Result<StarkSigner, MnemonicError> {
+        let root = match wallet {
+            StarknetWallet::Argent => {
+                let eth_private_key =
+                    derive_private_key(&self.root, &format!("{ETH_DERIVATION_PATH}/0"))?;
+                XPriv::root_from_seed(&eth_private_key, None)
+                    .map_err(|e| MnemonicError::InvalidMnemonic(e.to_string()))?
+            }
+            StarknetWallet::Braavos => self.root.clone(),
+        };
+        let private_key =
+            derive_private_key(&root, &format!("{STARKNET_DERIVATION_PATH}/{index}"))?;
+        let signing_key = SigningKey::from_secret_scalar(grind_key(&private_key));
+        Ok(StarkSigner(signing_key))
+    }
+
+    /// Derive the zklink signer of the `index`th ethereum account.
+    pub fn zklink_signer_at(&self, index: u32) -> Result<ZkLinkSigner, MnemonicError> {
+        let eth_signer = self.eth_signer_at(index)?;
+        Ok(ZkLinkSigner::new_from_eth_signer(&eth_signer)?)
+    }
+
+    /// Derive the zklink signer of the `index`th starknet account, `addr` is the address of
+    /// the deployed account contract.
+    pub fn starknet_zklink_signer_at(
+        &self,
+        wallet: StarknetWallet,
+        index: u32,
+        addr: &str,
+        chain_id: &str,
+    ) -> Result<ZkLinkSigner, MnemonicError> {
+        let starknet_signer = self.starknet_signer_at(wallet, index)?;
+        Ok(ZkLinkSigner::new_from_starknet_signer(
+            &starknet_signer,
+            addr,
+            chain_id,
+        )?)
+    }
+}
+
+fn derive_private_key(root: &XPriv, path: &str) -> Result<[u8; 32], MnemonicError> {
+    let key = root
+        .derive_path(path)
+        .map_err(|e| MnemonicError::InvalidDerivationPath(e.to_string()))?;
+    let key: &coins_bip32::ecdsa::SigningKey = key.as_ref();
+    Ok(key.to_bytes().into())
+}
+
+/// The `grindKey` of StarkWare, hash the key with an increasing index until the result is
+/// uniformly distributed modulo the order of the stark curve.
+fn grind_key(key: &[u8]) -> FieldElement {
+    let order = BigUint::parse_bytes(STARK_CURVE_ORDER.as_bytes(), 16).unwrap();
+    let max = BigUint::from(1u8) << 256;
+    let limit = &max - &max % &order;
+    let mut index = 0u32;
+    loop {
+        // the index is encoded in the minimal big endian bytes
+        let index_bytes = index.to_be_bytes();
+        let skip = index_bytes.iter().take(3).take_while(|b| **b == 0).count();
+        let hash = Sha256::new()
+            .chain_update(key)
+            .chain_update(&index_bytes[skip..])
+            .finalize();
+        let value = BigUint::from_bytes_be(&hash);
+        if value < limit {
+            let key = (value % &order).to_bytes_be();
+            return FieldElement::from_byte_slice_be(&key).unwrap();
+        }
+        index += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::eth_signer::Address;
+    use std::str::FromStr;
+
+    const PHRASE: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_mnemonic_eth_signer() {
+        let mnemonic = Mnemonic::new(PHRASE, None).unwrap();
+        let signer = mnemonic.eth_signer_at(0).unwrap();
+        assert_eq!(
+            signer.get_address(),
+            Address::from_str("0x9858EfFD232B4033E47d90003D41EC34EcaEda94").unwrap()
+        );
+        let signer = mnemonic.eth_signer("m/44'/60'/0'/0/1").unwrap();
+        assert_eq!(
+            signer.get_address(),
+            Address::from_str("0x6Fac4D18c912343BF86fa7049364Dd4E424Ab9C0").unwrap()
+        );
+
+        let zklink_signer = mnemonic.zklink_signer_at(1).unwrap();
+        let expected = ZkLinkSigner::new_from_eth_signer(&signer).unwrap();
+        assert_eq!(
+            zklink_signer.public_key().public_key_hash(),
+            expected.public_key().public_key_hash()
+        );
+
+        assert!(Mnemonic::new("abandon about", None).is_err());
+        assert!(mnemonic.eth_signer("m/44'/60'/x").is_err());
+    }
+
+    #[test]
+    fn test_mnemonic_starknet_signer() {
+        let mnemonic = Mnemonic::new(PHRASE, None).unwrap();
+        let expected = [
+            (
+                StarknetWallet::Argent,
+                0,
+                "0x18a556cbd949d1e6d25ed391bf032559fb6055f321c3e02714f7a6268bff3d1",
+            ),
+            (
+                StarknetWallet::Argent,
+                1,
+                "0xd0be385d5735a38651e3c5bea440321f5d36468a52057801ae0cb3dbb4876c",
+            ),
+            (
+                StarknetWallet::Braavos,
+                0,
+                "0x1b8e16cdf31892c56c0370f0e4ca0da096ef4e0c81007b3ba10b11452f8971",
+            ),
+            (
+                StarknetWallet::Braavos,
+                1,
+                "0x6d582b352685f7c37a2faa748536c741c3a8c660cb011bce57457a32cd04d1a",
+            ),
+        ];
+        for (wallet, index, private_key) in expected {
+            let signer = mnemonic.starknet_signer_at(wallet, index).unwrap();
+            let expected = StarkSigner::new_from_hex_str(private_key).unwrap();
+            assert_eq!(signer.public_key(), expected.public_key());
+        }
+    }
+}
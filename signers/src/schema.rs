@@ -0,0 +1,50 @@
+//! The JSON Schemas of the keys and the signatures, they are all serialized as hex strings.
+use crate::eth_signer::eip1271_signature::EIP1271Signature;
+use crate::eth_signer::packed_eth_signature::PackedEthSignature;
+use crate::starknet_signer::ecdsa_signature::StarkEip712Signature;
+use crate::zklink_signer::pubkey_hash::PubKeyHash;
+use crate::zklink_signer::public_key::PackedPublicKey;
+use crate::zklink_signer::signature::PackedSignature;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use zklink_sdk_utils::schema::string_schema;
+
+macro_rules! impl_string_schema {
+    ($ty:ident, $description:expr) => {
+        impl JsonSchema for $ty {
+            fn schema_name() -> String {
+                stringify!($ty).into()
+            }
+
+            fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+                string_schema($description)
+            }
+        }
+    };
+}
+
+impl_string_schema!(
+    PackedPublicKey,
+    "The 32 bytes packed zklink public key as a 0x-prefixed hex string"
+);
+impl_string_schema!(
+    PackedSignature,
+    "The 64 bytes packed zklink signature as a hex string without the 0x prefix"
+);
+impl_string_schema!(
+    PubKeyHash,
+    "The 20 bytes hash of the zklink public key as a 0x-prefixed hex string"
+);
+impl_string_schema!(
+    PackedEthSignature,
+    "The 65 bytes ethereum signature `r | s | v` as a 0x-prefixed hex string"
+);
+impl_string_schema!(
+    EIP1271Signature,
+    "The EIP-1271 signature of the contract wallet as a 0x-prefixed hex string"
+);
+impl_string_schema!(
+    StarkEip712Signature,
+    "The 96 bytes starknet signature `pub_key | r | s` as a 0x-prefixed hex string"
+);
@@ -15,7 +15,7 @@ pub struct Message {
     pub data: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum TypedDataMessage {
     CreateL2Key { message: Message },
     Transaction { message: TxMessage },
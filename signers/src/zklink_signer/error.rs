@@ -1,4 +1,5 @@
 use crate::eth_signer::error::EthSignerError;
+use crate::keystore::KeystoreError;
 use crate::starknet_signer::error::StarkSignerError;
 use thiserror::Error;
 #[cfg(target_arch = "wasm32")]
@@ -22,6 +23,8 @@ pub enum ZkSignerError {
     EthSignerError(#[from] EthSignerError),
     #[error("{0}")]
     StarkSignerError(#[from] StarkSignerError),
+    #[error("{0}")]
+    KeystoreError(#[from] KeystoreError),
 }
 
 impl ZkSignerError {
@@ -13,7 +13,7 @@ pub(crate) use franklin_crypto::{
 };
 
 const PACKED_POINT_SIZE: usize = 32;
-const SIGNATURE_SIZE: usize = 96;
+pub(crate) const SIGNATURE_SIZE: usize = 96;
 
 pub const NEW_PUBKEY_HASH_BYTES_LEN: usize = 20;
 pub const NEW_PUBKEY_HASH_WIDTH: usize = NEW_PUBKEY_HASH_BYTES_LEN * 8;
@@ -2,6 +2,7 @@ use super::error::ZkSignerError as Error;
 use super::{JUBJUB_PARAMS, RESCUE_PARAMS};
 
 use crate::eth_signer::H256;
+use crate::keystore::{self, Kdf};
 use crate::zklink_signer::public_key::PackedPublicKey;
 use crate::zklink_signer::signature::{PackedSignature, ZkLinkSignature};
 use crate::zklink_signer::utils;
@@ -56,9 +57,9 @@ pub fn sha256_bytes(input: &[u8]) -> Vec<u8> {
 }
 
 impl ZkLinkSigner {
-    const SIGN_MESSAGE: &'static str =
+    pub const SIGN_MESSAGE: &'static str =
         "Sign this message to create a key to interact with zkLink's layer2 services.\nNOTE: This application is powered by zkLink protocol.\n\nOnly sign this message for a trusted client!";
-    const STARKNET_SIGN_MESSAGE: &'static str = "Create zkLink's layer2 key.";
+    pub const STARKNET_SIGN_MESSAGE: &'static str = "Create zkLink's layer2 key.";
     pub fn new() -> Result<Self, Error> {
         let eth_pk = H256::random();
         let eth_signer = EthSigner::from(eth_pk);
@@ -161,10 +162,28 @@ impl ZkLinkSigner {
         fs_repr
             .read_be(bytes)
             .map_err(|_| Error::custom_error("couldn't read private key repr"))?;
-        let private_key = FLPrivateKey::<Engine>(
-            Fs::from_repr(fs_repr).expect("couldn't read private key from repr"),
-        );
-        Ok(private_key.into())
+        // e.g. an imported ethereum private key is not always below the modulus of Fs
+        let private_key = Fs::from_repr(fs_repr).map_err(Error::invalid_privkey)?;
+        Ok(FLPrivateKey::<Engine>(private_key).into())
+    }
+
+    /// Decrypt the private key encrypted by [`ZkLinkSigner::to_keystore`].
+    pub fn from_keystore(keystore: &str, password: &str) -> Result<Self, Error> {
+        let bytes = keystore::decrypt(keystore, password)?;
+        Self::new_from_bytes(&bytes)
+    }
+
+    /// Encrypt the private key into the same json as the Web3 Secret Storage v3 keystore, it
+    /// could be restored by [`ZkLinkSigner::from_keystore`].
+    pub fn to_keystore(&self, password: &str) -> Result<String, Error> {
+        let mut bytes = Vec::with_capacity(32);
+        let private_key: &EddsaPrivKey<Engine> = self.as_ref();
+        private_key
+            .0
+            .into_repr()
+            .write_be(&mut bytes)
+            .map_err(|e| Error::custom_error(e.to_string()))?;
+        Ok(keystore::encrypt(&bytes, password, Kdf::default())?)
     }
 
     /// We use musig Schnorr signature scheme.
@@ -256,4 +275,29 @@ mod test {
         );
         assert_eq!(serde_json::to_value(signature).unwrap(), expect_signature);
     }
+
+    #[test]
+    fn test_zklink_signer_keystore() {
+        let eth_private_key = "0xb32593e347bf09436b058fbeabc17ebd2c7c1fa42e542f5f78fc3580faef83b7";
+        let zklink_signer = ZkLinkSigner::new_from_hex_eth_signer(eth_private_key).unwrap();
+        let keystore = zklink_signer.to_keystore("password").unwrap();
+        let recovered = ZkLinkSigner::from_keystore(&keystore, "password").unwrap();
+        assert_eq!(
+            recovered.public_key().as_hex(),
+            "0x8e3eb3abb0cbf96605956a5313ab239ff685a64562332ac52ef51b9eb8d0d72c"
+        );
+        assert!(ZkLinkSigner::from_keystore(&keystore, "wrong").is_err());
+    }
+
+    #[test]
+    fn test_zklink_signer_out_of_field_key() {
+        // a valid ethereum private key, but not below the modulus of Fs
+        let bytes = [0xffu8; 32];
+        assert!(ZkLinkSigner::new_from_bytes(&bytes).is_err());
+        let keystore = keystore::encrypt(&bytes, "password", Kdf::default()).unwrap();
+        assert!(matches!(
+            ZkLinkSigner::from_keystore(&keystore, "password"),
+            Err(Error::InvalidPrivKey(_))
+        ));
+    }
 }
@@ -102,6 +102,7 @@ impl PackedSignature {
 
 /// ZkLink signature
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ZkLinkSignature {
     /// packed public key
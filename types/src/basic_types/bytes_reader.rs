@@ -0,0 +1,147 @@
+use crate::basic_types::pack::{unpack_fee_amount, unpack_token_amount};
+use crate::basic_types::{AccountId, ChainId, Nonce, SubAccountId, TokenId, ZkLinkAddress};
+use crate::error::TypeError;
+use crate::params::{
+    AMOUNT_BIT_WIDTH, BALANCE_BIT_WIDTH, FEE_BIT_WIDTH, FUNDING_RATE_BYTES, LAYER1_ADDR_BIT_WIDTH,
+    NEW_PUBKEY_HASH_BYTES_LEN, ORDER_NONCE_BIT_WIDTH, PAIR_SYMBOL_BYTES, PRICE_BIT_WIDTH,
+};
+use num::BigUint;
+use zklink_sdk_signers::zklink_signer::pubkey_hash::PubKeyHash;
+
+/// Read the fields from the big-endian encoded bytes, e.g. the signed bytes or the pubdata.
+pub(crate) struct BytesReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    error: fn(String) -> TypeError,
+}
+
+impl<'a> BytesReader<'a> {
+    /// The reader starts at `offset`, `error` wraps the error message of the invalid bytes.
+    pub(crate) fn new(bytes: &'a [u8], offset: usize, error: fn(String) -> TypeError) -> Self {
+        BytesReader {
+            bytes,
+            offset,
+            error,
+        }
+    }
+
+    pub(crate) fn error(&self, message: impl ToString) -> TypeError {
+        (self.error)(message.to_string())
+    }
+
+    pub(crate) fn read(&mut self, len: usize) -> Result<&'a [u8], TypeError> {
+        let bytes = self
+            .bytes
+            .get(self.offset..self.offset + len)
+            .ok_or_else(|| self.error("unexpected end of bytes"))?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    /// Skip the padding to the next multiple of `align`, e.g. the start of the next chunk.
+    pub(crate) fn align(&mut self, align: usize) {
+        self.offset = self.offset.div_ceil(align) * align;
+    }
+
+    /// Check all the bytes are read.
+    pub(crate) fn finish(&self) -> Result<(), TypeError> {
+        if self.offset != self.bytes.len() {
+            return Err(self.error(format!(
+                "expected {} bytes, got {}",
+                self.offset,
+                self.bytes.len()
+            )));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8, TypeError> {
+        Ok(self.read(1)?[0])
+    }
+
+    pub(crate) fn u16(&mut self) -> Result<u16, TypeError> {
+        Ok(u16::from_be_bytes(self.read(2)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn u32(&mut self) -> Result<u32, TypeError> {
+        Ok(u32::from_be_bytes(self.read(4)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn u64(&mut self) -> Result<u64, TypeError> {
+        Ok(u64::from_be_bytes(self.read(8)?.try_into().unwrap()))
+    }
+
+    pub(crate) fn chain_id(&mut self) -> Result<ChainId, TypeError> {
+        Ok(ChainId(self.u8()?))
+    }
+
+    pub(crate) fn account_id(&mut self) -> Result<AccountId, TypeError> {
+        Ok(AccountId(self.u32()?))
+    }
+
+    pub(crate) fn sub_account_id(&mut self) -> Result<SubAccountId, TypeError> {
+        Ok(SubAccountId(self.u8()?))
+    }
+
+    pub(crate) fn token_id(&mut self) -> Result<TokenId, TypeError> {
+        Ok(TokenId(self.u16()? as u32))
+    }
+
+    pub(crate) fn nonce(&mut self) -> Result<Nonce, TypeError> {
+        Ok(Nonce(self.u32()?))
+    }
+
+    pub(crate) fn order_nonce(&mut self) -> Result<Nonce, TypeError> {
+        let bytes = self.read(ORDER_NONCE_BIT_WIDTH / 8)?;
+        Ok(Nonce(u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]])))
+    }
+
+    pub(crate) fn amount(&mut self) -> Result<BigUint, TypeError> {
+        Ok(BigUint::from_bytes_be(self.read(BALANCE_BIT_WIDTH / 8)?))
+    }
+
+    pub(crate) fn price(&mut self) -> Result<BigUint, TypeError> {
+        Ok(BigUint::from_bytes_be(self.read(PRICE_BIT_WIDTH / 8)?))
+    }
+
+    pub(crate) fn packed_amount(&mut self) -> Result<BigUint, TypeError> {
+        unpack_token_amount(self.read(AMOUNT_BIT_WIDTH / 8)?)
+            .ok_or_else(|| self.error("invalid packed amount"))
+    }
+
+    pub(crate) fn packed_fee(&mut self) -> Result<BigUint, TypeError> {
+        unpack_fee_amount(self.read(FEE_BIT_WIDTH / 8)?)
+            .ok_or_else(|| self.error("invalid packed fee"))
+    }
+
+    pub(crate) fn funding_rate(&mut self) -> Result<i16, TypeError> {
+        // the sign is the highest bit, see `FundingInfo::get_bytes`
+        let bytes = self.read(FUNDING_RATE_BYTES)?;
+        let abs = u16::from_be_bytes([bytes[0] & 0b0111_1111, bytes[1]]) as i16;
+        Ok(if bytes[0] & 0b1000_0000 != 0 {
+            -abs
+        } else {
+            abs
+        })
+    }
+
+    pub(crate) fn pub_key_hash(&mut self) -> Result<PubKeyHash, TypeError> {
+        PubKeyHash::from_bytes(self.read(NEW_PUBKEY_HASH_BYTES_LEN)?).map_err(|e| self.error(e))
+    }
+
+    pub(crate) fn symbol(&mut self) -> Result<String, TypeError> {
+        let bytes = self.read(PAIR_SYMBOL_BYTES)?;
+        let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+        String::from_utf8(bytes[start..].to_vec()).map_err(|e| self.error(e))
+    }
+
+    /// The address is encoded as 32 bytes, the ethereum address is left padded with zeros.
+    pub(crate) fn address(&mut self) -> Result<ZkLinkAddress, TypeError> {
+        let bytes = self.read(LAYER1_ADDR_BIT_WIDTH / 8)?;
+        if bytes[..12].iter().all(|b| *b == 0) {
+            ZkLinkAddress::from_slice(&bytes[12..])
+        } else {
+            ZkLinkAddress::from_slice(bytes)
+        }
+    }
+}
@@ -13,6 +13,7 @@ pub use zklink_address::ZkLinkAddress;
 #[macro_use]
 mod macros;
 pub mod bit_convert;
+pub(crate) mod bytes_reader;
 pub mod float_convert;
 pub mod num_wrapper;
 pub mod pack;
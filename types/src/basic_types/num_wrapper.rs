@@ -5,6 +5,7 @@ use std::ops::{Deref, DerefMut};
 use zklink_sdk_utils::serde::{BigIntSerdeAsRadix10Str, BigUintSerdeAsRadix10Str};
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct BigUintSerdeWrapper(#[serde(with = "BigUintSerdeAsRadix10Str")] pub BigUint);
 
 impl From<BigUint> for BigUintSerdeWrapper {
@@ -39,6 +40,7 @@ impl ToString for BigUintSerdeWrapper {
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct BigIntSerdeWrapper(#[serde(with = "BigIntSerdeAsRadix10Str")] pub BigInt);
 
 impl From<BigUint> for BigIntSerdeWrapper {
@@ -242,3 +242,33 @@ pub const GLOBAL_ASSET_ACCOUNT_ID: AccountId = AccountId(1);
 pub const FEE_RATIO_BIT_WIDTH: usize = 8;
 pub const SIGNED_FUNDING_RATE_BIT_WIDTH: usize = 16;
 pub const FUNDING_RATE_BYTES: usize = SIGNED_FUNDING_RATE_BIT_WIDTH / 8;
+
+/// The block pubdata committed to layer1 is split into chunks, every op takes a whole number of chunks.
+pub const CHUNK_BYTES: usize = 23;
+pub const CHUNK_BIT_WIDTH: usize = CHUNK_BYTES * 8;
+/// The chunks of the ops with a fixed layout
+pub const NOOP_CHUNKS: usize = 1;
+pub const DEPOSIT_CHUNKS: usize = 3;
+pub const WITHDRAW_CHUNKS: usize = 3;
+pub const TRANSFER_CHUNKS: usize = 3;
+pub const FULL_EXIT_CHUNKS: usize = 3;
+pub const CHANGE_PUBKEY_CHUNKS: usize = 3;
+pub const FORCED_EXIT_CHUNKS: usize = 3;
+pub const ORDER_MATCHING_CHUNKS: usize = 4;
+pub const LIQUIDATION_CHUNKS: usize = 1;
+pub const AUTO_DELEVERAGING_CHUNKS: usize = 2;
+pub const DEPOSIT_BYTES: usize = DEPOSIT_CHUNKS * CHUNK_BYTES;
+pub const WITHDRAW_BYTES: usize = WITHDRAW_CHUNKS * CHUNK_BYTES;
+pub const TRANSFER_BYTES: usize = TRANSFER_CHUNKS * CHUNK_BYTES;
+pub const FULL_EXIT_BYTES: usize = FULL_EXIT_CHUNKS * CHUNK_BYTES;
+pub const CHANGE_PUBKEY_BYTES: usize = CHANGE_PUBKEY_CHUNKS * CHUNK_BYTES;
+pub const FORCED_EXIT_BYTES: usize = FORCED_EXIT_CHUNKS * CHUNK_BYTES;
+pub const ORDER_MATCHING_BYTES: usize = ORDER_MATCHING_CHUNKS * CHUNK_BYTES;
+pub const LIQUIDATION_BYTES: usize = LIQUIDATION_CHUNKS * CHUNK_BYTES;
+pub const AUTO_DELEVERAGING_BYTES: usize = AUTO_DELEVERAGING_CHUNKS * CHUNK_BYTES;
+/// ContractMatching takes one chunk for the header, and one chunk for each of the taker and makers.
+pub const CONTRACT_MATCHING_HEADER_CHUNKS: usize = 1;
+pub const CONTRACT_CHUNKS: usize = 1;
+/// Funding takes one chunk for the header, and the funding accounts are packed into the next chunks.
+pub const FUNDING_HEADER_CHUNKS: usize = 1;
+pub const FUNDING_ACCOUNTS_PER_CHUNK: usize = CHUNK_BYTES / (ACCOUNT_ID_BIT_WIDTH / 8);
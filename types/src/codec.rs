@@ -0,0 +1,308 @@
+//! The compact binary codec of the txs, the orders and the signatures, enabled by the `borsh`
+//! feature.
+//!
+//! The binary starts with the [`CODEC_VERSION`] followed by the borsh encoding of the value.
+//! The big integers are encoded as their minimal big-endian bytes, so every value has exactly one
+//! encoding.
+use crate::basic_types::ZkLinkAddress;
+use crate::error::TypeError;
+use crate::prelude::{Contract, Order, TxLayer1Signature, TxSignature, ZkLinkTx};
+use borsh::io::{Error, ErrorKind, Read, Result, Write};
+use borsh::{BorshDeserialize, BorshSerialize};
+use zklink_sdk_signers::zklink_signer::signature::ZkLinkSignature;
+
+/// The version of the binary layout, it's increased whenever the layout is changed, e.g. a tx
+/// type is added.
+pub const CODEC_VERSION: u8 = 1;
+
+pub trait BinaryCodec: BorshSerialize + BorshDeserialize {
+    fn to_binary(&self) -> Vec<u8> {
+        let mut bytes = vec![CODEC_VERSION];
+        BorshSerialize::serialize(self, &mut bytes).expect("write to vec never fails");
+        bytes
+    }
+
+    fn from_binary(bytes: &[u8]) -> std::result::Result<Self, TypeError> {
+        match bytes.split_first() {
+            Some((&CODEC_VERSION, data)) => {
+                borsh::from_slice(data).map_err(|e| TypeError::InvalidBinary(e.to_string()))
+            }
+            Some((version, _)) => Err(TypeError::InvalidBinary(format!(
+                "unsupported version {version}, expected {CODEC_VERSION}"
+            ))),
+            None => Err(TypeError::InvalidBinary("empty bytes".into())),
+        }
+    }
+}
+
+impl BinaryCodec for ZkLinkTx {}
+impl BinaryCodec for Order {}
+impl BinaryCodec for Contract {}
+impl BinaryCodec for TxSignature {}
+impl BinaryCodec for ZkLinkSignature {}
+impl BinaryCodec for TxLayer1Signature {}
+
+fn invalid_data(error: impl ToString) -> Error {
+    Error::new(ErrorKind::InvalidData, error.to_string())
+}
+
+impl BorshSerialize for ZkLinkAddress {
+    fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+        BorshSerialize::serialize(self.as_bytes(), writer)
+    }
+}
+
+impl BorshDeserialize for ZkLinkAddress {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+        let bytes = Vec::<u8>::deserialize_reader(reader)?;
+        ZkLinkAddress::from_slice(&bytes).map_err(invalid_data)
+    }
+}
+
+/// Encode the `BigUint` fields by `#[borsh(serialize_with, deserialize_with)]`.
+pub(crate) mod biguint {
+    use super::*;
+    use num::BigUint;
+
+    pub(crate) fn serialize<W: Write>(value: &BigUint, writer: &mut W) -> Result<()> {
+        BorshSerialize::serialize(&value.to_bytes_be(), writer)
+    }
+
+    pub(crate) fn deserialize<R: Read>(reader: &mut R) -> Result<BigUint> {
+        let bytes = Vec::<u8>::deserialize_reader(reader)?;
+        // zero is encoded as [0], the other leading zeros are not canonical
+        if bytes.is_empty() || (bytes.len() > 1 && bytes[0] == 0) {
+            return Err(invalid_data("non-canonical big integer"));
+        }
+        Ok(BigUint::from_bytes_be(&bytes))
+    }
+}
+
+/// Encode the `H256` fields by `#[borsh(serialize_with, deserialize_with)]`.
+pub(crate) mod h256 {
+    use super::*;
+    use zklink_sdk_signers::eth_signer::H256;
+
+    pub(crate) fn serialize<W: Write>(value: &H256, writer: &mut W) -> Result<()> {
+        writer.write_all(value.as_bytes())
+    }
+
+    pub(crate) fn deserialize<R: Read>(reader: &mut R) -> Result<H256> {
+        Ok(H256(<[u8; 32]>::deserialize_reader(reader)?))
+    }
+}
+
+/// Encode the `Option<H256>` fields by `#[borsh(serialize_with, deserialize_with)]`.
+pub(crate) mod option_h256 {
+    use super::*;
+    use zklink_sdk_signers::eth_signer::H256;
+
+    pub(crate) fn serialize<W: Write>(value: &Option<H256>, writer: &mut W) -> Result<()> {
+        BorshSerialize::serialize(&value.map(|hash| hash.0), writer)
+    }
+
+    pub(crate) fn deserialize<R: Read>(reader: &mut R) -> Result<Option<H256>> {
+        Ok(Option::<[u8; 32]>::deserialize_reader(reader)?.map(H256))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::basic_types::{
+        AccountId, BigUint, ChainId, Nonce, PairId, SlotId, SubAccountId, TimeStamp, TokenId,
+    };
+    use crate::prelude::{
+        ChangePubKey, ChangePubKeyAuthData, ContractMatching, ContractPrice, Create2Data, Deposit,
+        FundingInfo, OraclePrices, OrderMatching, Parameter, SpotPriceInfo, Transfer,
+        UpdateGlobalVar, Withdraw, H256,
+    };
+    use crate::tx_type::ZkSignatureTrait;
+    use serde::Serialize;
+    use std::str::FromStr;
+    use zklink_sdk_signers::eth_signer::eip1271_signature::EIP1271Signature;
+    use zklink_sdk_signers::eth_signer::packed_eth_signature::PackedEthSignature;
+    use zklink_sdk_signers::starknet_signer::ecdsa_signature::StarkEip712Signature;
+    use zklink_sdk_signers::zklink_signer::pk_signer::ZkLinkSigner;
+    use zklink_sdk_signers::zklink_signer::pubkey_hash::PubKeyHash;
+
+    const PRIVATE_KEY: &str = "be725250b123a39dab5b7579334d5888987c72a58f4508062545fe6e08ca94f4";
+    const ADDRESS: &str = "0xAFAFf3aD1a0425D792432D9eCD1c3e26Ef2C42E9";
+
+    /// The value is decoded as the same JSON and the binary is smaller than the JSON.
+    fn assert_round_trip<T: BinaryCodec + Serialize>(value: &T) {
+        let binary = value.to_binary();
+        let decoded = T::from_binary(&binary).unwrap();
+        let json = serde_json::to_string(value).unwrap();
+        assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+        assert!(binary.len() < json.len());
+    }
+
+    fn order(is_sell: bool, signer: &ZkLinkSigner) -> Order {
+        Order::new(
+            AccountId(10),
+            SubAccountId(1),
+            SlotId(3),
+            Nonce(7),
+            TokenId(32),
+            TokenId(17),
+            BigUint::from(10000u32),
+            BigUint::from(6000000000000000000u64),
+            is_sell,
+            false,
+            5,
+            10,
+            None,
+        )
+        .create_signed_order(signer)
+        .unwrap()
+    }
+
+    fn oracle_prices() -> OraclePrices {
+        OraclePrices {
+            contract_prices: vec![ContractPrice {
+                pair_id: PairId(1),
+                market_price: BigUint::from(2000000000000000000000u128),
+            }],
+            margin_prices: vec![SpotPriceInfo {
+                token_id: TokenId(17),
+                price: BigUint::from(1000000000000000000u64),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_txs_round_trip() {
+        let signer = ZkLinkSigner::new_from_hex_eth_signer(PRIVATE_KEY).unwrap();
+        let address = ZkLinkAddress::from_str(ADDRESS).unwrap();
+        let mut transfer = Transfer {
+            account_id: AccountId(10),
+            from_sub_account_id: SubAccountId(1),
+            to_sub_account_id: SubAccountId(1),
+            to: address.clone(),
+            token: TokenId(18),
+            amount: BigUint::from(10000u32),
+            fee: BigUint::from(3u32),
+            nonce: Nonce(1),
+            ts: TimeStamp(1693472232),
+            ..Default::default()
+        };
+        transfer.sign(&signer).unwrap();
+        let withdraw = Withdraw {
+            to_chain_id: ChainId(3),
+            account_id: AccountId(10),
+            to: address.clone(),
+            amount: BigUint::from(u128::MAX),
+            data_hash: Some(H256::repeat_byte(7)),
+            ..Default::default()
+        };
+        let change_pubkey = ChangePubKey {
+            new_pk_hash: PubKeyHash::from_hex("0xd8d5fb6a6caef06aa3dc2abdcdc240987e5330fe")
+                .unwrap(),
+            eth_auth_data: ChangePubKeyAuthData::EthCreate2 {
+                data: Create2Data {
+                    creator_address: address.clone(),
+                    salt_arg: H256::repeat_byte(1),
+                    code_hash: H256::repeat_byte(2),
+                },
+            },
+            ..Default::default()
+        };
+        let deposit = Deposit {
+            from: address.clone(),
+            to: address,
+            eth_hash: Some(H256::repeat_byte(3)),
+            ..Default::default()
+        };
+        let order_matching = OrderMatching {
+            taker: order(false, &signer),
+            maker: order(true, &signer),
+            oracle_prices: oracle_prices(),
+            ..Default::default()
+        };
+        let contract_matching = ContractMatching {
+            taker: Contract {
+                size: BigUint::from(100u32),
+                price: BigUint::from(2000u32),
+                direction: 1,
+                ..Default::default()
+            },
+            maker: vec![Contract::default(), Contract::default()],
+            oracle_prices: oracle_prices(),
+            ..Default::default()
+        };
+        let update_global_var = UpdateGlobalVar {
+            from_chain_id: ChainId(1),
+            sub_account_id: SubAccountId(1),
+            parameter: Parameter::FundingInfos {
+                infos: vec![FundingInfo {
+                    pair_id: PairId(1),
+                    price: BigUint::from(2000u32),
+                    funding_rate: -5,
+                }],
+            },
+            serial_id: 100,
+        };
+
+        let txs: Vec<ZkLinkTx> = vec![
+            transfer.into(),
+            withdraw.into(),
+            change_pubkey.into(),
+            deposit.into(),
+            order_matching.clone().into(),
+            contract_matching.clone().into(),
+            update_global_var.into(),
+        ];
+        for tx in txs {
+            assert_round_trip(&tx);
+        }
+        assert_round_trip(&order_matching.maker);
+        assert_round_trip(&contract_matching.taker);
+    }
+
+    #[test]
+    fn test_signatures_round_trip() {
+        let signer = ZkLinkSigner::new_from_hex_eth_signer(PRIVATE_KEY).unwrap();
+        let signature = signer.sign_musig(b"zklink").unwrap();
+        assert_round_trip(&signature);
+
+        let eth_signature = PackedEthSignature::from_hex("0x91dc468f37b6ef35cd0972881d37636f0c8f8dc974608ee9bf2e20ec03c546876092999bb802e6d673bb9fc858d750fa3e578b6bd2f3fe5a8e74ca23504a42661c").unwrap();
+        let stark_signature =
+            StarkEip712Signature::from_hex(&format!("0x{:064x}{:064x}{:064x}", 1, 2, 3)).unwrap();
+        let layer1_signatures = [
+            TxLayer1Signature::EthereumSignature(eth_signature.clone()),
+            TxLayer1Signature::EIP1271Signature(EIP1271Signature(vec![1, 2, 3])),
+            TxLayer1Signature::StarkSignature(stark_signature),
+        ];
+        for layer1_signature in &layer1_signatures {
+            assert_round_trip(layer1_signature);
+        }
+
+        let tx_signature = TxSignature {
+            tx: Transfer::default().into(),
+            layer1_signature: Some(eth_signature.into()),
+        };
+        assert_round_trip(&tx_signature);
+    }
+
+    #[test]
+    fn test_invalid_binary() {
+        let tx: ZkLinkTx = Transfer::default().into();
+        let mut binary = tx.to_binary();
+        assert_eq!(binary[0], CODEC_VERSION);
+
+        // trailing bytes
+        binary.push(0);
+        assert!(ZkLinkTx::from_binary(&binary).is_err());
+        binary.pop();
+        // unknown version
+        binary[0] = CODEC_VERSION + 1;
+        assert!(ZkLinkTx::from_binary(&binary).is_err());
+        assert!(ZkLinkTx::from_binary(&[]).is_err());
+
+        // the amount 1 encoded with a leading zero
+        let mut bytes = Vec::new();
+        BorshSerialize::serialize(&vec![0u8, 1], &mut bytes).unwrap();
+        assert!(biguint::deserialize(&mut bytes.as_slice()).is_err());
+    }
+}
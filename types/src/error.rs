@@ -1,6 +1,9 @@
+use crate::basic_types::AccountId;
 use thiserror::Error;
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::JsValue;
+use zklink_sdk_signers::eth_signer::Address;
+use zklink_sdk_signers::zklink_signer::pubkey_hash::PubKeyHash;
 
 #[derive(Debug, Error, PartialEq)]
 pub enum TypeError {
@@ -18,6 +21,12 @@ pub enum TypeError {
     TooBigInteger,
     #[error("{0}")]
     InvalidBigIntStr(String),
+    #[error("Invalid pubdata: {0}")]
+    InvalidPubdata(String),
+    #[error("Invalid signed bytes: {0}")]
+    InvalidSignedBytes(String),
+    #[error("Invalid binary: {0}")]
+    InvalidBinary(String),
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -26,3 +35,34 @@ impl From<TypeError> for JsValue {
         JsValue::from_str(&format!("error: {error}"))
     }
 }
+
+#[derive(Debug, Error, PartialEq)]
+pub enum TxVerifyError {
+    #[error("Invalid zklink signature")]
+    InvalidZkLinkSignature,
+    #[error("Tx is signed by pubkey hash {}, expected {}", .0.as_hex(), .1.as_hex())]
+    ZkLinkSignerMismatch(PubKeyHash, PubKeyHash),
+    #[error("Token symbol is required to verify the layer1 signature")]
+    MissingTokenSymbol,
+    #[error("Starknet chain id is required to verify the starknet signature")]
+    MissingStarknetChainId,
+    #[error("Invalid layer1 signature: {0}")]
+    InvalidLayer1Signature(String),
+    #[error("Layer1 signature is signed by {0:?}, expected {1:?}")]
+    Layer1SignerMismatch(Address, Address),
+    #[error("Layer1 signature of {0} could not be verified offline")]
+    UnsupportedLayer1Signature(String),
+    #[error("ChangePubKey is authorized by {0:?}, expected {1:?}")]
+    ChangePubKeyAuthMismatch(Address, Address),
+    #[error("Layer1 signature is required")]
+    MissingLayer1Signature,
+    #[error("Pubkey hash of the account {0} is required to verify its order")]
+    MissingOrderPubKeyHash(AccountId),
+}
+
+#[cfg(target_arch = "wasm32")]
+impl From<TxVerifyError> for JsValue {
+    fn from(error: TxVerifyError) -> Self {
+        JsValue::from_str(&format!("error: {error}"))
+    }
+}
@@ -1,5 +1,10 @@
 pub mod basic_types;
+#[cfg(feature = "borsh")]
+pub mod codec;
 pub mod error;
+pub mod operations;
+#[cfg(feature = "schemars")]
+mod schema;
 pub mod signatures;
 pub mod tx_builder;
 pub mod tx_type;
@@ -19,14 +24,20 @@ pub mod prelude {
         PriorityOpId, SlotId, SubAccountId, TimeStamp, TokenId,
     };
     pub use super::error::TypeError;
+    pub use super::operations::{
+        AutoDeleveragingOp, ChangePubKeyOp, ContractMatchingOp, DepositOp, ForcedExitOp,
+        FullExitOp, FundingOp, LiquidationOp, OrderMatchingOp, PubdataOp, TransferOp,
+        UpdateGlobalVarOp, WithdrawOp, ZkLinkOp,
+    };
     pub use super::signatures::{TxLayer1Signature, TxSignature};
     pub use super::tx_builder::*;
     #[cfg(feature = "ffi")]
     pub use super::tx_type::ToZklinkTx;
     pub use super::tx_type::{
-        change_pubkey::{ChangePubKey, ChangePubKeyAuthData, Create2Data},
+        change_pubkey::{ChangePubKey, ChangePubKeyAuthData, Create2Data, EthECDSAAuthScheme},
         contract::*,
         deposit::Deposit,
+        display::{DisplayMetadata, FieldValue, TokenMetadata, TxDescription, TxDisplay, TxField},
         exit_info::ExitInfo,
         forced_exit::ForcedExit,
         full_exit::FullExit,
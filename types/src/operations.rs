@@ -0,0 +1,1429 @@
+//! The operations executed in the zklink block, and their pubdata committed to layer1.
+//!
+//! Every op wraps the tx it executes with the data that is decided in the execution, e.g. the
+//! account id of the deposit. Its pubdata starts with the op code(the `TX_TYPE` of the tx) and
+//! is padded with zeros to a whole number of chunks, see [`CHUNK_BYTES`].
+//!
+//! The pubdata only keeps the data checked by the circuit, the fields that are not contained
+//! (e.g. the signatures and the timestamps) are left as default after `from_pubdata`.
+use crate::basic_types::bytes_reader::BytesReader;
+use crate::basic_types::pack::{pack_fee_amount, pack_token_amount};
+use crate::basic_types::pad::pad_front;
+use crate::basic_types::{AccountId, MarginId, Nonce, PairId, SlotId, ZkLinkAddress};
+use crate::error::TypeError;
+use crate::params::*;
+use crate::prelude::{
+    AutoDeleveraging, ChangePubKey, Contract, ContractMatching, Deposit, ForcedExit, FullExit,
+    Funding, FundingInfo, GetBytes, Liquidation, Order, OrderMatching, Parameter, Transfer,
+    UpdateGlobalVar, Withdraw, ZkLinkTx,
+};
+use num::BigUint;
+
+/// The op code of the empty chunk that pads the block.
+pub const NOOP_OP_CODE: u8 = 0x00;
+
+const AMOUNT_BYTES: usize = BALANCE_BIT_WIDTH / 8;
+const PRICE_BYTES: usize = PRICE_BIT_WIDTH / 8;
+
+pub trait PubdataOp: Sized {
+    /// The first byte of the pubdata, it's the `TX_TYPE` of the tx.
+    const OP_CODE: u8;
+
+    /// The number of the chunks of the op whose pubdata starts at `pubdata`, only the first
+    /// chunk is read.
+    fn chunks_of(pubdata: &[u8]) -> Result<usize, TypeError>;
+
+    /// The number of the chunks of the op.
+    fn chunks(&self) -> usize;
+
+    /// Encode the op into the pubdata of `chunks() * CHUNK_BYTES` bytes, fails if the tx could
+    /// not be encoded, e.g. too many makers.
+    fn to_pubdata(&self) -> Result<Vec<u8>, TypeError>;
+
+    /// Decode the op from the pubdata, the length must be exactly the chunks of the op.
+    fn from_pubdata(pubdata: &[u8]) -> Result<Self, TypeError>;
+}
+
+/// The deposit of a layer1 tx to the account found or created for `tx.to`.
+#[derive(Debug, Clone)]
+pub struct DepositOp {
+    pub tx: Deposit,
+    pub account_id: AccountId,
+}
+
+/// op_code(1) | chain_id(1) | account_id(4) | sub_account_id(1) | l1_source_token(2) | l2_target_token(2) | amount(16) | owner(32)
+impl PubdataOp for DepositOp {
+    const OP_CODE: u8 = Deposit::TX_TYPE;
+
+    fn chunks_of(_pubdata: &[u8]) -> Result<usize, TypeError> {
+        Ok(DEPOSIT_CHUNKS)
+    }
+
+    fn chunks(&self) -> usize {
+        DEPOSIT_CHUNKS
+    }
+
+    fn to_pubdata(&self) -> Result<Vec<u8>, TypeError> {
+        let mut out = vec![Self::OP_CODE, *self.tx.from_chain_id];
+        out.extend(self.account_id.to_be_bytes());
+        out.push(*self.tx.sub_account_id);
+        out.extend(self.tx.l1_source_token.get_bytes());
+        out.extend(self.tx.l2_target_token.get_bytes());
+        out.extend(amount_bytes(&self.tx.amount));
+        out.extend(self.tx.to.to_fixed_bytes());
+        pad_chunks(out, DEPOSIT_CHUNKS)
+    }
+
+    fn from_pubdata(pubdata: &[u8]) -> Result<Self, TypeError> {
+        let mut reader = pubdata_reader(pubdata, Self::OP_CODE, DEPOSIT_CHUNKS)?;
+        let from_chain_id = reader.chain_id()?;
+        let account_id = reader.account_id()?;
+        let tx = Deposit {
+            from_chain_id,
+            sub_account_id: reader.sub_account_id()?,
+            l1_source_token: reader.token_id()?,
+            l2_target_token: reader.token_id()?,
+            amount: reader.amount()?,
+            to: reader.address()?,
+            ..Default::default()
+        };
+        Ok(DepositOp { tx, account_id })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WithdrawOp {
+    pub tx: Withdraw,
+}
+
+/// op_code(1) | chain_id(1) | account_id(4) | sub_account_id(1) | l1_target_token(2) | l2_source_token(2) | amount(16) | fee(2)
+/// | owner(32) | nonce(4) | withdraw_fee_ratio(2) | withdraw_to_l1(1)
+impl PubdataOp for WithdrawOp {
+    const OP_CODE: u8 = Withdraw::TX_TYPE;
+
+    fn chunks_of(_pubdata: &[u8]) -> Result<usize, TypeError> {
+        Ok(WITHDRAW_CHUNKS)
+    }
+
+    fn chunks(&self) -> usize {
+        WITHDRAW_CHUNKS
+    }
+
+    fn to_pubdata(&self) -> Result<Vec<u8>, TypeError> {
+        let tx = &self.tx;
+        let mut out = vec![Self::OP_CODE, *tx.to_chain_id];
+        out.extend(tx.account_id.to_be_bytes());
+        out.push(*tx.sub_account_id);
+        out.extend(tx.l1_target_token.get_bytes());
+        out.extend(tx.l2_source_token.get_bytes());
+        out.extend(amount_bytes(&tx.amount));
+        out.extend(pack_fee_amount(&tx.fee));
+        out.extend(tx.to.to_fixed_bytes());
+        out.extend(tx.nonce.to_be_bytes());
+        out.extend(tx.withdraw_fee_ratio.to_be_bytes());
+        out.push(tx.withdraw_to_l1);
+        pad_chunks(out, WITHDRAW_CHUNKS)
+    }
+
+    fn from_pubdata(pubdata: &[u8]) -> Result<Self, TypeError> {
+        let mut reader = pubdata_reader(pubdata, Self::OP_CODE, WITHDRAW_CHUNKS)?;
+        let tx = Withdraw {
+            to_chain_id: reader.chain_id()?,
+            account_id: reader.account_id()?,
+            sub_account_id: reader.sub_account_id()?,
+            l1_target_token: reader.token_id()?,
+            l2_source_token: reader.token_id()?,
+            amount: reader.amount()?,
+            fee: reader.packed_fee()?,
+            to: reader.address()?,
+            nonce: reader.nonce()?,
+            withdraw_fee_ratio: reader.u16()?,
+            withdraw_to_l1: reader.u8()?,
+            ..Default::default()
+        };
+        Ok(WithdrawOp { tx })
+    }
+}
+
+/// The transfer to the account found or created for `tx.to`.
+#[derive(Debug, Clone)]
+pub struct TransferOp {
+    pub tx: Transfer,
+    pub to_account_id: AccountId,
+}
+
+/// op_code(1) | account_id(4) | from_sub_account_id(1) | token(2) | packed_amount(5) | to(32) | to_account_id(4)
+/// | to_sub_account_id(1) | fee(2)
+impl PubdataOp for TransferOp {
+    const OP_CODE: u8 = Transfer::TX_TYPE;
+
+    fn chunks_of(_pubdata: &[u8]) -> Result<usize, TypeError> {
+        Ok(TRANSFER_CHUNKS)
+    }
+
+    fn chunks(&self) -> usize {
+        TRANSFER_CHUNKS
+    }
+
+    fn to_pubdata(&self) -> Result<Vec<u8>, TypeError> {
+        let tx = &self.tx;
+        let mut out = vec![Self::OP_CODE];
+        out.extend(tx.account_id.to_be_bytes());
+        out.push(*tx.from_sub_account_id);
+        out.extend(tx.token.get_bytes());
+        out.extend(pack_token_amount(&tx.amount));
+        out.extend(tx.to.to_fixed_bytes());
+        out.extend(self.to_account_id.to_be_bytes());
+        out.push(*tx.to_sub_account_id);
+        out.extend(pack_fee_amount(&tx.fee));
+        pad_chunks(out, TRANSFER_CHUNKS)
+    }
+
+    fn from_pubdata(pubdata: &[u8]) -> Result<Self, TypeError> {
+        let mut reader = pubdata_reader(pubdata, Self::OP_CODE, TRANSFER_CHUNKS)?;
+        let account_id = reader.account_id()?;
+        let from_sub_account_id = reader.sub_account_id()?;
+        let token = reader.token_id()?;
+        let amount = reader.packed_amount()?;
+        let to = reader.address()?;
+        let to_account_id = reader.account_id()?;
+        let tx = Transfer {
+            account_id,
+            from_sub_account_id,
+            to_sub_account_id: reader.sub_account_id()?,
+            to,
+            token,
+            amount,
+            fee: reader.packed_fee()?,
+            ..Default::default()
+        };
+        Ok(TransferOp { tx, to_account_id })
+    }
+}
+
+/// The full exit of all the balance of the token, the amount is decided in the execution.
+#[derive(Debug, Clone)]
+pub struct FullExitOp {
+    pub tx: FullExit,
+    pub exit_amount: BigUint,
+}
+
+/// op_code(1) | chain_id(1) | account_id(4) | sub_account_id(1) | owner(32) | l1_target_token(2) | l2_source_token(2) | amount(16)
+impl PubdataOp for FullExitOp {
+    const OP_CODE: u8 = FullExit::TX_TYPE;
+
+    fn chunks_of(_pubdata: &[u8]) -> Result<usize, TypeError> {
+        Ok(FULL_EXIT_CHUNKS)
+    }
+
+    fn chunks(&self) -> usize {
+        FULL_EXIT_CHUNKS
+    }
+
+    fn to_pubdata(&self) -> Result<Vec<u8>, TypeError> {
+        let tx = &self.tx;
+        let mut out = vec![Self::OP_CODE, *tx.to_chain_id];
+        out.extend(tx.account_id.to_be_bytes());
+        out.push(*tx.sub_account_id);
+        out.extend(tx.exit_address.to_fixed_bytes());
+        out.extend(tx.l1_target_token.get_bytes());
+        out.extend(tx.l2_source_token.get_bytes());
+        out.extend(amount_bytes(&self.exit_amount));
+        pad_chunks(out, FULL_EXIT_CHUNKS)
+    }
+
+    fn from_pubdata(pubdata: &[u8]) -> Result<Self, TypeError> {
+        let mut reader = pubdata_reader(pubdata, Self::OP_CODE, FULL_EXIT_CHUNKS)?;
+        let tx = FullExit {
+            to_chain_id: reader.chain_id()?,
+            account_id: reader.account_id()?,
+            sub_account_id: reader.sub_account_id()?,
+            exit_address: reader.address()?,
+            l1_target_token: reader.token_id()?,
+            l2_source_token: reader.token_id()?,
+            ..Default::default()
+        };
+        let exit_amount = reader.amount()?;
+        Ok(FullExitOp { tx, exit_amount })
+    }
+}
+
+/// The change of the pubkey hash of the account at `address`.
+#[derive(Debug, Clone)]
+pub struct ChangePubKeyOp {
+    pub tx: ChangePubKey,
+    pub address: ZkLinkAddress,
+}
+
+/// op_code(1) | chain_id(1) | account_id(4) | sub_account_id(1) | new_pk_hash(20) | owner(32) | nonce(4) | fee_token(2) | fee(2)
+impl PubdataOp for ChangePubKeyOp {
+    const OP_CODE: u8 = ChangePubKey::TX_TYPE;
+
+    fn chunks_of(_pubdata: &[u8]) -> Result<usize, TypeError> {
+        Ok(CHANGE_PUBKEY_CHUNKS)
+    }
+
+    fn chunks(&self) -> usize {
+        CHANGE_PUBKEY_CHUNKS
+    }
+
+    fn to_pubdata(&self) -> Result<Vec<u8>, TypeError> {
+        let tx = &self.tx;
+        let mut out = vec![Self::OP_CODE, *tx.chain_id];
+        out.extend(tx.account_id.to_be_bytes());
+        out.push(*tx.sub_account_id);
+        out.extend(tx.new_pk_hash.data);
+        out.extend(self.address.to_fixed_bytes());
+        out.extend(tx.nonce.to_be_bytes());
+        out.extend(tx.fee_token.get_bytes());
+        out.extend(pack_fee_amount(&tx.fee));
+        pad_chunks(out, CHANGE_PUBKEY_CHUNKS)
+    }
+
+    fn from_pubdata(pubdata: &[u8]) -> Result<Self, TypeError> {
+        let mut reader = pubdata_reader(pubdata, Self::OP_CODE, CHANGE_PUBKEY_CHUNKS)?;
+        let chain_id = reader.chain_id()?;
+        let account_id = reader.account_id()?;
+        let sub_account_id = reader.sub_account_id()?;
+        let new_pk_hash = reader.pub_key_hash()?;
+        let address = reader.address()?;
+        let tx = ChangePubKey {
+            chain_id,
+            account_id,
+            sub_account_id,
+            new_pk_hash,
+            nonce: reader.nonce()?,
+            fee_token: reader.token_id()?,
+            fee: reader.packed_fee()?,
+            ..Default::default()
+        };
+        Ok(ChangePubKeyOp { tx, address })
+    }
+}
+
+/// The forced exit of the account found for `tx.target`.
+#[derive(Debug, Clone)]
+pub struct ForcedExitOp {
+    pub tx: ForcedExit,
+    pub target_account_id: AccountId,
+}
+
+/// op_code(1) | chain_id(1) | initiator_account_id(4) | initiator_sub_account_id(1) | initiator_nonce(4) | target_account_id(4)
+/// | target_sub_account_id(1) | l1_target_token(2) | l2_source_token(2) | amount(16) | withdraw_to_l1(1) | target(32)
+impl PubdataOp for ForcedExitOp {
+    const OP_CODE: u8 = ForcedExit::TX_TYPE;
+
+    fn chunks_of(_pubdata: &[u8]) -> Result<usize, TypeError> {
+        Ok(FORCED_EXIT_CHUNKS)
+    }
+
+    fn chunks(&self) -> usize {
+        FORCED_EXIT_CHUNKS
+    }
+
+    fn to_pubdata(&self) -> Result<Vec<u8>, TypeError> {
+        let tx = &self.tx;
+        let mut out = vec![Self::OP_CODE, *tx.to_chain_id];
+        out.extend(tx.initiator_account_id.to_be_bytes());
+        out.push(*tx.initiator_sub_account_id);
+        out.extend(tx.initiator_nonce.to_be_bytes());
+        out.extend(self.target_account_id.to_be_bytes());
+        out.push(*tx.target_sub_account_id);
+        out.extend(tx.l1_target_token.get_bytes());
+        out.extend(tx.l2_source_token.get_bytes());
+        out.extend(amount_bytes(&tx.exit_amount));
+        out.push(tx.withdraw_to_l1);
+        out.extend(tx.target.to_fixed_bytes());
+        pad_chunks(out, FORCED_EXIT_CHUNKS)
+    }
+
+    fn from_pubdata(pubdata: &[u8]) -> Result<Self, TypeError> {
+        let mut reader = pubdata_reader(pubdata, Self::OP_CODE, FORCED_EXIT_CHUNKS)?;
+        let to_chain_id = reader.chain_id()?;
+        let initiator_account_id = reader.account_id()?;
+        let initiator_sub_account_id = reader.sub_account_id()?;
+        let initiator_nonce = reader.nonce()?;
+        let target_account_id = reader.account_id()?;
+        let tx = ForcedExit {
+            to_chain_id,
+            initiator_account_id,
+            initiator_sub_account_id,
+            initiator_nonce,
+            target_sub_account_id: reader.sub_account_id()?,
+            l1_target_token: reader.token_id()?,
+            l2_source_token: reader.token_id()?,
+            exit_amount: reader.amount()?,
+            withdraw_to_l1: reader.u8()?,
+            target: reader.address()?,
+            ..Default::default()
+        };
+        Ok(ForcedExitOp {
+            tx,
+            target_account_id,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OrderMatchingOp {
+    pub tx: OrderMatching,
+}
+
+/// op_code(1) | account_id(4) | sub_account_id(1) | fee_token(2) | fee(2)
+/// | maker: account_id(4) | sub_account_id(1) | slot_id(2) | nonce(3) | fee_rates(2) | has_subsidy(1)
+/// | taker: account_id(4) | sub_account_id(1) | slot_id(2) | nonce(3) | fee_rates(2) | has_subsidy(1) | is_sell(1)
+/// | base_token(2) | quote_token(2) | base_amount(16) | quote_amount(16)
+impl PubdataOp for OrderMatchingOp {
+    const OP_CODE: u8 = OrderMatching::TX_TYPE;
+
+    fn chunks_of(_pubdata: &[u8]) -> Result<usize, TypeError> {
+        Ok(ORDER_MATCHING_CHUNKS)
+    }
+
+    fn chunks(&self) -> usize {
+        ORDER_MATCHING_CHUNKS
+    }
+
+    fn to_pubdata(&self) -> Result<Vec<u8>, TypeError> {
+        let tx = &self.tx;
+        let mut out = vec![Self::OP_CODE];
+        out.extend(tx.account_id.to_be_bytes());
+        out.push(*tx.sub_account_id);
+        out.extend(tx.fee_token.get_bytes());
+        out.extend(pack_fee_amount(&tx.fee));
+        for order in [&tx.maker, &tx.taker] {
+            out.extend(order.account_id.to_be_bytes());
+            out.push(*order.sub_account_id);
+            out.extend((*order.slot_id as u16).to_be_bytes());
+            out.extend(order_nonce_bytes(order.nonce));
+            out.extend(order.fee_rates);
+            out.push(order.has_subsidy);
+        }
+        out.push(tx.taker.is_sell);
+        out.extend(tx.taker.base_token_id.get_bytes());
+        out.extend(tx.taker.quote_token_id.get_bytes());
+        out.extend(amount_bytes(&tx.expect_base_amount));
+        out.extend(amount_bytes(&tx.expect_quote_amount));
+        pad_chunks(out, ORDER_MATCHING_CHUNKS)
+    }
+
+    fn from_pubdata(pubdata: &[u8]) -> Result<Self, TypeError> {
+        let mut reader = pubdata_reader(pubdata, Self::OP_CODE, ORDER_MATCHING_CHUNKS)?;
+        let account_id = reader.account_id()?;
+        let sub_account_id = reader.sub_account_id()?;
+        let fee_token = reader.token_id()?;
+        let fee = reader.packed_fee()?;
+        let mut orders = Vec::with_capacity(2);
+        for _ in 0..2 {
+            orders.push(Order {
+                account_id: reader.account_id()?,
+                sub_account_id: reader.sub_account_id()?,
+                slot_id: SlotId(reader.u16()? as u32),
+                nonce: reader.order_nonce()?,
+                fee_rates: [reader.u8()?, reader.u8()?],
+                has_subsidy: reader.u8()?,
+                ..Default::default()
+            });
+        }
+        let is_sell = reader.u8()?;
+        if is_sell > 1 {
+            return Err(TypeError::InvalidPubdata(format!(
+                "invalid is_sell {is_sell}"
+            )));
+        }
+        let base_token_id = reader.token_id()?;
+        let quote_token_id = reader.token_id()?;
+        let mut taker = orders.pop().unwrap();
+        let mut maker = orders.pop().unwrap();
+        for order in [&mut maker, &mut taker] {
+            order.base_token_id = base_token_id;
+            order.quote_token_id = quote_token_id;
+        }
+        taker.is_sell = is_sell;
+        maker.is_sell = 1 - is_sell;
+        let tx = OrderMatching {
+            account_id,
+            sub_account_id,
+            taker,
+            maker,
+            fee,
+            fee_token,
+            expect_base_amount: reader.amount()?,
+            expect_quote_amount: reader.amount()?,
+            ..Default::default()
+        };
+        Ok(OrderMatchingOp { tx })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ContractMatchingOp {
+    pub tx: ContractMatching,
+}
+
+/// op_code(1) | account_id(4) | sub_account_id(1) | fee_token(2) | fee(2) | maker_num(1), padded to a chunk.
+/// Then the taker and the makers, each in a chunk:
+/// account_id(4) | sub_account_id(1) | slot_id(2) | nonce(3) | pair_id(1) | packed_size(5) | direction(1) | fee_rates(2) | has_subsidy(1)
+impl PubdataOp for ContractMatchingOp {
+    const OP_CODE: u8 = ContractMatching::TX_TYPE;
+
+    fn chunks_of(pubdata: &[u8]) -> Result<usize, TypeError> {
+        let maker_num = *pubdata
+            .get(10)
+            .ok_or_else(|| TypeError::InvalidPubdata("missing the maker number".to_string()))?;
+        Ok(CONTRACT_MATCHING_HEADER_CHUNKS + (1 + maker_num as usize) * CONTRACT_CHUNKS)
+    }
+
+    fn chunks(&self) -> usize {
+        CONTRACT_MATCHING_HEADER_CHUNKS + (1 + self.tx.maker.len()) * CONTRACT_CHUNKS
+    }
+
+    fn to_pubdata(&self) -> Result<Vec<u8>, TypeError> {
+        let tx = &self.tx;
+        let mut out = vec![Self::OP_CODE];
+        out.extend(tx.account_id.to_be_bytes());
+        out.push(*tx.sub_account_id);
+        out.extend(tx.fee_token.get_bytes());
+        out.extend(pack_fee_amount(&tx.fee));
+        out.push(item_num(tx.maker.len(), "makers")?);
+        let mut chunks = CONTRACT_MATCHING_HEADER_CHUNKS;
+        for contract in std::iter::once(&tx.taker).chain(&tx.maker) {
+            out = pad_chunks(out, chunks)?;
+            out.extend(contract.account_id.to_be_bytes());
+            out.push(*contract.sub_account_id);
+            out.extend((*contract.slot_id as u16).to_be_bytes());
+            out.extend(order_nonce_bytes(contract.nonce));
+            out.push(*contract.pair_id as u8);
+            out.extend(pack_token_amount(&contract.size));
+            out.push(contract.direction);
+            out.extend(contract.fee_rates);
+            out.push(contract.has_subsidy);
+            chunks += CONTRACT_CHUNKS;
+        }
+        pad_chunks(out, chunks)
+    }
+
+    fn from_pubdata(pubdata: &[u8]) -> Result<Self, TypeError> {
+        let chunks = Self::chunks_of(pubdata)?;
+        let mut reader = pubdata_reader(pubdata, Self::OP_CODE, chunks)?;
+        let account_id = reader.account_id()?;
+        let sub_account_id = reader.sub_account_id()?;
+        let fee_token = reader.token_id()?;
+        let fee = reader.packed_fee()?;
+        let maker_num = reader.u8()? as usize;
+        let mut contracts = Vec::with_capacity(1 + maker_num);
+        for _ in 0..=maker_num {
+            reader.align(CHUNK_BYTES);
+            contracts.push(Contract {
+                account_id: reader.account_id()?,
+                sub_account_id: reader.sub_account_id()?,
+                slot_id: SlotId(reader.u16()? as u32),
+                nonce: reader.order_nonce()?,
+                pair_id: PairId(reader.u8()? as u16),
+                size: reader.packed_amount()?,
+                direction: reader.u8()?,
+                fee_rates: [reader.u8()?, reader.u8()?],
+                has_subsidy: reader.u8()?,
+                ..Default::default()
+            });
+        }
+        let taker = contracts.remove(0);
+        let tx = ContractMatching {
+            account_id,
+            sub_account_id,
+            maker: contracts,
+            taker,
+            fee,
+            fee_token,
+            ..Default::default()
+        };
+        Ok(ContractMatchingOp { tx })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LiquidationOp {
+    pub tx: Liquidation,
+}
+
+/// op_code(1) | account_id(4) | sub_account_id(1) | sub_account_nonce(4) | liquidation_account_id(4) | fee_token(2) | fee(2)
+impl PubdataOp for LiquidationOp {
+    const OP_CODE: u8 = Liquidation::TX_TYPE;
+
+    fn chunks_of(_pubdata: &[u8]) -> Result<usize, TypeError> {
+        Ok(LIQUIDATION_CHUNKS)
+    }
+
+    fn chunks(&self) -> usize {
+        LIQUIDATION_CHUNKS
+    }
+
+    fn to_pubdata(&self) -> Result<Vec<u8>, TypeError> {
+        let tx = &self.tx;
+        let mut out = vec![Self::OP_CODE];
+        out.extend(tx.account_id.to_be_bytes());
+        out.push(*tx.sub_account_id);
+        out.extend(tx.sub_account_nonce.to_be_bytes());
+        out.extend(tx.liquidation_account_id.to_be_bytes());
+        out.extend(tx.fee_token.get_bytes());
+        out.extend(pack_fee_amount(&tx.fee));
+        pad_chunks(out, LIQUIDATION_CHUNKS)
+    }
+
+    fn from_pubdata(pubdata: &[u8]) -> Result<Self, TypeError> {
+        let mut reader = pubdata_reader(pubdata, Self::OP_CODE, LIQUIDATION_CHUNKS)?;
+        let tx = Liquidation {
+            account_id: reader.account_id()?,
+            sub_account_id: reader.sub_account_id()?,
+            sub_account_nonce: reader.nonce()?,
+            liquidation_account_id: reader.account_id()?,
+            fee_token: reader.token_id()?,
+            fee: reader.packed_fee()?,
+            ..Default::default()
+        };
+        Ok(LiquidationOp { tx })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AutoDeleveragingOp {
+    pub tx: AutoDeleveraging,
+}
+
+/// op_code(1) | account_id(4) | sub_account_id(1) | sub_account_nonce(4) | adl_account_id(4) | pair_id(1) | packed_adl_size(5)
+/// | adl_price(15) | fee_token(2) | fee(2)
+impl PubdataOp for AutoDeleveragingOp {
+    const OP_CODE: u8 = AutoDeleveraging::TX_TYPE;
+
+    fn chunks_of(_pubdata: &[u8]) -> Result<usize, TypeError> {
+        Ok(AUTO_DELEVERAGING_CHUNKS)
+    }
+
+    fn chunks(&self) -> usize {
+        AUTO_DELEVERAGING_CHUNKS
+    }
+
+    fn to_pubdata(&self) -> Result<Vec<u8>, TypeError> {
+        let tx = &self.tx;
+        let mut out = vec![Self::OP_CODE];
+        out.extend(tx.account_id.to_be_bytes());
+        out.push(*tx.sub_account_id);
+        out.extend(tx.sub_account_nonce.to_be_bytes());
+        out.extend(tx.adl_account_id.to_be_bytes());
+        out.push(*tx.pair_id as u8);
+        out.extend(pack_token_amount(&tx.adl_size));
+        out.extend(pad_front(&tx.adl_price.to_bytes_be(), PRICE_BYTES));
+        out.extend(tx.fee_token.get_bytes());
+        out.extend(pack_fee_amount(&tx.fee));
+        pad_chunks(out, AUTO_DELEVERAGING_CHUNKS)
+    }
+
+    fn from_pubdata(pubdata: &[u8]) -> Result<Self, TypeError> {
+        let mut reader = pubdata_reader(pubdata, Self::OP_CODE, AUTO_DELEVERAGING_CHUNKS)?;
+        let tx = AutoDeleveraging {
+            account_id: reader.account_id()?,
+            sub_account_id: reader.sub_account_id()?,
+            sub_account_nonce: reader.nonce()?,
+            adl_account_id: reader.account_id()?,
+            pair_id: PairId(reader.u8()? as u16),
+            adl_size: reader.packed_amount()?,
+            adl_price: reader.price()?,
+            fee_token: reader.token_id()?,
+            fee: reader.packed_fee()?,
+            ..Default::default()
+        };
+        Ok(AutoDeleveragingOp { tx })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FundingOp {
+    pub tx: Funding,
+}
+
+/// op_code(1) | account_id(4) | sub_account_id(1) | sub_account_nonce(4) | fee_token(2) | fee(2) | account_num(2), padded to a chunk.
+/// Then the funding account ids(4), `FUNDING_ACCOUNTS_PER_CHUNK` of them in a chunk.
+impl PubdataOp for FundingOp {
+    const OP_CODE: u8 = Funding::TX_TYPE;
+
+    fn chunks_of(pubdata: &[u8]) -> Result<usize, TypeError> {
+        let account_num = pubdata
+            .get(14..16)
+            .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+            .ok_or_else(|| TypeError::InvalidPubdata("missing the account number".to_string()))?;
+        Ok(FUNDING_HEADER_CHUNKS + (account_num as usize).div_ceil(FUNDING_ACCOUNTS_PER_CHUNK))
+    }
+
+    fn chunks(&self) -> usize {
+        FUNDING_HEADER_CHUNKS
+            + self
+                .tx
+                .funding_account_ids
+                .len()
+                .div_ceil(FUNDING_ACCOUNTS_PER_CHUNK)
+    }
+
+    fn to_pubdata(&self) -> Result<Vec<u8>, TypeError> {
+        let tx = &self.tx;
+        let mut out = vec![Self::OP_CODE];
+        out.extend(tx.account_id.to_be_bytes());
+        out.push(*tx.sub_account_id);
+        out.extend(tx.sub_account_nonce.to_be_bytes());
+        out.extend(tx.fee_token.get_bytes());
+        out.extend(pack_fee_amount(&tx.fee));
+        out.extend(
+            item_num::<u16>(tx.funding_account_ids.len(), "funding accounts")?.to_be_bytes(),
+        );
+        let mut chunks = FUNDING_HEADER_CHUNKS;
+        for account_ids in tx.funding_account_ids.chunks(FUNDING_ACCOUNTS_PER_CHUNK) {
+            out = pad_chunks(out, chunks)?;
+            for account_id in account_ids {
+                out.extend(account_id.to_be_bytes());
+            }
+            chunks += 1;
+        }
+        pad_chunks(out, chunks)
+    }
+
+    fn from_pubdata(pubdata: &[u8]) -> Result<Self, TypeError> {
+        let chunks = Self::chunks_of(pubdata)?;
+        let mut reader = pubdata_reader(pubdata, Self::OP_CODE, chunks)?;
+        let account_id = reader.account_id()?;
+        let sub_account_id = reader.sub_account_id()?;
+        let sub_account_nonce = reader.nonce()?;
+        let fee_token = reader.token_id()?;
+        let fee = reader.packed_fee()?;
+        let account_num = reader.u16()? as usize;
+        let mut funding_account_ids = Vec::with_capacity(account_num);
+        for i in 0..account_num {
+            if i % FUNDING_ACCOUNTS_PER_CHUNK == 0 {
+                reader.align(CHUNK_BYTES);
+            }
+            funding_account_ids.push(reader.account_id()?);
+        }
+        let tx = Funding {
+            account_id,
+            sub_account_id,
+            sub_account_nonce,
+            funding_account_ids,
+            fee,
+            fee_token,
+            ..Default::default()
+        };
+        Ok(FundingOp { tx })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UpdateGlobalVarOp {
+    pub tx: UpdateGlobalVar,
+}
+
+/// op_code(1) | from_chain_id(1) | sub_account_id(1) | serial_id(8) | parameter_type(1) | parameter, the parameter is:
+/// * FeeAccount, InsuranceFundAccount: account_id(4)
+/// * MarginInfo: margin_id(1) | symbol(15) | token_id(2) | ratio(1)
+/// * ContractInfo: pair_id(1) | symbol(15) | initial_margin_rate(2) | maintenance_margin_rate(2)
+/// * FundingInfos: info_num(1) | infos: pair_id(1) | price(15) | funding_rate(2)
+impl UpdateGlobalVarOp {
+    const HEADER_BYTES: usize = 12;
+
+    fn parameter_bytes(parameter_type: u8, info_num: usize) -> Result<usize, TypeError> {
+        let bytes = match parameter_type {
+            Parameter::FEE_ACCOUNT_PARAM_TYPE | Parameter::INSURANCE_FUND_PARAM_TYPE => {
+                ACCOUNT_ID_BIT_WIDTH / 8
+            }
+            Parameter::MARGIN_INFO_PARAM_TYPE => 1 + PAIR_SYMBOL_BYTES + TOKEN_BIT_WIDTH / 8 + 1,
+            Parameter::CONTRACT_INFO_PARAM_TYPE => PAIR_BIT_WIDTH / 8 + PAIR_SYMBOL_BYTES + 4,
+            Parameter::FUNDING_RATE_PARAM_TYPE => {
+                1 + info_num * (PAIR_BIT_WIDTH / 8 + PRICE_BYTES + FUNDING_RATE_BYTES)
+            }
+            _ => {
+                return Err(TypeError::InvalidPubdata(format!(
+                    "invalid parameter type {parameter_type}"
+                )))
+            }
+        };
+        Ok(bytes)
+    }
+}
+
+impl PubdataOp for UpdateGlobalVarOp {
+    const OP_CODE: u8 = UpdateGlobalVar::TX_TYPE;
+
+    fn chunks_of(pubdata: &[u8]) -> Result<usize, TypeError> {
+        let (Some(parameter_type), info_num) = (
+            pubdata.get(Self::HEADER_BYTES - 1),
+            pubdata.get(Self::HEADER_BYTES),
+        ) else {
+            return Err(TypeError::InvalidPubdata(
+                "missing the parameter type".to_string(),
+            ));
+        };
+        let info_num = info_num.copied().unwrap_or_default() as usize;
+        let bytes = Self::HEADER_BYTES + Self::parameter_bytes(*parameter_type, info_num)?;
+        Ok(bytes.div_ceil(CHUNK_BYTES))
+    }
+
+    fn chunks(&self) -> usize {
+        let info_num = match &self.tx.parameter {
+            Parameter::FundingInfos { infos } => infos.len(),
+            _ => 0,
+        };
+        let parameter_bytes =
+            Self::parameter_bytes(self.tx.parameter.parameter_type(), info_num).unwrap();
+        (Self::HEADER_BYTES + parameter_bytes).div_ceil(CHUNK_BYTES)
+    }
+
+    fn to_pubdata(&self) -> Result<Vec<u8>, TypeError> {
+        let tx = &self.tx;
+        let mut out = vec![Self::OP_CODE, *tx.from_chain_id, *tx.sub_account_id];
+        out.extend(tx.serial_id.to_be_bytes());
+        out.push(tx.parameter.parameter_type());
+        match &tx.parameter {
+            Parameter::FundingInfos { infos } => {
+                out.push(item_num(infos.len(), "funding infos")?);
+                out.extend(infos.get_bytes());
+            }
+            // the same as the signed bytes without the parameter type
+            parameter => out.extend(&parameter.get_bytes()[1..]),
+        }
+        pad_chunks(out, self.chunks())
+    }
+
+    fn from_pubdata(pubdata: &[u8]) -> Result<Self, TypeError> {
+        let chunks = Self::chunks_of(pubdata)?;
+        let mut reader = pubdata_reader(pubdata, Self::OP_CODE, chunks)?;
+        let from_chain_id = reader.chain_id()?;
+        let sub_account_id = reader.sub_account_id()?;
+        let serial_id = reader.u64()?;
+        let parameter = match reader.u8()? {
+            Parameter::FEE_ACCOUNT_PARAM_TYPE => Parameter::FeeAccount {
+                account_id: reader.account_id()?,
+            },
+            Parameter::INSURANCE_FUND_PARAM_TYPE => Parameter::InsuranceFundAccount {
+                account_id: reader.account_id()?,
+            },
+            Parameter::MARGIN_INFO_PARAM_TYPE => Parameter::MarginInfo {
+                margin_id: MarginId(reader.u8()?),
+                symbol: reader.symbol()?,
+                token_id: reader.token_id()?,
+                ratio: reader.u8()?,
+            },
+            Parameter::CONTRACT_INFO_PARAM_TYPE => Parameter::ContractInfo {
+                pair_id: PairId(reader.u8()? as u16),
+                symbol: reader.symbol()?,
+                initial_margin_rate: reader.u16()?,
+                maintenance_margin_rate: reader.u16()?,
+            },
+            _ => {
+                let info_num = reader.u8()?;
+                let mut infos = Vec::with_capacity(info_num as usize);
+                for _ in 0..info_num {
+                    infos.push(FundingInfo {
+                        pair_id: PairId(reader.u8()? as u16),
+                        price: reader.price()?,
+                        funding_rate: reader.funding_rate()?,
+                    });
+                }
+                Parameter::FundingInfos { infos }
+            }
+        };
+        let tx = UpdateGlobalVar {
+            from_chain_id,
+            sub_account_id,
+            parameter,
+            serial_id,
+        };
+        Ok(UpdateGlobalVarOp { tx })
+    }
+}
+
+/// The op executed in the zklink block.
+#[derive(Debug, Clone)]
+pub enum ZkLinkOp {
+    Deposit(Box<DepositOp>),
+    Withdraw(Box<WithdrawOp>),
+    Transfer(Box<TransferOp>),
+    FullExit(Box<FullExitOp>),
+    ChangePubKey(Box<ChangePubKeyOp>),
+    ForcedExit(Box<ForcedExitOp>),
+    OrderMatching(Box<OrderMatchingOp>),
+    ContractMatching(Box<ContractMatchingOp>),
+    Liquidation(Box<LiquidationOp>),
+    AutoDeleveraging(Box<AutoDeleveragingOp>),
+    UpdateGlobalVar(Box<UpdateGlobalVarOp>),
+    Funding(Box<FundingOp>),
+}
+
+impl ZkLinkOp {
+    /// The tx executed by the op.
+    pub fn tx(&self) -> ZkLinkTx {
+        match self {
+            ZkLinkOp::Deposit(op) => op.tx.clone().into(),
+            ZkLinkOp::Withdraw(op) => op.tx.clone().into(),
+            ZkLinkOp::Transfer(op) => op.tx.clone().into(),
+            ZkLinkOp::FullExit(op) => op.tx.clone().into(),
+            ZkLinkOp::ChangePubKey(op) => op.tx.clone().into(),
+            ZkLinkOp::ForcedExit(op) => op.tx.clone().into(),
+            ZkLinkOp::OrderMatching(op) => op.tx.clone().into(),
+            ZkLinkOp::ContractMatching(op) => op.tx.clone().into(),
+            ZkLinkOp::Liquidation(op) => op.tx.clone().into(),
+            ZkLinkOp::AutoDeleveraging(op) => op.tx.clone().into(),
+            ZkLinkOp::UpdateGlobalVar(op) => op.tx.clone().into(),
+            ZkLinkOp::Funding(op) => op.tx.clone().into(),
+        }
+    }
+
+    pub fn chunks(&self) -> usize {
+        match self {
+            ZkLinkOp::Deposit(op) => op.chunks(),
+            ZkLinkOp::Withdraw(op) => op.chunks(),
+            ZkLinkOp::Transfer(op) => op.chunks(),
+            ZkLinkOp::FullExit(op) => op.chunks(),
+            ZkLinkOp::ChangePubKey(op) => op.chunks(),
+            ZkLinkOp::ForcedExit(op) => op.chunks(),
+            ZkLinkOp::OrderMatching(op) => op.chunks(),
+            ZkLinkOp::ContractMatching(op) => op.chunks(),
+            ZkLinkOp::Liquidation(op) => op.chunks(),
+            ZkLinkOp::AutoDeleveraging(op) => op.chunks(),
+            ZkLinkOp::UpdateGlobalVar(op) => op.chunks(),
+            ZkLinkOp::Funding(op) => op.chunks(),
+        }
+    }
+
+    pub fn to_pubdata(&self) -> Result<Vec<u8>, TypeError> {
+        match self {
+            ZkLinkOp::Deposit(op) => op.to_pubdata(),
+            ZkLinkOp::Withdraw(op) => op.to_pubdata(),
+            ZkLinkOp::Transfer(op) => op.to_pubdata(),
+            ZkLinkOp::FullExit(op) => op.to_pubdata(),
+            ZkLinkOp::ChangePubKey(op) => op.to_pubdata(),
+            ZkLinkOp::ForcedExit(op) => op.to_pubdata(),
+            ZkLinkOp::OrderMatching(op) => op.to_pubdata(),
+            ZkLinkOp::ContractMatching(op) => op.to_pubdata(),
+            ZkLinkOp::Liquidation(op) => op.to_pubdata(),
+            ZkLinkOp::AutoDeleveraging(op) => op.to_pubdata(),
+            ZkLinkOp::UpdateGlobalVar(op) => op.to_pubdata(),
+            ZkLinkOp::Funding(op) => op.to_pubdata(),
+        }
+    }
+
+    /// Decode the op by the op code at the first byte, the length must be exactly the chunks of the op.
+    pub fn from_pubdata(pubdata: &[u8]) -> Result<Self, TypeError> {
+        let op = match pubdata.first().copied() {
+            Some(DepositOp::OP_CODE) => Self::Deposit(Box::new(DepositOp::from_pubdata(pubdata)?)),
+            Some(WithdrawOp::OP_CODE) => {
+                Self::Withdraw(Box::new(WithdrawOp::from_pubdata(pubdata)?))
+            }
+            Some(TransferOp::OP_CODE) => {
+                Self::Transfer(Box::new(TransferOp::from_pubdata(pubdata)?))
+            }
+            Some(FullExitOp::OP_CODE) => {
+                Self::FullExit(Box::new(FullExitOp::from_pubdata(pubdata)?))
+            }
+            Some(ChangePubKeyOp::OP_CODE) => {
+                Self::ChangePubKey(Box::new(ChangePubKeyOp::from_pubdata(pubdata)?))
+            }
+            Some(ForcedExitOp::OP_CODE) => {
+                Self::ForcedExit(Box::new(ForcedExitOp::from_pubdata(pubdata)?))
+            }
+            Some(OrderMatchingOp::OP_CODE) => {
+                Self::OrderMatching(Box::new(OrderMatchingOp::from_pubdata(pubdata)?))
+            }
+            Some(ContractMatchingOp::OP_CODE) => {
+                Self::ContractMatching(Box::new(ContractMatchingOp::from_pubdata(pubdata)?))
+            }
+            Some(LiquidationOp::OP_CODE) => {
+                Self::Liquidation(Box::new(LiquidationOp::from_pubdata(pubdata)?))
+            }
+            Some(AutoDeleveragingOp::OP_CODE) => {
+                Self::AutoDeleveraging(Box::new(AutoDeleveragingOp::from_pubdata(pubdata)?))
+            }
+            Some(UpdateGlobalVarOp::OP_CODE) => {
+                Self::UpdateGlobalVar(Box::new(UpdateGlobalVarOp::from_pubdata(pubdata)?))
+            }
+            Some(FundingOp::OP_CODE) => Self::Funding(Box::new(FundingOp::from_pubdata(pubdata)?)),
+            op_code => {
+                return Err(TypeError::InvalidPubdata(format!(
+                    "invalid op code {op_code:?}"
+                )))
+            }
+        };
+        Ok(op)
+    }
+
+    /// The number of the chunks of the op whose pubdata starts at `pubdata`.
+    pub fn chunks_of(pubdata: &[u8]) -> Result<usize, TypeError> {
+        match pubdata.first().copied() {
+            Some(NOOP_OP_CODE) => Ok(NOOP_CHUNKS),
+            Some(DepositOp::OP_CODE) => DepositOp::chunks_of(pubdata),
+            Some(WithdrawOp::OP_CODE) => WithdrawOp::chunks_of(pubdata),
+            Some(TransferOp::OP_CODE) => TransferOp::chunks_of(pubdata),
+            Some(FullExitOp::OP_CODE) => FullExitOp::chunks_of(pubdata),
+            Some(ChangePubKeyOp::OP_CODE) => ChangePubKeyOp::chunks_of(pubdata),
+            Some(ForcedExitOp::OP_CODE) => ForcedExitOp::chunks_of(pubdata),
+            Some(OrderMatchingOp::OP_CODE) => OrderMatchingOp::chunks_of(pubdata),
+            Some(ContractMatchingOp::OP_CODE) => ContractMatchingOp::chunks_of(pubdata),
+            Some(LiquidationOp::OP_CODE) => LiquidationOp::chunks_of(pubdata),
+            Some(AutoDeleveragingOp::OP_CODE) => AutoDeleveragingOp::chunks_of(pubdata),
+            Some(UpdateGlobalVarOp::OP_CODE) => UpdateGlobalVarOp::chunks_of(pubdata),
+            Some(FundingOp::OP_CODE) => FundingOp::chunks_of(pubdata),
+            op_code => Err(TypeError::InvalidPubdata(format!(
+                "invalid op code {op_code:?}"
+            ))),
+        }
+    }
+
+    /// Split the pubdata of the block into the ops, the noop chunks are skipped.
+    pub fn from_block_pubdata(pubdata: &[u8]) -> Result<Vec<Self>, TypeError> {
+        if pubdata.len() % CHUNK_BYTES != 0 {
+            return Err(TypeError::InvalidPubdata(format!(
+                "the length {} is not a multiple of the chunk",
+                pubdata.len()
+            )));
+        }
+        let mut ops = Vec::new();
+        let mut offset = 0;
+        while offset < pubdata.len() {
+            let op_bytes = Self::chunks_of(&pubdata[offset..])? * CHUNK_BYTES;
+            let op_pubdata = pubdata.get(offset..offset + op_bytes).ok_or_else(|| {
+                TypeError::InvalidPubdata(format!("the op at {offset} is truncated"))
+            })?;
+            if op_pubdata[0] != NOOP_OP_CODE {
+                ops.push(Self::from_pubdata(op_pubdata)?);
+            }
+            offset += op_bytes;
+        }
+        Ok(ops)
+    }
+}
+
+macro_rules! impl_from_op {
+    ($($op:ident => $variant:ident),*) => {
+        $(impl From<$op> for ZkLinkOp {
+            fn from(op: $op) -> Self {
+                Self::$variant(Box::new(op))
+            }
+        })*
+    };
+}
+
+impl_from_op!(
+    DepositOp => Deposit,
+    WithdrawOp => Withdraw,
+    TransferOp => Transfer,
+    FullExitOp => FullExit,
+    ChangePubKeyOp => ChangePubKey,
+    ForcedExitOp => ForcedExit,
+    OrderMatchingOp => OrderMatching,
+    ContractMatchingOp => ContractMatching,
+    LiquidationOp => Liquidation,
+    AutoDeleveragingOp => AutoDeleveraging,
+    UpdateGlobalVarOp => UpdateGlobalVar,
+    FundingOp => Funding
+);
+
+fn pad_chunks(mut out: Vec<u8>, chunks: usize) -> Result<Vec<u8>, TypeError> {
+    if out.len() > chunks * CHUNK_BYTES {
+        return Err(TypeError::InvalidPubdata(format!(
+            "{} bytes exceed {chunks} chunks",
+            out.len()
+        )));
+    }
+    out.resize(chunks * CHUNK_BYTES, 0);
+    Ok(out)
+}
+
+/// The number of the items prefixed to them in the pubdata, e.g. the makers.
+fn item_num<T: TryFrom<usize>>(num: usize, items: &str) -> Result<T, TypeError> {
+    T::try_from(num).map_err(|_| TypeError::InvalidPubdata(format!("too many {items}: {num}")))
+}
+
+fn amount_bytes(amount: &BigUint) -> Vec<u8> {
+    pad_front(&amount.to_bytes_be(), AMOUNT_BYTES)
+}
+
+fn order_nonce_bytes(nonce: Nonce) -> Vec<u8> {
+    nonce.to_be_bytes()[1..].to_vec()
+}
+
+/// Check the length and the op code, the reader starts after the op code.
+fn pubdata_reader(pubdata: &[u8], op_code: u8, chunks: usize) -> Result<BytesReader, TypeError> {
+    if pubdata.len() != chunks * CHUNK_BYTES {
+        return Err(TypeError::InvalidPubdata(format!(
+            "the length of op {op_code} should be {}, got {}",
+            chunks * CHUNK_BYTES,
+            pubdata.len()
+        )));
+    }
+    if pubdata[0] != op_code {
+        return Err(TypeError::InvalidPubdata(format!(
+            "expected op code {op_code}, got {}",
+            pubdata[0]
+        )));
+    }
+    Ok(BytesReader::new(pubdata, 1, TypeError::InvalidPubdata))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::basic_types::{ChainId, SubAccountId, TokenId};
+    use std::str::FromStr;
+    use zklink_sdk_signers::zklink_signer::pubkey_hash::PubKeyHash;
+
+    const ADDRESS: &str = "0xAFAFf3aD1a0425D792432D9eCD1c3e26Ef2C42E9";
+
+    fn address() -> ZkLinkAddress {
+        ZkLinkAddress::from_str(ADDRESS).unwrap()
+    }
+
+    fn check_round_trip<T: PubdataOp>(op: &T) -> T {
+        let pubdata = op.to_pubdata().unwrap();
+        assert_eq!(pubdata.len(), op.chunks() * CHUNK_BYTES);
+        assert_eq!(T::chunks_of(&pubdata).unwrap(), op.chunks());
+        let decoded = T::from_pubdata(&pubdata).unwrap();
+        assert_eq!(decoded.to_pubdata().unwrap(), pubdata);
+        decoded
+    }
+
+    #[test]
+    fn test_deposit_pubdata() {
+        let op = DepositOp {
+            tx: Deposit {
+                from: address(),
+                to: address(),
+                from_chain_id: ChainId(1),
+                sub_account_id: SubAccountId(2),
+                l1_source_token: TokenId(18),
+                l2_target_token: TokenId(17),
+                amount: BigUint::from(100u32),
+                serial_id: 7,
+                ..Default::default()
+            },
+            account_id: AccountId(10),
+        };
+        let pubdata = op.to_pubdata().unwrap();
+        let mut expected = vec![Deposit::TX_TYPE, 1, 0, 0, 0, 10, 2, 0, 18, 0, 17];
+        expected.extend(100u128.to_be_bytes());
+        expected.extend([0u8; 12]);
+        expected.extend(hex::decode(ADDRESS.trim_start_matches("0x")).unwrap());
+        expected.resize(DEPOSIT_BYTES, 0);
+        assert_eq!(pubdata, expected);
+
+        let decoded = check_round_trip(&op);
+        assert_eq!(decoded.account_id, AccountId(10));
+        assert_eq!(decoded.tx.to, address());
+        assert_eq!(decoded.tx.amount, op.tx.amount);
+
+        // the op code mismatch
+        assert!(WithdrawOp::from_pubdata(&pubdata).is_err());
+        // the length mismatch
+        assert!(DepositOp::from_pubdata(&pubdata[..59]).is_err());
+    }
+
+    #[test]
+    fn test_fixed_ops_pubdata() {
+        let withdraw = WithdrawOp {
+            tx: Withdraw {
+                to_chain_id: ChainId(1),
+                account_id: AccountId(10),
+                sub_account_id: SubAccountId(1),
+                to: address(),
+                l2_source_token: TokenId(18),
+                l1_target_token: TokenId(17),
+                amount: BigUint::from(10000u32),
+                fee: BigUint::from(3u32),
+                nonce: Nonce(1),
+                withdraw_to_l1: 1,
+                withdraw_fee_ratio: 50,
+                ..Default::default()
+            },
+        };
+        let decoded = check_round_trip(&withdraw);
+        assert_eq!(decoded.tx.withdraw_fee_ratio, 50);
+
+        let transfer = TransferOp {
+            tx: Transfer {
+                account_id: AccountId(10),
+                from_sub_account_id: SubAccountId(1),
+                to_sub_account_id: SubAccountId(2),
+                to: address(),
+                token: TokenId(18),
+                amount: BigUint::from(10000u32),
+                fee: BigUint::from(3u32),
+                ..Default::default()
+            },
+            to_account_id: AccountId(11),
+        };
+        let decoded = check_round_trip(&transfer);
+        assert_eq!(decoded.to_account_id, AccountId(11));
+        assert_eq!(decoded.tx.amount, BigUint::from(10000u32));
+
+        let full_exit = FullExitOp {
+            tx: FullExit {
+                to_chain_id: ChainId(1),
+                account_id: AccountId(10),
+                sub_account_id: SubAccountId(1),
+                exit_address: address(),
+                l2_source_token: TokenId(18),
+                l1_target_token: TokenId(17),
+                ..Default::default()
+            },
+            exit_amount: BigUint::from(500u32),
+        };
+        assert_eq!(
+            check_round_trip(&full_exit).exit_amount,
+            BigUint::from(500u32)
+        );
+
+        let change_pubkey = ChangePubKeyOp {
+            tx: ChangePubKey {
+                chain_id: ChainId(1),
+                account_id: AccountId(10),
+                sub_account_id: SubAccountId(1),
+                new_pk_hash: PubKeyHash::from_hex("0xd8d5fb6a6caef06aa3dc2abdcdc240987e5330fe")
+                    .unwrap(),
+                fee_token: TokenId(18),
+                fee: BigUint::from(3u32),
+                nonce: Nonce(2),
+                ..Default::default()
+            },
+            address: address(),
+        };
+        let decoded = check_round_trip(&change_pubkey);
+        assert_eq!(decoded.tx.new_pk_hash, change_pubkey.tx.new_pk_hash);
+
+        let forced_exit = ForcedExitOp {
+            tx: ForcedExit {
+                to_chain_id: ChainId(1),
+                initiator_account_id: AccountId(10),
+                initiator_sub_account_id: SubAccountId(1),
+                initiator_nonce: Nonce(3),
+                target: address(),
+                target_sub_account_id: SubAccountId(1),
+                l2_source_token: TokenId(18),
+                l1_target_token: TokenId(17),
+                exit_amount: BigUint::from(500u32),
+                withdraw_to_l1: 1,
+                ..Default::default()
+            },
+            target_account_id: AccountId(12),
+        };
+        assert_eq!(
+            forced_exit.to_pubdata().unwrap()[FORCED_EXIT_BYTES - 1],
+            0xe9
+        );
+        check_round_trip(&forced_exit);
+
+        let liquidation = LiquidationOp {
+            tx: Liquidation {
+                account_id: AccountId(1),
+                sub_account_id: SubAccountId(1),
+                sub_account_nonce: Nonce(3),
+                liquidation_account_id: AccountId(10),
+                fee_token: TokenId(18),
+                fee: BigUint::from(3u32),
+                ..Default::default()
+            },
+        };
+        check_round_trip(&liquidation);
+
+        let adl = AutoDeleveragingOp {
+            tx: AutoDeleveraging {
+                account_id: AccountId(1),
+                sub_account_id: SubAccountId(1),
+                sub_account_nonce: Nonce(3),
+                adl_account_id: AccountId(10),
+                pair_id: PairId(2),
+                adl_size: BigUint::from(1000u32),
+                adl_price: BigUint::from(1_000_000_000_000_000_000u128),
+                fee_token: TokenId(18),
+                fee: BigUint::from(3u32),
+                ..Default::default()
+            },
+        };
+        assert_eq!(check_round_trip(&adl).tx.adl_price, adl.tx.adl_price);
+    }
+
+    #[test]
+    fn test_variable_ops_pubdata() {
+        let order = |account_id: u32, is_sell: u8| Order {
+            account_id: AccountId(account_id),
+            sub_account_id: SubAccountId(1),
+            slot_id: SlotId(3),
+            nonce: Nonce(4),
+            base_token_id: TokenId(32),
+            quote_token_id: TokenId(1),
+            is_sell,
+            fee_rates: [5, 3],
+            ..Default::default()
+        };
+        let order_matching = OrderMatchingOp {
+            tx: OrderMatching {
+                account_id: AccountId(1),
+                sub_account_id: SubAccountId(1),
+                taker: order(10, 1),
+                maker: order(11, 0),
+                fee: BigUint::from(3u32),
+                fee_token: TokenId(1),
+                expect_base_amount: BigUint::from(100u32),
+                expect_quote_amount: BigUint::from(200u32),
+                ..Default::default()
+            },
+        };
+        let decoded = check_round_trip(&order_matching);
+        assert_eq!(decoded.tx.maker.account_id, AccountId(11));
+        assert_eq!(decoded.tx.maker.is_sell, 0);
+        // is_sell follows the taker part: 10 + 2 * 13 bytes
+        let mut pubdata = order_matching.to_pubdata().unwrap();
+        pubdata[36] = 2;
+        assert!(matches!(
+            OrderMatchingOp::from_pubdata(&pubdata),
+            Err(TypeError::InvalidPubdata(_))
+        ));
+
+        let contract = |account_id: u32| Contract {
+            account_id: AccountId(account_id),
+            sub_account_id: SubAccountId(1),
+            slot_id: SlotId(3),
+            nonce: Nonce(4),
+            pair_id: PairId(1),
+            size: BigUint::from(100u32),
+            direction: 1,
+            fee_rates: [5, 3],
+            ..Default::default()
+        };
+        let contract_matching = ContractMatchingOp {
+            tx: ContractMatching {
+                account_id: AccountId(1),
+                sub_account_id: SubAccountId(1),
+                maker: vec![contract(11), contract(12)],
+                taker: contract(10),
+                fee: BigUint::from(3u32),
+                fee_token: TokenId(1),
+                ..Default::default()
+            },
+        };
+        assert_eq!(contract_matching.chunks(), 4);
+        let decoded = check_round_trip(&contract_matching);
+        assert_eq!(decoded.tx.maker[1].account_id, AccountId(12));
+        let mut too_many_makers = contract_matching.clone();
+        too_many_makers.tx.maker = vec![contract(11); 256];
+        assert!(matches!(
+            too_many_makers.to_pubdata(),
+            Err(TypeError::InvalidPubdata(_))
+        ));
+
+        let funding = FundingOp {
+            tx: Funding {
+                account_id: AccountId(1),
+                sub_account_id: SubAccountId(1),
+                sub_account_nonce: Nonce(3),
+                funding_account_ids: (10..17).map(AccountId).collect(),
+                fee: BigUint::from(3u32),
+                fee_token: TokenId(1),
+                ..Default::default()
+            },
+        };
+        assert_eq!(funding.chunks(), 3);
+        let decoded = check_round_trip(&funding);
+        assert_eq!(
+            decoded.tx.funding_account_ids,
+            funding.tx.funding_account_ids
+        );
+
+        let update_global_var = UpdateGlobalVarOp {
+            tx: UpdateGlobalVar {
+                from_chain_id: ChainId(1),
+                sub_account_id: SubAccountId(1),
+                parameter: Parameter::FundingInfos {
+                    infos: vec![
+                        FundingInfo {
+                            pair_id: PairId(0),
+                            price: BigUint::from(1_000_000_000_000_000_000u128),
+                            funding_rate: -5,
+                        },
+                        FundingInfo {
+                            pair_id: PairId(1),
+                            price: BigUint::from(1_000_000_000_000_000u128),
+                            funding_rate: i16::MAX,
+                        },
+                    ],
+                },
+                serial_id: 100,
+            },
+        };
+        let decoded = check_round_trip(&update_global_var);
+        let Parameter::FundingInfos { infos } = decoded.tx.parameter else {
+            panic!("not funding infos");
+        };
+        assert_eq!(infos[0].funding_rate, -5);
+        assert_eq!(infos[1].funding_rate, i16::MAX);
+
+        let update_global_var = UpdateGlobalVarOp {
+            tx: UpdateGlobalVar {
+                from_chain_id: ChainId(1),
+                sub_account_id: SubAccountId(1),
+                parameter: Parameter::ContractInfo {
+                    pair_id: PairId(1),
+                    symbol: "BTC/USD".to_string(),
+                    initial_margin_rate: 50,
+                    maintenance_margin_rate: 30,
+                },
+                serial_id: 101,
+            },
+        };
+        let decoded = check_round_trip(&update_global_var);
+        let Parameter::ContractInfo { symbol, .. } = decoded.tx.parameter else {
+            panic!("not contract info");
+        };
+        assert_eq!(symbol, "BTC/USD");
+    }
+
+    #[test]
+    fn test_block_pubdata() {
+        let transfer = TransferOp {
+            tx: Transfer {
+                account_id: AccountId(10),
+                to: address(),
+                token: TokenId(18),
+                amount: BigUint::from(10000u32),
+                ..Default::default()
+            },
+            to_account_id: AccountId(11),
+        };
+        let funding = FundingOp {
+            tx: Funding {
+                account_id: AccountId(1),
+                funding_account_ids: vec![AccountId(10), AccountId(11)],
+                fee_token: TokenId(1),
+                ..Default::default()
+            },
+        };
+        let mut pubdata = transfer.to_pubdata().unwrap();
+        pubdata.extend([NOOP_OP_CODE; CHUNK_BYTES]);
+        pubdata.extend(funding.to_pubdata().unwrap());
+        pubdata.extend([NOOP_OP_CODE; CHUNK_BYTES]);
+
+        let ops = ZkLinkOp::from_block_pubdata(&pubdata).unwrap();
+        assert_eq!(ops.len(), 2);
+        assert!(matches!(ops[0], ZkLinkOp::Transfer(_)));
+        assert!(matches!(ops[1], ZkLinkOp::Funding(_)));
+        assert_eq!(
+            ops.iter()
+                .map(|op| op.to_pubdata().unwrap())
+                .collect::<Vec<_>>()
+                .concat(),
+            [
+                transfer.to_pubdata().unwrap(),
+                funding.to_pubdata().unwrap()
+            ]
+            .concat()
+        );
+
+        // the last op is truncated
+        let truncated = &pubdata[..TRANSFER_BYTES + 2 * CHUNK_BYTES];
+        assert!(ZkLinkOp::from_block_pubdata(truncated).is_err());
+    }
+}
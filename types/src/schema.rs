@@ -0,0 +1,29 @@
+//! The JSON Schemas of the basic types serialized as strings, the schemas of the txs are derived.
+use crate::basic_types::tx_hash::TxHash;
+use crate::basic_types::zklink_address::ZkLinkAddress;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use zklink_sdk_utils::schema::string_schema;
+
+impl JsonSchema for ZkLinkAddress {
+    fn schema_name() -> String {
+        "ZkLinkAddress".into()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        string_schema(
+            "The 20 bytes (evm) or 32 bytes (starknet) layer1 address as a 0x-prefixed hex string",
+        )
+    }
+}
+
+impl JsonSchema for TxHash {
+    fn schema_name() -> String {
+        "TxHash".into()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        string_schema("The 32 bytes hash of the zklink tx as a 0x-prefixed hex string")
+    }
+}
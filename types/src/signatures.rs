@@ -9,6 +9,11 @@ use zklink_sdk_signers::starknet_signer::ecdsa_signature::StarkEip712Signature;
 /// corresponding to the account address,
 /// or on-chain signature via EIP-1271.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type", content = "signature")]
 pub enum TxLayer1Signature {
     EthereumSignature(PackedEthSignature),
@@ -17,6 +22,11 @@ pub enum TxLayer1Signature {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TxSignature {
     pub tx: ZkLinkTx,
     pub layer1_signature: Option<TxLayer1Signature>,
@@ -2,11 +2,12 @@ use crate::basic_types::pack::pack_fee_amount;
 use crate::basic_types::{
     AccountId, ChainId, GetBytes, Nonce, SubAccountId, TimeStamp, TokenId, ZkLinkAddress,
 };
+use crate::error::{TxVerifyError, TypeError};
 use crate::params::{SIGNED_CHANGE_PUBKEY_BIT_WIDTH, TX_TYPE_BIT_WIDTH};
 #[cfg(feature = "ffi")]
 use crate::prelude::ChangePubKeyBuilder;
 use crate::tx_type::validator::*;
-use crate::tx_type::{format_units, TxTrait, ZkSignatureTrait};
+use crate::tx_type::{format_units, signed_bytes_reader, TxTrait, ZkSignatureTrait};
 use ethers::utils::keccak256;
 use num::{BigUint, Zero};
 use serde::{Deserialize, Serialize};
@@ -15,8 +16,8 @@ use zklink_sdk_signers::eth_signer::eip712::eip712::{EIP712Domain, TypedData};
 use zklink_sdk_signers::eth_signer::eip712::{BytesM, Uint};
 use zklink_sdk_signers::eth_signer::error::EthSignerError;
 use zklink_sdk_signers::eth_signer::packed_eth_signature::PackedEthSignature;
-use zklink_sdk_signers::eth_signer::EthTypedData;
 use zklink_sdk_signers::eth_signer::H256;
+use zklink_sdk_signers::eth_signer::{Address, EthTypedData};
 use zklink_sdk_signers::zklink_signer::error::ZkSignerError;
 use zklink_sdk_signers::zklink_signer::pk_signer::ZkLinkSigner;
 use zklink_sdk_signers::zklink_signer::pubkey_hash::PubKeyHash;
@@ -24,10 +25,31 @@ use zklink_sdk_signers::zklink_signer::signature::ZkLinkSignature;
 use zklink_sdk_utils::serde::BigUintSerdeAsRadix10Str;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Create2Data {
     pub creator_address: ZkLinkAddress,
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::h256::serialize",
+            deserialize_with = "crate::codec::h256::deserialize"
+        )
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub salt_arg: H256,
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::h256::serialize",
+            deserialize_with = "crate::codec::h256::deserialize"
+        )
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub code_hash: H256,
 }
 
@@ -50,6 +72,11 @@ impl Create2Data {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum ChangePubKeyAuthData {
     Onchain,
@@ -114,6 +141,11 @@ impl ChangePubKeyAuthData {
 ///
 /// Without public key hash set, account is unable to execute any L2 transactions.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ChangePubKey {
     /// According to layer2 chain id , select eip712domain layer1 chain id.
@@ -133,6 +165,13 @@ pub struct ChangePubKey {
     /// Fee for the transaction, need packaging
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "fee_packable")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub fee: BigUint,
     /// Current account nonce of account_id
     #[validate(custom = "nonce_validator")]
@@ -188,6 +227,26 @@ impl ChangePubKey {
         builder.build()
     }
 
+    /// Decode the bytes signed by the zklink signer, the signature and the `eth_auth_data` are
+    /// left as default.
+    pub fn from_signed_bytes(bytes: &[u8]) -> Result<Self, TypeError> {
+        let mut reader = signed_bytes_reader(bytes, Self::TX_TYPE)?;
+        let tx = ChangePubKey {
+            chain_id: reader.chain_id()?,
+            account_id: reader.account_id()?,
+            sub_account_id: reader.sub_account_id()?,
+            new_pk_hash: reader.pub_key_hash()?,
+            fee_token: reader.token_id()?,
+            fee: reader.packed_fee()?,
+            nonce: reader.nonce()?,
+            ts: TimeStamp(reader.u32()?),
+            signature: Default::default(),
+            eth_auth_data: Default::default(),
+        };
+        reader.finish()?;
+        Ok(tx)
+    }
+
     pub fn sign(&mut self, signer: &ZkLinkSigner) -> Result<(), ZkSignerError> {
         let bytes = self.get_bytes();
         self.signature = signer.sign_musig(&bytes)?;
@@ -265,6 +324,72 @@ impl ChangePubKey {
             data_hash,
         })
     }
+
+    /// Recover the layer1 address which authorized the `ChangePubKey`.
+    ///
+    /// For `EthECDSA` it's the signer of the message of the `scheme`, and for `EthCreate2` it's
+    /// the CREATE2 address derived from `new_pk_hash`. The `Onchain` auth is set by a layer1 tx,
+    /// which could not be verified offline.
+    pub fn verify_eth_auth(&self, scheme: &EthECDSAAuthScheme) -> Result<Address, TxVerifyError> {
+        match &self.eth_auth_data {
+            ChangePubKeyAuthData::Onchain => Err(TxVerifyError::UnsupportedLayer1Signature(
+                "Onchain auth".to_string(),
+            )),
+            ChangePubKeyAuthData::EthECDSA { eth_signature } => match scheme {
+                EthECDSAAuthScheme::PersonalMessage => {
+                    let message =
+                        Self::get_eth_sign_msg(&self.new_pk_hash, self.nonce, self.account_id);
+                    eth_signature
+                        .signature_recover_signer(message.as_bytes())
+                        .map_err(|e| TxVerifyError::InvalidLayer1Signature(e.to_string()))
+                }
+                EthECDSAAuthScheme::EIP712 {
+                    layer_one_chain_id,
+                    verifying_contract,
+                } => {
+                    let typed_data = self
+                        .to_eip712_request_payload(*layer_one_chain_id, verifying_contract)
+                        .map_err(|e| TxVerifyError::InvalidLayer1Signature(e.to_string()))?;
+                    eth_signature
+                        .0
+                        .recover(typed_data.data_hash)
+                        .map_err(|e| TxVerifyError::InvalidLayer1Signature(e.to_string()))
+                }
+            },
+            ChangePubKeyAuthData::EthCreate2 { data } => {
+                Ok(Address::from(&data.get_address(&self.new_pk_hash.data)))
+            }
+        }
+    }
+
+    /// Check the auth data is authorized by the account, the `EthECDSA` signature is verified
+    /// with the `scheme` only.
+    pub fn check_eth_auth(
+        &self,
+        scheme: &EthECDSAAuthScheme,
+        account_address: &ZkLinkAddress,
+    ) -> Result<(), TxVerifyError> {
+        let expected = Address::from(account_address);
+        let signer = self.verify_eth_auth(scheme)?;
+        if signer == expected {
+            Ok(())
+        } else {
+            Err(TxVerifyError::ChangePubKeyAuthMismatch(signer, expected))
+        }
+    }
+}
+
+/// The message signed by the `EthECDSA` auth data of `ChangePubKey`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EthECDSAAuthScheme {
+    /// The personal message of [`ChangePubKey::get_eth_sign_msg`], which is signed by the
+    /// signers of this sdk.
+    PersonalMessage,
+    /// The EIP-712 typed data of [`ChangePubKey::to_eip712_request_payload`].
+    EIP712 {
+        layer_one_chain_id: u32,
+        verifying_contract: ZkLinkAddress,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -366,4 +491,103 @@ mod test {
         let tx: Result<ChangePubKey, _> = serde_json::from_str(s);
         assert!(tx.is_ok())
     }
+
+    #[test]
+    fn test_change_pubkey_verify_create2_auth() {
+        let s = r#"
+        {
+   "ethAuthType":"EthCreate2",
+   "chainId":2,
+   "account":"0x4504d5BE8634e3896d42784A5aB89fc41C3d4511",
+   "accountId":22,
+   "subAccountId":0,
+   "fee":"0",
+   "nonce":0,
+   "ts":1698128102,
+   "type":"ChangePubKey",
+   "newPkHash":"0x179d3888ad53fb3ce5e01f548c2e7c50dea076a6",
+   "feeToken":17,
+   "ethAuthData":{
+      "type":"EthCreate2",
+      "creatorAddress":"0x6E253C951A40fAf4032faFbEc19262Cd1531A5F5",
+      "saltArg":"0x0000000000000000000000000000000000000000000000000000000000000000",
+      "codeHash":"0x4f063cd4b2e3a885f61fefb0988cc12487182c4f09ff5de374103f5812f33fe7"
+   },
+   "signature":{
+      "pubKey":"0b3e7d5328193b9cda3d5372cece28be209b4c7c136e734c6261c4fda965e710",
+      "signature":"2f28abf960060dab8d829af5e243b35e2d41545c3354eef4f897a44bca73c629b60d997e343c4a0dd95e68f879c1e09c17fccb93906d458b1a75b7910d89a303"
+   }
+}"#;
+        let tx: ChangePubKey = serde_json::from_str(s).unwrap();
+        let account =
+            ZkLinkAddress::from_hex("0x4504d5BE8634e3896d42784A5aB89fc41C3d4511").unwrap();
+        let scheme = EthECDSAAuthScheme::PersonalMessage;
+        assert_eq!(
+            tx.verify_eth_auth(&scheme).unwrap(),
+            Address::from(&account)
+        );
+        assert!(tx.check_eth_auth(&scheme, &account).is_ok());
+    }
+
+    #[test]
+    fn test_change_pubkey_verify_eth_ecdsa_auth() {
+        let eth_private_key = "be725250b123a39dab5b7579334d5888987c72a58f4508062545fe6e08ca94f4";
+        let eth_signer = EthSigner::try_from(eth_private_key).unwrap();
+        let zk_signer = ZkLinkSigner::new_from_hex_eth_signer(eth_private_key).unwrap();
+        let mut tx = ChangePubKeyBuilder {
+            chain_id: ChainId(1),
+            account_id: AccountId(1),
+            sub_account_id: SubAccountId(1),
+            new_pubkey_hash: zk_signer.public_key().public_key_hash(),
+            fee_token: TokenId(18),
+            fee: BigUint::from(100u32),
+            nonce: Nonce(1),
+            eth_signature: None,
+            timestamp: 1693472232u32.into(),
+        }
+        .build();
+        let account: ZkLinkAddress = eth_signer.get_address().into();
+        let contract =
+            ZkLinkAddress::from_hex("0x0000000000000000000000000000000000000001").unwrap();
+        let eip712 = |layer_one_chain_id| EthECDSAAuthScheme::EIP712 {
+            layer_one_chain_id,
+            verifying_contract: contract.clone(),
+        };
+
+        // the personal message signed by the sdk
+        let message = ChangePubKey::get_eth_sign_msg(&tx.new_pk_hash, tx.nonce, tx.account_id);
+        let eth_signature = eth_signer.sign_message(message.as_bytes()).unwrap();
+        tx.eth_auth_data = ChangePubKeyAuthData::EthECDSA { eth_signature };
+        let scheme = EthECDSAAuthScheme::PersonalMessage;
+        assert_eq!(
+            tx.verify_eth_auth(&scheme).unwrap(),
+            eth_signer.get_address()
+        );
+        assert!(tx.check_eth_auth(&scheme, &account).is_ok());
+        assert!(matches!(
+            tx.check_eth_auth(&eip712(1), &account),
+            Err(TxVerifyError::ChangePubKeyAuthMismatch(_, _))
+        ));
+
+        // the EIP-712 typed data
+        let typed_data = tx.to_eip712_request_payload(1, &contract).unwrap();
+        let eth_signature = eth_signer
+            .sign_hash(typed_data.data_hash.as_bytes())
+            .unwrap();
+        tx.eth_auth_data = ChangePubKeyAuthData::EthECDSA { eth_signature };
+        assert_eq!(
+            tx.verify_eth_auth(&eip712(1)).unwrap(),
+            eth_signer.get_address()
+        );
+        assert!(tx.check_eth_auth(&eip712(1), &account).is_ok());
+        // signed for another layer1 chain
+        assert!(matches!(
+            tx.check_eth_auth(&eip712(2), &account),
+            Err(TxVerifyError::ChangePubKeyAuthMismatch(_, _))
+        ));
+        assert!(matches!(
+            tx.check_eth_auth(&scheme, &account),
+            Err(TxVerifyError::ChangePubKeyAuthMismatch(_, _))
+        ));
+    }
 }
@@ -15,6 +15,11 @@ use zklink_sdk_utils::serde::BigUintSerdeAsRadix10Str;
 
 /// `AutoDeleveraging` transaction was used to auto deleveraging some account.
 #[derive(Default, Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct AutoDeleveraging {
     #[validate(custom = "account_validator")]
@@ -35,13 +40,34 @@ pub struct AutoDeleveraging {
     /// size of adl position
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "adl_size_unpackable")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub adl_size: BigUint,
     /// Price of adl position
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "price_validator")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub adl_price: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "fee_packable")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub fee: BigUint,
     #[validate(custom = "token_validator")]
     pub fee_token: TokenId,
@@ -19,6 +19,11 @@ use zklink_sdk_utils::serde::BigUintSerdeAsRadix10Str;
 
 /// `ContractMatching` transaction was used to match two contract orders.
 #[derive(Default, Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ContractMatching {
     #[validate(custom = "account_validator")]
@@ -33,6 +38,13 @@ pub struct ContractMatching {
 
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "fee_packable")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub fee: BigUint,
     #[validate(custom = "token_validator")]
     pub fee_token: TokenId,
@@ -98,6 +110,11 @@ impl ZkSignatureTrait for ContractMatching {
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Contract {
     #[validate(custom = "account_validator")]
@@ -114,10 +131,24 @@ pub struct Contract {
     /// Position size for open positions
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "amount_packable")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub size: BigUint,
     /// Price of open positions
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "price_validator")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub price: BigUint,
     /// 0 -> short, 1 -> long
     #[validate(custom = "direction_validator")]
@@ -16,6 +16,11 @@ use zklink_sdk_signers::zklink_signer::ZkLinkSignature;
 use zklink_sdk_utils::serde::BigUintSerdeAsRadix10Str;
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct FundingInfo {
     #[validate(custom = "pair_validator")]
@@ -23,6 +28,13 @@ pub struct FundingInfo {
     /// The index price of the specified pair.
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "external_price_validator")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub price: BigUint,
     #[validate(custom = "funding_rate_validator")]
     pub funding_rate: i16,
@@ -50,6 +62,11 @@ impl GetBytes for FundingInfo {
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Funding {
     #[validate(custom = "account_validator")]
@@ -61,6 +78,13 @@ pub struct Funding {
     pub funding_account_ids: Vec<AccountId>,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "fee_packable")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub fee: BigUint,
     #[validate(custom = "token_validator")]
     pub fee_token: TokenId,
@@ -14,6 +14,11 @@ use zklink_sdk_utils::serde::BigUintSerdeAsRadix10Str;
 
 /// `Liquidation` transaction was used to liquidation some burst users.
 #[derive(Default, Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Liquidation {
     #[validate(custom = "account_validator")]
@@ -33,6 +38,13 @@ pub struct Liquidation {
 
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "fee_packable")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub fee: BigUint,
     #[validate(custom = "token_validator")]
     pub fee_token: TokenId,
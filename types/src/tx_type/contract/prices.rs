@@ -12,12 +12,24 @@ use zklink_sdk_utils::serde::BigUintSerdeAsRadix10Str;
 
 /// The current contract price, which is used to handle Liquidation and ADL
 #[derive(Default, Debug, Clone, Serialize, Deserialize, Validate, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ContractPrice {
     #[validate(custom = "pair_validator")]
     pub pair_id: PairId,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "external_price_validator")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub market_price: BigUint,
 }
 
@@ -41,12 +53,24 @@ impl GetBytes for ContractPrice {
 
 /// The current margin token price, used to handle Liquidation and ADL
 #[derive(Default, Debug, Clone, Serialize, Deserialize, Validate, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct SpotPriceInfo {
     #[validate(custom = "token_validator")]
     pub token_id: TokenId,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "external_price_validator")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub price: BigUint,
 }
 
@@ -67,6 +91,11 @@ impl GetBytes for SpotPriceInfo {
 
 /// The current margin token price, used to handle Liquidation and ADL
 #[derive(Debug, Clone, Serialize, Deserialize, Validate, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct OraclePrices {
     /// The current prices of all contracts
@@ -11,6 +11,11 @@ use zklink_sdk_utils::serde::BigUintSerdeAsRadix10Str;
 
 /// `Mapping` transaction performs a move of funds from one zklink account to another.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Deposit {
     /// Layer1 address of the transaction initiator's L1 account.
@@ -32,12 +37,35 @@ pub struct Deposit {
     /// Amount of tokens deposited.
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "amount_unpackable")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub amount: BigUint,
     /// serial id for unique tx_hash
     pub serial_id: u64,
     /// Transaction hash of linea/zksync/starket etc
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::h256::serialize",
+            deserialize_with = "crate::codec::h256::deserialize"
+        )
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub l2_hash: H256,
     /// Transaction hash of ethereum, exist when deposit going from Ethereum bridge to linea/zksync/starket etc
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::option_h256::serialize",
+            deserialize_with = "crate::codec::option_h256::deserialize"
+        )
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
     pub eth_hash: Option<H256>,
 }
 
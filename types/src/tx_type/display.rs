@@ -0,0 +1,800 @@
+//! Render the txs into the structured fields and the plain text, e.g. for the approval UI and
+//! the audit logs.
+//!
+//! The amounts and the prices are formatted with the decimals, the tokens and the contract pairs
+//! are named by the metadata supplied by the caller, see [`DisplayMetadata`].
+use crate::basic_types::{AccountId, PairId, SubAccountId, TokenId, ZkLinkAddress};
+use crate::params::TOKEN_MAX_PRECISION;
+use crate::prelude::{
+    AutoDeleveraging, ChangePubKey, Contract, ContractMatching, Deposit, ForcedExit, FullExit,
+    Funding, Liquidation, OraclePrices, Order, OrderMatching, Parameter, Transfer, UpdateGlobalVar,
+    Withdraw, ZkLinkTx,
+};
+use crate::tx_type::format_units;
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// The symbol and the decimals of the token.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenMetadata {
+    pub symbol: String,
+    /// The decimals of the token amounts in the txs, the amounts in layer2 are
+    /// `TOKEN_MAX_PRECISION` in general.
+    pub decimals: u8,
+}
+
+/// The metadata of the tokens and the contract pairs used to render the txs.
+///
+/// The tokens and the pairs not found are rendered by their ids, and the amounts of the unknown
+/// tokens are formatted with `TOKEN_MAX_PRECISION`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DisplayMetadata {
+    pub tokens: HashMap<TokenId, TokenMetadata>,
+    pub pairs: HashMap<PairId, String>,
+}
+
+impl DisplayMetadata {
+    pub fn with_token(mut self, token_id: TokenId, symbol: &str, decimals: u8) -> Self {
+        let symbol = symbol.to_string();
+        self.tokens
+            .insert(token_id, TokenMetadata { symbol, decimals });
+        self
+    }
+
+    pub fn with_pair(mut self, pair_id: PairId, symbol: &str) -> Self {
+        self.pairs.insert(pair_id, symbol.to_string());
+        self
+    }
+
+    pub fn token_symbol(&self, token_id: TokenId) -> String {
+        match self.tokens.get(&token_id) {
+            Some(token) => token.symbol.clone(),
+            None => format!("token#{token_id}"),
+        }
+    }
+
+    pub fn pair_symbol(&self, pair_id: PairId) -> String {
+        match self.pairs.get(&pair_id) {
+            Some(symbol) => symbol.clone(),
+            None => format!("pair#{pair_id}"),
+        }
+    }
+
+    fn amount(&self, amount: &BigUint, token_id: TokenId) -> FieldValue {
+        let decimals = self
+            .tokens
+            .get(&token_id)
+            .map_or(TOKEN_MAX_PRECISION, |token| token.decimals);
+        FieldValue::Amount {
+            amount: format_units(amount, decimals),
+            token: self.token_symbol(token_id),
+        }
+    }
+}
+
+/// The value of the field, rendered by `Display` in the plain text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum FieldValue {
+    /// The amount formatted with the decimals of the token.
+    Amount {
+        amount: String,
+        token: String,
+    },
+    /// The price of the token or the contract pair, the prices are always formatted with
+    /// `TOKEN_MAX_PRECISION` decimals.
+    Price {
+        price: String,
+        symbol: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Account {
+        account_id: AccountId,
+        sub_account_id: SubAccountId,
+    },
+    Address {
+        address: ZkLinkAddress,
+    },
+    Text {
+        text: String,
+    },
+}
+
+impl FieldValue {
+    fn price(price: &BigUint, symbol: String) -> Self {
+        FieldValue::Price {
+            price: format_units(price, TOKEN_MAX_PRECISION),
+            symbol,
+        }
+    }
+
+    fn account(account_id: AccountId, sub_account_id: SubAccountId) -> Self {
+        FieldValue::Account {
+            account_id,
+            sub_account_id,
+        }
+    }
+
+    fn address(address: &ZkLinkAddress) -> Self {
+        FieldValue::Address {
+            address: address.clone(),
+        }
+    }
+
+    fn text(text: impl ToString) -> Self {
+        FieldValue::Text {
+            text: text.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValue::Amount { amount, token } => write!(f, "{amount} {token}"),
+            FieldValue::Price { price, symbol } => write!(f, "{price} ({symbol})"),
+            FieldValue::Account {
+                account_id,
+                sub_account_id,
+            } => write!(f, "account {account_id}, sub account {sub_account_id}"),
+            FieldValue::Address { address } => write!(f, "{}", address.to_string()),
+            FieldValue::Text { text } => write!(f, "{text}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxField {
+    pub name: String,
+    pub value: FieldValue,
+}
+
+/// The structured description of the tx, rendered by `Display` as the action followed by a
+/// line of `name: value` for every field.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxDescription {
+    pub action: String,
+    pub fields: Vec<TxField>,
+}
+
+impl TxDescription {
+    pub fn new(action: &str) -> Self {
+        TxDescription {
+            action: action.to_string(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Get the value of the first field named `name`.
+    pub fn field(&self, name: &str) -> Option<&FieldValue> {
+        self.fields
+            .iter()
+            .find(|field| field.name == name)
+            .map(|field| &field.value)
+    }
+
+    fn push(&mut self, name: impl ToString, value: FieldValue) {
+        self.fields.push(TxField {
+            name: name.to_string(),
+            value,
+        });
+    }
+
+    fn push_oracle_prices(&mut self, prices: &OraclePrices, metadata: &DisplayMetadata) {
+        for price in &prices.contract_prices {
+            let symbol = metadata.pair_symbol(price.pair_id);
+            self.push(
+                "contract price",
+                FieldValue::price(&price.market_price, symbol),
+            );
+        }
+        for price in &prices.margin_prices {
+            let symbol = metadata.token_symbol(price.token_id);
+            self.push("margin price", FieldValue::price(&price.price, symbol));
+        }
+    }
+
+    fn push_order(&mut self, prefix: &str, order: &Order, metadata: &DisplayMetadata) {
+        let pair = format!(
+            "{}/{}",
+            metadata.token_symbol(order.base_token_id),
+            metadata.token_symbol(order.quote_token_id)
+        );
+        let side = if order.is_sell == 1 { "sell" } else { "buy" };
+        self.push(
+            format!("{prefix}account"),
+            FieldValue::account(order.account_id, order.sub_account_id),
+        );
+        self.push(format!("{prefix}side"), FieldValue::text(side));
+        self.push(
+            format!("{prefix}amount"),
+            metadata.amount(&order.amount, order.base_token_id),
+        );
+        self.push(
+            format!("{prefix}price"),
+            FieldValue::price(&order.price, pair),
+        );
+        self.push(
+            format!("{prefix}fee rates"),
+            FieldValue::text(fee_rates(order.fee_rates)),
+        );
+        self.push(format!("{prefix}slot"), FieldValue::text(order.slot_id));
+        self.push(format!("{prefix}nonce"), FieldValue::text(order.nonce));
+    }
+
+    fn push_contract(&mut self, prefix: &str, contract: &Contract, metadata: &DisplayMetadata) {
+        let pair = metadata.pair_symbol(contract.pair_id);
+        let direction = if contract.direction == 1 {
+            "long"
+        } else {
+            "short"
+        };
+        self.push(
+            format!("{prefix}account"),
+            FieldValue::account(contract.account_id, contract.sub_account_id),
+        );
+        self.push(format!("{prefix}direction"), FieldValue::text(direction));
+        self.push(
+            format!("{prefix}size"),
+            FieldValue::text(format_units(&contract.size, TOKEN_MAX_PRECISION)),
+        );
+        self.push(
+            format!("{prefix}price"),
+            FieldValue::price(&contract.price, pair),
+        );
+        self.push(
+            format!("{prefix}fee rates"),
+            FieldValue::text(fee_rates(contract.fee_rates)),
+        );
+        self.push(format!("{prefix}slot"), FieldValue::text(contract.slot_id));
+        self.push(format!("{prefix}nonce"), FieldValue::text(contract.nonce));
+    }
+}
+
+impl fmt::Display for TxDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.action)?;
+        for field in &self.fields {
+            write!(f, "\n{}: {}", field.name, field.value)?;
+        }
+        Ok(())
+    }
+}
+
+/// The maker and taker fee rates, 100 means 1%.
+fn fee_rates(fee_rates: [u8; 2]) -> String {
+    format!(
+        "maker {}%, taker {}%",
+        format_units(fee_rates[0], 2),
+        format_units(fee_rates[1], 2)
+    )
+}
+
+/// Render the tx (or the order) in the same way for every type.
+pub trait TxDisplay {
+    fn describe(&self, metadata: &DisplayMetadata) -> TxDescription;
+
+    fn display_text(&self, metadata: &DisplayMetadata) -> String {
+        self.describe(metadata).to_string()
+    }
+}
+
+impl TxDisplay for Deposit {
+    fn describe(&self, metadata: &DisplayMetadata) -> TxDescription {
+        let mut desc = TxDescription::new("Deposit");
+        desc.push("from", FieldValue::address(&self.from));
+        desc.push("from chain", FieldValue::text(self.from_chain_id));
+        desc.push("to", FieldValue::address(&self.to));
+        desc.push("to sub account", FieldValue::text(self.sub_account_id));
+        desc.push(
+            "amount",
+            metadata.amount(&self.amount, self.l2_target_token),
+        );
+        desc.push(
+            "layer1 token",
+            FieldValue::text(metadata.token_symbol(self.l1_source_token)),
+        );
+        desc
+    }
+}
+
+impl TxDisplay for Withdraw {
+    fn describe(&self, metadata: &DisplayMetadata) -> TxDescription {
+        let mut desc = TxDescription::new("Withdraw");
+        desc.push(
+            "from",
+            FieldValue::account(self.account_id, self.sub_account_id),
+        );
+        desc.push("to", FieldValue::address(&self.to));
+        desc.push("to chain", FieldValue::text(self.to_chain_id));
+        desc.push(
+            "amount",
+            metadata.amount(&self.amount, self.l2_source_token),
+        );
+        desc.push(
+            "layer1 token",
+            FieldValue::text(metadata.token_symbol(self.l1_target_token)),
+        );
+        desc.push("fee", metadata.amount(&self.fee, self.l2_source_token));
+        if self.withdraw_to_l1 == 1 {
+            desc.push("withdraw to", FieldValue::text("layer1"));
+        }
+        desc.push(
+            "withdraw fee ratio",
+            FieldValue::text(format!("{}%", format_units(self.withdraw_fee_ratio, 2))),
+        );
+        desc.push("nonce", FieldValue::text(self.nonce));
+        desc
+    }
+}
+
+impl TxDisplay for Transfer {
+    fn describe(&self, metadata: &DisplayMetadata) -> TxDescription {
+        let mut desc = TxDescription::new("Transfer");
+        desc.push(
+            "from",
+            FieldValue::account(self.account_id, self.from_sub_account_id),
+        );
+        desc.push("to", FieldValue::address(&self.to));
+        desc.push("to sub account", FieldValue::text(self.to_sub_account_id));
+        desc.push("amount", metadata.amount(&self.amount, self.token));
+        desc.push("fee", metadata.amount(&self.fee, self.token));
+        desc.push("nonce", FieldValue::text(self.nonce));
+        desc
+    }
+}
+
+impl TxDisplay for FullExit {
+    fn describe(&self, metadata: &DisplayMetadata) -> TxDescription {
+        let mut desc = TxDescription::new("Full exit");
+        desc.push(
+            "account",
+            FieldValue::account(self.account_id, self.sub_account_id),
+        );
+        desc.push("to", FieldValue::address(&self.exit_address));
+        desc.push("to chain", FieldValue::text(self.to_chain_id));
+        desc.push(
+            "token",
+            FieldValue::text(metadata.token_symbol(self.l2_source_token)),
+        );
+        desc.push(
+            "layer1 token",
+            FieldValue::text(metadata.token_symbol(self.l1_target_token)),
+        );
+        desc.push_oracle_prices(&self.oracle_prices, metadata);
+        desc
+    }
+}
+
+impl TxDisplay for ChangePubKey {
+    fn describe(&self, metadata: &DisplayMetadata) -> TxDescription {
+        let mut desc = TxDescription::new("Change pubkey");
+        desc.push(
+            "account",
+            FieldValue::account(self.account_id, self.sub_account_id),
+        );
+        desc.push("chain", FieldValue::text(self.chain_id));
+        desc.push(
+            "new pubkey hash",
+            FieldValue::text(self.new_pk_hash.as_hex()),
+        );
+        desc.push("fee", metadata.amount(&self.fee, self.fee_token));
+        desc.push("nonce", FieldValue::text(self.nonce));
+        desc
+    }
+}
+
+impl TxDisplay for ForcedExit {
+    fn describe(&self, metadata: &DisplayMetadata) -> TxDescription {
+        let mut desc = TxDescription::new("Forced exit");
+        desc.push(
+            "initiator",
+            FieldValue::account(self.initiator_account_id, self.initiator_sub_account_id),
+        );
+        desc.push("target", FieldValue::address(&self.target));
+        desc.push(
+            "target sub account",
+            FieldValue::text(self.target_sub_account_id),
+        );
+        desc.push("to chain", FieldValue::text(self.to_chain_id));
+        desc.push(
+            "amount",
+            metadata.amount(&self.exit_amount, self.l2_source_token),
+        );
+        desc.push(
+            "layer1 token",
+            FieldValue::text(metadata.token_symbol(self.l1_target_token)),
+        );
+        if self.withdraw_to_l1 == 1 {
+            desc.push("withdraw to", FieldValue::text("layer1"));
+        }
+        desc.push("nonce", FieldValue::text(self.initiator_nonce));
+        desc
+    }
+}
+
+impl TxDisplay for Order {
+    fn describe(&self, metadata: &DisplayMetadata) -> TxDescription {
+        let mut desc = TxDescription::new("Order");
+        desc.push_order("", self, metadata);
+        desc
+    }
+}
+
+impl TxDisplay for OrderMatching {
+    fn describe(&self, metadata: &DisplayMetadata) -> TxDescription {
+        let mut desc = TxDescription::new("Order matching");
+        desc.push(
+            "submitter",
+            FieldValue::account(self.account_id, self.sub_account_id),
+        );
+        desc.push_order("maker ", &self.maker, metadata);
+        desc.push_order("taker ", &self.taker, metadata);
+        desc.push(
+            "expect base amount",
+            metadata.amount(&self.expect_base_amount, self.maker.base_token_id),
+        );
+        desc.push(
+            "expect quote amount",
+            metadata.amount(&self.expect_quote_amount, self.maker.quote_token_id),
+        );
+        desc.push("fee", metadata.amount(&self.fee, self.fee_token));
+        desc.push_oracle_prices(&self.oracle_prices, metadata);
+        desc
+    }
+}
+
+impl TxDisplay for Contract {
+    fn describe(&self, metadata: &DisplayMetadata) -> TxDescription {
+        let mut desc = TxDescription::new("Contract");
+        desc.push_contract("", self, metadata);
+        desc
+    }
+}
+
+impl TxDisplay for ContractMatching {
+    fn describe(&self, metadata: &DisplayMetadata) -> TxDescription {
+        let mut desc = TxDescription::new("Contract matching");
+        desc.push(
+            "submitter",
+            FieldValue::account(self.account_id, self.sub_account_id),
+        );
+        desc.push_contract("taker ", &self.taker, metadata);
+        for (i, maker) in self.maker.iter().enumerate() {
+            desc.push_contract(&format!("maker{i} "), maker, metadata);
+        }
+        desc.push("fee", metadata.amount(&self.fee, self.fee_token));
+        desc.push_oracle_prices(&self.oracle_prices, metadata);
+        desc
+    }
+}
+
+impl TxDisplay for Liquidation {
+    fn describe(&self, metadata: &DisplayMetadata) -> TxDescription {
+        let mut desc = TxDescription::new("Liquidation");
+        desc.push(
+            "liquidator",
+            FieldValue::account(self.account_id, self.sub_account_id),
+        );
+        desc.push(
+            "liquidation account",
+            FieldValue::text(self.liquidation_account_id),
+        );
+        desc.push("fee", metadata.amount(&self.fee, self.fee_token));
+        desc.push("nonce", FieldValue::text(self.sub_account_nonce));
+        desc.push_oracle_prices(&self.oracle_prices, metadata);
+        desc
+    }
+}
+
+impl TxDisplay for AutoDeleveraging {
+    fn describe(&self, metadata: &DisplayMetadata) -> TxDescription {
+        let mut desc = TxDescription::new("Auto deleveraging");
+        desc.push(
+            "submitter",
+            FieldValue::account(self.account_id, self.sub_account_id),
+        );
+        desc.push("adl account", FieldValue::text(self.adl_account_id));
+        desc.push(
+            "adl size",
+            FieldValue::text(format_units(&self.adl_size, TOKEN_MAX_PRECISION)),
+        );
+        desc.push(
+            "adl price",
+            FieldValue::price(&self.adl_price, metadata.pair_symbol(self.pair_id)),
+        );
+        desc.push("fee", metadata.amount(&self.fee, self.fee_token));
+        desc.push("nonce", FieldValue::text(self.sub_account_nonce));
+        desc.push_oracle_prices(&self.oracle_prices, metadata);
+        desc
+    }
+}
+
+impl TxDisplay for Funding {
+    fn describe(&self, metadata: &DisplayMetadata) -> TxDescription {
+        let mut desc = TxDescription::new("Funding");
+        desc.push(
+            "submitter",
+            FieldValue::account(self.account_id, self.sub_account_id),
+        );
+        let accounts = self
+            .funding_account_ids
+            .iter()
+            .map(|account_id| account_id.to_string())
+            .collect::<Vec<_>>();
+        desc.push("funding accounts", FieldValue::text(accounts.join(", ")));
+        desc.push("fee", metadata.amount(&self.fee, self.fee_token));
+        desc.push("nonce", FieldValue::text(self.sub_account_nonce));
+        desc
+    }
+}
+
+impl TxDisplay for UpdateGlobalVar {
+    fn describe(&self, metadata: &DisplayMetadata) -> TxDescription {
+        let mut desc = TxDescription::new("Update global var");
+        desc.push("from chain", FieldValue::text(self.from_chain_id));
+        match &self.parameter {
+            Parameter::FeeAccount { account_id } => {
+                desc.push("fee account", FieldValue::text(account_id));
+            }
+            Parameter::InsuranceFundAccount { account_id } => {
+                desc.push("insurance fund account", FieldValue::text(account_id));
+            }
+            Parameter::MarginInfo {
+                margin_id,
+                symbol,
+                token_id,
+                ratio,
+            } => {
+                desc.push("margin", FieldValue::text(margin_id));
+                desc.push("margin symbol", FieldValue::text(symbol));
+                desc.push(
+                    "margin token",
+                    FieldValue::text(metadata.token_symbol(*token_id)),
+                );
+                desc.push("margin ratio", FieldValue::text(format!("{ratio}%")));
+            }
+            Parameter::FundingInfos { infos } => {
+                for info in infos {
+                    let symbol = metadata.pair_symbol(info.pair_id);
+                    desc.push(
+                        "index price",
+                        FieldValue::price(&info.price, symbol.clone()),
+                    );
+                    desc.push(
+                        format!("{symbol} funding rate"),
+                        FieldValue::text(info.funding_rate),
+                    );
+                }
+            }
+            Parameter::ContractInfo {
+                pair_id,
+                symbol,
+                initial_margin_rate,
+                maintenance_margin_rate,
+            } => {
+                desc.push("pair", FieldValue::text(pair_id));
+                desc.push("pair symbol", FieldValue::text(symbol));
+                desc.push("initial margin rate", FieldValue::text(initial_margin_rate));
+                desc.push(
+                    "maintenance margin rate",
+                    FieldValue::text(maintenance_margin_rate),
+                );
+            }
+        }
+        desc
+    }
+}
+
+impl TxDisplay for ZkLinkTx {
+    fn describe(&self, metadata: &DisplayMetadata) -> TxDescription {
+        match self {
+            ZkLinkTx::Deposit(tx) => tx.describe(metadata),
+            ZkLinkTx::Withdraw(tx) => tx.describe(metadata),
+            ZkLinkTx::Transfer(tx) => tx.describe(metadata),
+            ZkLinkTx::FullExit(tx) => tx.describe(metadata),
+            ZkLinkTx::ChangePubKey(tx) => tx.describe(metadata),
+            ZkLinkTx::ForcedExit(tx) => tx.describe(metadata),
+            ZkLinkTx::OrderMatching(tx) => tx.describe(metadata),
+            ZkLinkTx::ContractMatching(tx) => tx.describe(metadata),
+            ZkLinkTx::Liquidation(tx) => tx.describe(metadata),
+            ZkLinkTx::AutoDeleveraging(tx) => tx.describe(metadata),
+            ZkLinkTx::Funding(tx) => tx.describe(metadata),
+            ZkLinkTx::UpdateGlobalVar(tx) => tx.describe(metadata),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::basic_types::{ChainId, Nonce, SlotId};
+    use crate::prelude::{ContractPrice, FundingInfo, SpotPriceInfo};
+    use std::str::FromStr;
+
+    fn metadata() -> DisplayMetadata {
+        DisplayMetadata::default()
+            .with_token(TokenId(17), "USDC", TOKEN_MAX_PRECISION)
+            .with_token(TokenId(18), "ETH", TOKEN_MAX_PRECISION)
+            .with_pair(PairId(1), "ETH/USDC")
+    }
+
+    #[test]
+    fn test_display_transfer() {
+        let tx = Transfer {
+            account_id: AccountId(10),
+            from_sub_account_id: SubAccountId(1),
+            to_sub_account_id: SubAccountId(2),
+            to: ZkLinkAddress::from_str("0xAFAFf3aD1a0425D792432D9eCD1c3e26Ef2C42E9").unwrap(),
+            token: TokenId(18),
+            amount: BigUint::from(1500000000000000000u64),
+            fee: BigUint::from(1000000000000000u64),
+            nonce: Nonce(1),
+            ..Default::default()
+        };
+        let tx = ZkLinkTx::from(tx);
+        let desc = tx.describe(&metadata());
+        assert_eq!(desc.action, "Transfer");
+        assert_eq!(
+            desc.field("amount"),
+            Some(&FieldValue::Amount {
+                amount: "1.5".into(),
+                token: "ETH".into()
+            })
+        );
+        assert_eq!(
+            tx.display_text(&metadata()),
+            "Transfer\n\
+            from: account 10, sub account 1\n\
+            to: 0xafaff3ad1a0425d792432d9ecd1c3e26ef2c42e9\n\
+            to sub account: 2\n\
+            amount: 1.5 ETH\n\
+            fee: 0.001 ETH\n\
+            nonce: 1"
+        );
+
+        // the unknown tokens are rendered by the ids
+        let desc = tx.describe(&DisplayMetadata::default());
+        assert_eq!(desc.field("fee").unwrap().to_string(), "0.001 token#18");
+    }
+
+    #[test]
+    fn test_display_contract_txs() {
+        let oracle_prices = OraclePrices {
+            contract_prices: vec![ContractPrice {
+                pair_id: PairId(1),
+                market_price: BigUint::from(2000u32) * BigUint::from(10u64.pow(18)),
+            }],
+            margin_prices: vec![SpotPriceInfo {
+                token_id: TokenId(17),
+                price: BigUint::from(10u64.pow(18)),
+            }],
+        };
+        let tx = ZkLinkTx::from(Liquidation {
+            account_id: AccountId(1),
+            sub_account_id: SubAccountId(1),
+            sub_account_nonce: Nonce(3),
+            oracle_prices,
+            liquidation_account_id: AccountId(20),
+            fee: BigUint::from(0u32),
+            fee_token: TokenId(17),
+            ..Default::default()
+        });
+        let desc = tx.describe(&metadata());
+        assert_eq!(desc.action, "Liquidation");
+        assert_eq!(
+            desc.field("contract price").unwrap().to_string(),
+            "2000.0 (ETH/USDC)"
+        );
+        assert_eq!(
+            desc.field("margin price").unwrap().to_string(),
+            "1.0 (USDC)"
+        );
+
+        let contract = |account_id: u32, direction: u8| Contract {
+            account_id: AccountId(account_id),
+            sub_account_id: SubAccountId(1),
+            slot_id: SlotId(3),
+            nonce: Nonce(2),
+            pair_id: PairId(1),
+            size: BigUint::from(15u32) * BigUint::from(10u64.pow(17)),
+            price: BigUint::from(2000u32) * BigUint::from(10u64.pow(18)),
+            direction,
+            fee_rates: [5, 10],
+            ..Default::default()
+        };
+        let tx = ZkLinkTx::from(ContractMatching {
+            account_id: AccountId(1),
+            sub_account_id: SubAccountId(1),
+            taker: contract(10, 1),
+            maker: vec![contract(11, 0), contract(12, 0)],
+            fee: BigUint::from(0u32),
+            fee_token: TokenId(17),
+            ..Default::default()
+        });
+        let desc = tx.describe(&metadata());
+        assert_eq!(desc.action, "Contract matching");
+        assert_eq!(
+            desc.field("taker account").unwrap().to_string(),
+            "account 10, sub account 1"
+        );
+        assert_eq!(desc.field("taker direction").unwrap().to_string(), "long");
+        assert_eq!(desc.field("taker size").unwrap().to_string(), "1.5");
+        assert_eq!(
+            desc.field("taker price").unwrap().to_string(),
+            "2000.0 (ETH/USDC)"
+        );
+        assert_eq!(
+            desc.field("taker fee rates").unwrap().to_string(),
+            "maker 0.05%, taker 0.1%"
+        );
+        assert_eq!(
+            desc.field("maker0 account").unwrap().to_string(),
+            "account 11, sub account 1"
+        );
+        assert_eq!(desc.field("maker0 direction").unwrap().to_string(), "short");
+        assert_eq!(
+            desc.field("maker1 account").unwrap().to_string(),
+            "account 12, sub account 1"
+        );
+        assert_eq!(desc.field("maker1 slot").unwrap().to_string(), "3");
+        assert!(desc.field("maker2 account").is_none());
+
+        let tx = ZkLinkTx::from(AutoDeleveraging {
+            account_id: AccountId(1),
+            sub_account_id: SubAccountId(1),
+            adl_account_id: AccountId(20),
+            pair_id: PairId(1),
+            adl_size: BigUint::from(5u32) * BigUint::from(10u64.pow(17)),
+            adl_price: BigUint::from(1999u32) * BigUint::from(10u64.pow(18)),
+            fee: BigUint::from(0u32),
+            fee_token: TokenId(17),
+            ..Default::default()
+        });
+        let desc = tx.describe(&metadata());
+        assert_eq!(desc.field("adl account").unwrap().to_string(), "20");
+        assert_eq!(desc.field("adl size").unwrap().to_string(), "0.5");
+        assert_eq!(
+            desc.field("adl price"),
+            Some(&FieldValue::Price {
+                price: "1999.0".into(),
+                symbol: "ETH/USDC".into()
+            })
+        );
+
+        let tx = ZkLinkTx::from(UpdateGlobalVar {
+            from_chain_id: ChainId(1),
+            sub_account_id: SubAccountId(1),
+            parameter: Parameter::FundingInfos {
+                infos: vec![FundingInfo {
+                    pair_id: PairId(1),
+                    price: BigUint::from(2001u32) * BigUint::from(10u64.pow(18)),
+                    funding_rate: -3,
+                }],
+            },
+            serial_id: 101,
+        });
+        assert_eq!(
+            tx.display_text(&metadata()),
+            "Update global var\n\
+            from chain: 1\n\
+            index price: 2001.0 (ETH/USDC)\n\
+            ETH/USDC funding rate: -3"
+        );
+
+        let tx = ZkLinkTx::from(UpdateGlobalVar {
+            from_chain_id: ChainId(1),
+            sub_account_id: SubAccountId(1),
+            parameter: Parameter::FeeAccount {
+                account_id: AccountId(2),
+            },
+            serial_id: 100,
+        });
+        assert_eq!(
+            tx.display_text(&metadata()),
+            "Update global var\nfrom chain: 1\nfee account: 2"
+        );
+    }
+}
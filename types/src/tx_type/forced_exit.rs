@@ -5,11 +5,12 @@ use zklink_sdk_utils::serde::BigUintSerdeAsRadix10Str;
 use crate::basic_types::{
     AccountId, ChainId, GetBytes, Nonce, SubAccountId, TimeStamp, TokenId, ZkLinkAddress,
 };
+use crate::error::TypeError;
 use crate::params::{SIGNED_FORCED_EXIT_BIT_WIDTH, TX_TYPE_BIT_WIDTH};
 #[cfg(feature = "ffi")]
 use crate::prelude::ForcedExitBuilder;
 use crate::tx_type::validator::*;
-use crate::tx_type::{TxTrait, ZkSignatureTrait};
+use crate::tx_type::{signed_bytes_reader, TxTrait, ZkSignatureTrait};
 use serde::{Deserialize, Serialize};
 use zklink_sdk_signers::zklink_signer::signature::ZkLinkSignature;
 
@@ -24,6 +25,11 @@ use zklink_sdk_signers::zklink_signer::signature::ZkLinkSignature;
 /// cannot prove its identity in L2 (e.g. it's an existing smart contract),
 /// so the funds won't get "locked" in L2.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ForcedExit {
     /// The chain ID of receiver of the transaction.
@@ -54,6 +60,13 @@ pub struct ForcedExit {
     /// Amount of funds to exit, layer1 can not unpack it, do not packaging
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "amount_unpackable")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub exit_amount: BigUint,
     /// whether withdraw to layer1.
     #[validate(custom = "boolean_validator")]
@@ -71,6 +84,27 @@ impl ForcedExit {
     pub fn new(builder: ForcedExitBuilder) -> Self {
         builder.build()
     }
+
+    /// Decode the bytes signed by the zklink signer, the signature is left as default.
+    pub fn from_signed_bytes(bytes: &[u8]) -> Result<Self, TypeError> {
+        let mut reader = signed_bytes_reader(bytes, Self::TX_TYPE)?;
+        let tx = ForcedExit {
+            to_chain_id: reader.chain_id()?,
+            initiator_account_id: reader.account_id()?,
+            initiator_sub_account_id: reader.sub_account_id()?,
+            target: reader.address()?,
+            target_sub_account_id: reader.sub_account_id()?,
+            l2_source_token: reader.token_id()?,
+            l1_target_token: reader.token_id()?,
+            initiator_nonce: reader.nonce()?,
+            exit_amount: reader.amount()?,
+            withdraw_to_l1: reader.u8()?,
+            ts: TimeStamp(reader.u32()?),
+            signature: Default::default(),
+        };
+        reader.finish()?;
+        Ok(tx)
+    }
 }
 
 impl GetBytes for ForcedExit {
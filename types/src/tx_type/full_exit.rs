@@ -10,6 +10,11 @@ use zklink_sdk_signers::eth_signer::H256;
 
 /// `Mapping` transaction performs a move of funds from one zklink account to another.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct FullExit {
     #[validate(custom = "chain_id_validator")]
@@ -30,6 +35,14 @@ pub struct FullExit {
     pub oracle_prices: OraclePrices,
     pub serial_id: u64,
     /// Transaction hash of linea/zksync/starket etc
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::h256::serialize",
+            deserialize_with = "crate::codec::h256::deserialize"
+        )
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "String"))]
     pub l2_hash: H256,
 }
 
@@ -1,4 +1,6 @@
+use crate::basic_types::bytes_reader::BytesReader;
 use crate::basic_types::{GetBytes, ZkLinkAddress};
+use crate::error::TypeError;
 #[cfg(feature = "ffi")]
 use crate::prelude::ZkLinkTx;
 use crate::tx_type::change_pubkey::ChangePubKey;
@@ -25,6 +27,7 @@ pub mod validator;
 pub mod change_pubkey;
 pub mod contract;
 pub mod deposit;
+pub mod display;
 pub mod exit_info;
 pub mod forced_exit;
 pub mod full_exit;
@@ -55,6 +58,17 @@ impl OrderMatching {
     pub const TX_TYPE: u8 = 0x08;
 }
 
+/// Read the signed bytes(see `GetBytes`) that start with `tx_type`, e.g. the `TX_TYPE` of the
+/// tx or the `MSG_TYPE` of the order.
+pub(crate) fn signed_bytes_reader(bytes: &[u8], tx_type: u8) -> Result<BytesReader, TypeError> {
+    match bytes.first() {
+        Some(t) if *t == tx_type => Ok(BytesReader::new(bytes, 1, TypeError::InvalidSignedBytes)),
+        _ => Err(TypeError::InvalidSignedBytes(format!(
+            "expected the bytes of type {tx_type}"
+        ))),
+    }
+}
+
 /// Construct the first part of the message that should be signed by Ethereum key.
 /// The pattern is as follows:
 ///
@@ -4,13 +4,14 @@ use crate::basic_types::params::{
     ORDERS_BYTES, PRICE_BIT_WIDTH, SIGNED_ORDER_MATCHING_BIT_WIDTH, TX_TYPE_BIT_WIDTH,
 };
 use crate::basic_types::{AccountId, GetBytes, Nonce, SlotId, SubAccountId, TokenId};
+use crate::error::TypeError;
 use crate::params::{SIGNED_ORDER_BIT_WIDTH, TOKEN_MAX_PRECISION};
 use crate::prelude::OraclePrices;
 #[cfg(feature = "ffi")]
 use crate::prelude::OrderMatchingBuilder;
 use crate::signatures::TxLayer1Signature;
 use crate::tx_type::validator::*;
-use crate::tx_type::{format_units, TxTrait, ZkSignatureTrait};
+use crate::tx_type::{format_units, signed_bytes_reader, TxTrait, ZkSignatureTrait};
 use num::{BigUint, One, ToPrimitive, Zero};
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "ffi")]
@@ -24,6 +25,11 @@ use zklink_sdk_signers::zklink_signer::utils::rescue_hash_orders;
 use zklink_sdk_utils::serde::BigUintSerdeAsRadix10Str;
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Order {
     #[validate(custom = "account_validator")]
@@ -41,11 +47,25 @@ pub struct Order {
     /// The amount of base token buy or sell
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "amount_packable")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub amount: BigUint,
 
     /// How much a quote token, accuracy will be improved
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "price_validator")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub price: BigUint,
     /// Order type, 0: buy, 1: sell
     #[validate(custom = "boolean_validator")]
@@ -93,6 +113,27 @@ impl Order {
         }
     }
 
+    /// Decode the bytes signed by the zklink signer, the signature is left as default.
+    pub fn from_signed_bytes(bytes: &[u8]) -> Result<Self, TypeError> {
+        let mut reader = signed_bytes_reader(bytes, Self::MSG_TYPE)?;
+        let order = Order {
+            account_id: reader.account_id()?,
+            sub_account_id: reader.sub_account_id()?,
+            slot_id: SlotId(reader.u16()? as u32),
+            nonce: reader.order_nonce()?,
+            base_token_id: reader.token_id()?,
+            quote_token_id: reader.token_id()?,
+            price: reader.price()?,
+            is_sell: reader.u8()?,
+            fee_rates: [reader.u8()?, reader.u8()?],
+            has_subsidy: reader.u8()?,
+            amount: reader.packed_amount()?,
+            signature: Default::default(),
+        };
+        reader.finish()?;
+        Ok(order)
+    }
+
     #[cfg(feature = "ffi")]
     pub fn create_signed_order(
         &self,
@@ -170,6 +211,11 @@ impl ZkSignatureTrait for Order {
 
 /// `OrderMatching` transaction was used to match two orders.
 #[derive(Default, Debug, Clone, Serialize, Deserialize, Validate)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct OrderMatching {
     /// zklink network account ID of the transaction initiator.
@@ -190,6 +236,13 @@ pub struct OrderMatching {
     /// Fee for the transaction, need packaging
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "fee_packable")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub fee: BigUint,
     #[validate(custom = "token_validator")]
     pub fee_token: TokenId,
@@ -220,9 +273,23 @@ pub struct OrderMatching {
     /// maker: 10000, m2 <-> taker: taker: 10000, t2 <-> expect_base_amount t2 - t1, (t2 <= m2)
     /// t1 and t2 both packable, but (t2 - t1) may not be packable
     #[validate(custom = "amount_unpackable")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub expect_base_amount: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "amount_unpackable")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub expect_quote_amount: BigUint,
 
     /// Time range when the transaction is valid(layer2).
@@ -360,4 +427,31 @@ mod test {
             assert_eq!(bytes.len(), bytes_len);
         }
     }
+
+    #[test]
+    fn test_order_from_signed_bytes() {
+        let order = Order::new(
+            AccountId(10),
+            SubAccountId(1),
+            SlotId(3),
+            Nonce(7),
+            TokenId(32),
+            TokenId(17),
+            BigUint::from(10000u32),
+            BigUint::from(6000000000000000000u64),
+            true,
+            false,
+            5,
+            10,
+            None,
+        );
+        let bytes = order.get_bytes();
+        let decoded = Order::from_signed_bytes(&bytes).unwrap();
+        assert_eq!(decoded.get_bytes(), bytes);
+        assert_eq!(decoded.price, order.price);
+        assert_eq!(decoded.fee_rates, [5, 10]);
+
+        // not the bytes of order
+        assert!(Order::from_signed_bytes(&bytes[1..]).is_err());
+    }
 }
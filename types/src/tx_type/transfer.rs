@@ -2,9 +2,11 @@ use crate::basic_types::pack::{pack_fee_amount, pack_token_amount};
 use crate::basic_types::{
     AccountId, GetBytes, Nonce, SubAccountId, TimeStamp, TokenId, ZkLinkAddress,
 };
+use crate::error::TypeError;
 use crate::tx_type::validator::*;
 use crate::tx_type::{
-    ethereum_sign_message_part, starknet_sign_message_part, TxTrait, ZkSignatureTrait,
+    ethereum_sign_message_part, signed_bytes_reader, starknet_sign_message_part, TxTrait,
+    ZkSignatureTrait,
 };
 
 use crate::params::{SIGNED_TRANSFER_BIT_WIDTH, TOKEN_MAX_PRECISION, TX_TYPE_BIT_WIDTH};
@@ -27,6 +29,11 @@ use zklink_sdk_signers::zklink_signer::signature::ZkLinkSignature;
 use zklink_sdk_utils::serde::BigUintSerdeAsRadix10Str;
 /// `Transfer` transaction performs a move of funds from one zklink account to another.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Transfer {
     /// zklink network account ID of the transaction initiator.
@@ -47,10 +54,24 @@ pub struct Transfer {
     /// Amount of funds to transfer, layer1 need unpack it, do packaging
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "amount_packable")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub amount: BigUint,
     /// Fee for the transaction, need packaging
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "fee_packable")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub fee: BigUint,
     /// Current account nonce.
     #[validate(custom = "nonce_validator")]
@@ -69,6 +90,25 @@ impl Transfer {
         builder.build()
     }
 
+    /// Decode the bytes signed by the zklink signer, the signature is left as default.
+    pub fn from_signed_bytes(bytes: &[u8]) -> Result<Self, TypeError> {
+        let mut reader = signed_bytes_reader(bytes, Self::TX_TYPE)?;
+        let tx = Transfer {
+            account_id: reader.account_id()?,
+            from_sub_account_id: reader.sub_account_id()?,
+            to: reader.address()?,
+            to_sub_account_id: reader.sub_account_id()?,
+            token: reader.token_id()?,
+            amount: reader.packed_amount()?,
+            fee: reader.packed_fee()?,
+            nonce: reader.nonce()?,
+            ts: TimeStamp(reader.u32()?),
+            signature: Default::default(),
+        };
+        reader.finish()?;
+        Ok(tx)
+    }
+
     /// Get the first part of the message we expect to be signed by Ethereum account key.
     /// The only difference is the missing `nonce` since it's added at the end of the transactions
     /// batch message.
@@ -13,18 +13,25 @@ use crate::basic_types::pack::pack_fee_amount;
 use crate::basic_types::{
     AccountId, ChainId, GetBytes, Nonce, SubAccountId, TimeStamp, TokenId, ZkLinkAddress,
 };
+use crate::error::TypeError;
 use crate::params::TOKEN_MAX_PRECISION;
 #[cfg(feature = "ffi")]
 use crate::prelude::WithdrawBuilder;
 use crate::tx_type::validator::*;
 use crate::tx_type::{
-    ethereum_sign_message_part, starknet_sign_message_part, TxTrait, ZkSignatureTrait,
+    ethereum_sign_message_part, signed_bytes_reader, starknet_sign_message_part, TxTrait,
+    ZkSignatureTrait,
 };
 use zklink_sdk_signers::eth_signer::H256;
 use zklink_sdk_signers::starknet_signer::typed_data::message::TxMessage;
 
 /// `Withdraw` transaction performs a withdrawal of funds from zklink account to L1 account.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Withdraw {
     /// Target chain of withdraw.
@@ -48,12 +55,34 @@ pub struct Withdraw {
     /// Amount of funds to withdraw, layer1 can not unpack it, do not packaging
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "amount_unpackable")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub amount: BigUint,
     /// Call data hash
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::option_h256::serialize",
+            deserialize_with = "crate::codec::option_h256::deserialize"
+        )
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "Option<String>"))]
     pub data_hash: Option<H256>,
     /// Fee for the transaction, need packaging
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     #[validate(custom = "fee_packable")]
+    #[cfg_attr(
+        feature = "borsh",
+        borsh(
+            serialize_with = "crate::codec::biguint::serialize",
+            deserialize_with = "crate::codec::biguint::deserialize"
+        )
+    )]
     pub fee: BigUint,
     /// Current account nonce.
     #[validate(custom = "nonce_validator")]
@@ -78,6 +107,35 @@ impl Withdraw {
         builder.build()
     }
 
+    /// Decode the bytes signed by the zklink signer, the signature is left as default.
+    pub fn from_signed_bytes(bytes: &[u8]) -> Result<Self, TypeError> {
+        let mut reader = signed_bytes_reader(bytes, Self::TX_TYPE)?;
+        // the data hash is only signed if it's set
+        let has_data_hash = bytes.len() > Self::default().bytes_len();
+        let tx = Withdraw {
+            to_chain_id: reader.chain_id()?,
+            account_id: reader.account_id()?,
+            sub_account_id: reader.sub_account_id()?,
+            to: reader.address()?,
+            l2_source_token: reader.token_id()?,
+            l1_target_token: reader.token_id()?,
+            amount: reader.amount()?,
+            data_hash: if has_data_hash {
+                Some(H256::from_slice(reader.read(H256::len_bytes())?))
+            } else {
+                None
+            },
+            fee: reader.packed_fee()?,
+            nonce: reader.nonce()?,
+            withdraw_to_l1: reader.u8()?,
+            withdraw_fee_ratio: reader.u16()?,
+            ts: TimeStamp(reader.u32()?),
+            signature: Default::default(),
+        };
+        reader.finish()?;
+        Ok(tx)
+    }
+
     /// Get the first part of the message we expect to be signed by Ethereum account key.
     /// The only difference is the missing `nonce` since it's added at the end of the transactions
     /// batch message.
@@ -162,7 +220,11 @@ impl GetBytes for Withdraw {
     }
 
     fn bytes_len(&self) -> usize {
-        72
+        if self.data_hash.is_some() {
+            104
+        } else {
+            72
+        }
     }
 }
 
@@ -1,19 +1,26 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use validator::{Validate, ValidationErrors};
 
-use crate::basic_types::{tx_hash::TxHash, Nonce};
+use crate::basic_types::{tx_hash::TxHash, AccountId, Nonce, ZkLinkAddress};
+use crate::error::{TxVerifyError, TypeError};
 use crate::prelude::{
-    AutoDeleveraging, ContractMatching, Funding, Liquidation, SpotPriceInfo, UpdateGlobalVar,
+    AutoDeleveraging, ContractMatching, Funding, Liquidation, Order, SpotPriceInfo, UpdateGlobalVar,
 };
-use crate::tx_type::change_pubkey::ChangePubKey;
+use crate::signatures::TxLayer1Signature;
+use crate::tx_type::change_pubkey::{ChangePubKey, EthECDSAAuthScheme};
 use crate::tx_type::deposit::Deposit;
 use crate::tx_type::forced_exit::ForcedExit;
 use crate::tx_type::full_exit::FullExit;
 use crate::tx_type::order_matching::OrderMatching;
 use crate::tx_type::transfer::Transfer;
 use crate::tx_type::withdraw::Withdraw;
-use crate::tx_type::TxTrait;
+use crate::tx_type::{TxTrait, ZkSignatureTrait};
 use wasm_bindgen::prelude::wasm_bindgen;
+use zklink_sdk_signers::eth_signer::Address;
+use zklink_sdk_signers::starknet_signer::typed_data::message::{TxMessage, TypedDataMessage};
+use zklink_sdk_signers::starknet_signer::typed_data::TypedData;
+use zklink_sdk_signers::zklink_signer::pubkey_hash::PubKeyHash;
 
 /// A set of L2 transaction type supported by the zklink network.
 #[wasm_bindgen]
@@ -35,6 +42,11 @@ pub enum ZkLinkTxType {
 
 /// A set of L2 transaction supported by the zklink network.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(
+    feature = "borsh",
+    derive(borsh::BorshSerialize, borsh::BorshDeserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum ZkLinkTx {
     FullExit(Box<FullExit>),
@@ -123,6 +135,51 @@ impl From<Funding> for ZkLinkTx {
     }
 }
 
+/// The account expected to sign the tx, used by [`ZkLinkTx::verify`].
+#[derive(Debug, Clone)]
+pub struct TxVerifyContext {
+    /// The current pubkey hash of the account
+    pub pub_key_hash: PubKeyHash,
+    /// The layer1 address of the account
+    pub address: ZkLinkAddress,
+    /// The symbol of the token used in the layer1 signing message of `Transfer` and `Withdraw`
+    pub token_symbol: Option<String>,
+    /// The layer1 signature submitted with the tx
+    pub layer1_signature: Option<TxLayer1Signature>,
+    /// Required to verify the starknet signature
+    pub starknet_chain_id: Option<String>,
+    /// The message signed by the `EthECDSA` auth data of `ChangePubKey`
+    pub eth_auth_scheme: EthECDSAAuthScheme,
+    /// The current pubkey hashes of the accounts of the maker/taker orders and contracts of
+    /// `OrderMatching` and `ContractMatching`
+    pub order_pub_key_hashes: HashMap<AccountId, PubKeyHash>,
+}
+
+impl TxVerifyContext {
+    fn verify_order_signature(
+        &self,
+        account_id: AccountId,
+        order: &impl ZkSignatureTrait,
+    ) -> Result<(), TxVerifyError> {
+        let expected = self
+            .order_pub_key_hashes
+            .get(&account_id)
+            .ok_or(TxVerifyError::MissingOrderPubKeyHash(account_id))?;
+        check_zklink_signer(order.verify_signature(), *expected)
+    }
+}
+
+fn check_zklink_signer(
+    signer: Option<PubKeyHash>,
+    expected: PubKeyHash,
+) -> Result<(), TxVerifyError> {
+    let signer = signer.ok_or(TxVerifyError::InvalidZkLinkSignature)?;
+    if signer != expected {
+        return Err(TxVerifyError::ZkLinkSignerMismatch(signer, expected));
+    }
+    Ok(())
+}
+
 impl ZkLinkTx {
     /// Check tx format
     pub fn validate(&self) -> Result<(), ValidationErrors> {
@@ -200,6 +257,43 @@ impl ZkLinkTx {
         TxHash { data: out }
     }
 
+    /// Decode the bytes signed by the zklink signer(see `GetBytes`) by the leading `TX_TYPE`,
+    /// the signatures are left as default.
+    ///
+    /// Only the txs signed in fixed layouts can be decoded, the others sign the hash of the
+    /// orders or the contracts which could not be reversed.
+    pub fn from_signed_bytes(bytes: &[u8]) -> Result<Self, TypeError> {
+        let Some(tx_type) = bytes.first() else {
+            return Err(TypeError::InvalidSignedBytes("empty bytes".into()));
+        };
+        let tx = match *tx_type {
+            Transfer::TX_TYPE => Transfer::from_signed_bytes(bytes)?.into(),
+            Withdraw::TX_TYPE => Withdraw::from_signed_bytes(bytes)?.into(),
+            ChangePubKey::TX_TYPE => ChangePubKey::from_signed_bytes(bytes)?.into(),
+            ForcedExit::TX_TYPE => ForcedExit::from_signed_bytes(bytes)?.into(),
+            OrderMatching::TX_TYPE
+            | ContractMatching::TX_TYPE
+            | Liquidation::TX_TYPE
+            | AutoDeleveraging::TX_TYPE
+            | Funding::TX_TYPE => {
+                return Err(TypeError::InvalidSignedBytes(format!(
+                    "tx type {tx_type} signs the hashed bytes which could not be decoded"
+                )))
+            }
+            Order::MSG_TYPE => {
+                return Err(TypeError::InvalidSignedBytes(
+                    "the bytes of order should be decoded by `Order::from_signed_bytes`".into(),
+                ))
+            }
+            tx_type => {
+                return Err(TypeError::InvalidSignedBytes(format!(
+                    "unsupported tx type {tx_type}"
+                )))
+            }
+        };
+        Ok(tx)
+    }
+
     /// Returns the account nonce associated with transaction.
     pub fn nonce(&self) -> Nonce {
         match self {
@@ -217,6 +311,117 @@ impl ZkLinkTx {
         }
     }
 
+    /// Check the zklink signature and the layer1 signature against the account.
+    ///
+    /// `ChangePubKey` must be signed by the new pubkey hash instead of the current one and be
+    /// authorized by the account, and the priority txs are not signed. The maker/taker orders
+    /// and contracts must be signed by their own accounts. The layer1 signature is required by
+    /// `Transfer` and `Withdraw`, it's only checked when provided for the others. EIP-1271
+    /// signatures and the `Onchain` auth need an on-chain call, which are not supported here.
+    pub fn verify(&self, ctx: &TxVerifyContext) -> Result<(), TxVerifyError> {
+        let (signer, expected) = match self {
+            ZkLinkTx::Transfer(tx) => (tx.verify_signature(), ctx.pub_key_hash),
+            ZkLinkTx::Withdraw(tx) => (tx.verify_signature(), ctx.pub_key_hash),
+            ZkLinkTx::ChangePubKey(tx) => (tx.verify_signature(), tx.new_pk_hash),
+            ZkLinkTx::ForcedExit(tx) => (tx.verify_signature(), ctx.pub_key_hash),
+            ZkLinkTx::OrderMatching(tx) => (tx.verify_signature(), ctx.pub_key_hash),
+            ZkLinkTx::ContractMatching(tx) => (tx.verify_signature(), ctx.pub_key_hash),
+            ZkLinkTx::AutoDeleveraging(tx) => (tx.verify_signature(), ctx.pub_key_hash),
+            ZkLinkTx::Funding(tx) => (tx.verify_signature(), ctx.pub_key_hash),
+            ZkLinkTx::Liquidation(tx) => (tx.verify_signature(), ctx.pub_key_hash),
+            ZkLinkTx::Deposit(_) | ZkLinkTx::FullExit(_) | ZkLinkTx::UpdateGlobalVar(_) => {
+                return Ok(())
+            }
+        };
+        check_zklink_signer(signer, expected)?;
+
+        match self {
+            ZkLinkTx::OrderMatching(tx) => {
+                for order in [&tx.taker, &tx.maker] {
+                    ctx.verify_order_signature(order.account_id, order)?;
+                }
+            }
+            ZkLinkTx::ContractMatching(tx) => {
+                for contract in std::iter::once(&tx.taker).chain(&tx.maker) {
+                    ctx.verify_order_signature(contract.account_id, contract)?;
+                }
+            }
+            ZkLinkTx::ChangePubKey(tx) => tx.check_eth_auth(&ctx.eth_auth_scheme, &ctx.address)?,
+            _ => {}
+        }
+
+        match &ctx.layer1_signature {
+            None if matches!(self, ZkLinkTx::Transfer(_) | ZkLinkTx::Withdraw(_)) => {
+                Err(TxVerifyError::MissingLayer1Signature)
+            }
+            None => Ok(()),
+            Some(TxLayer1Signature::EthereumSignature(signature)) => {
+                let message = self.eth_sign_msg(ctx)?;
+                let signer = signature
+                    .signature_recover_signer(message.as_bytes())
+                    .map_err(|e| TxVerifyError::InvalidLayer1Signature(e.to_string()))?;
+                let expected = Address::from(&ctx.address);
+                if signer != expected {
+                    return Err(TxVerifyError::Layer1SignerMismatch(signer, expected));
+                }
+                Ok(())
+            }
+            Some(TxLayer1Signature::StarkSignature(signature)) => {
+                let message = self.starknet_sign_msg(ctx)?;
+                let chain_id = ctx
+                    .starknet_chain_id
+                    .clone()
+                    .ok_or(TxVerifyError::MissingStarknetChainId)?;
+                let typed_data =
+                    TypedData::new(TypedDataMessage::Transaction { message }, chain_id);
+                let is_valid = signature
+                    .verify(&typed_data, &ctx.address.to_string())
+                    .map_err(|e| TxVerifyError::InvalidLayer1Signature(e.to_string()))?;
+                if !is_valid {
+                    return Err(TxVerifyError::InvalidLayer1Signature(
+                        "starknet signature mismatch".to_string(),
+                    ));
+                }
+                Ok(())
+            }
+            Some(TxLayer1Signature::EIP1271Signature(_)) => Err(
+                TxVerifyError::UnsupportedLayer1Signature("EIP1271Signature".to_string()),
+            ),
+        }
+    }
+
+    fn eth_sign_msg(&self, ctx: &TxVerifyContext) -> Result<String, TxVerifyError> {
+        let token_symbol = || {
+            ctx.token_symbol
+                .as_deref()
+                .ok_or(TxVerifyError::MissingTokenSymbol)
+        };
+        match self {
+            ZkLinkTx::Transfer(tx) => Ok(tx.get_eth_sign_msg(token_symbol()?)),
+            ZkLinkTx::Withdraw(tx) => Ok(tx.get_eth_sign_msg(token_symbol()?)),
+            ZkLinkTx::OrderMatching(tx) => Ok(tx.get_eth_sign_msg()),
+            _ => Err(TxVerifyError::UnsupportedLayer1Signature(format!(
+                "{:?}",
+                self.tx_type()
+            ))),
+        }
+    }
+
+    fn starknet_sign_msg(&self, ctx: &TxVerifyContext) -> Result<TxMessage, TxVerifyError> {
+        let token_symbol = ctx
+            .token_symbol
+            .as_deref()
+            .ok_or(TxVerifyError::MissingTokenSymbol);
+        match self {
+            ZkLinkTx::Transfer(tx) => Ok(tx.get_starknet_sign_msg(token_symbol?)),
+            ZkLinkTx::Withdraw(tx) => Ok(tx.get_starknet_sign_msg(token_symbol?)),
+            _ => Err(TxVerifyError::UnsupportedLayer1Signature(format!(
+                "{:?}",
+                self.tx_type()
+            ))),
+        }
+    }
+
     /// Returns the margin prices of the transaction.
     pub fn margin_prices(&self) -> Vec<SpotPriceInfo> {
         match self {
@@ -233,6 +438,15 @@ impl ZkLinkTx {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::basic_types::{
+        AccountId, BigUint, ChainId, GetBytes, SubAccountId, TimeStamp, TokenId,
+    };
+    use crate::tx_builder::TransferBuilder;
+    use crate::tx_type::change_pubkey::ChangePubKeyAuthData;
+    use std::str::FromStr;
+    use zklink_sdk_signers::eth_signer::EthSigner;
+    use zklink_sdk_signers::eth_signer::H256;
+    use zklink_sdk_signers::zklink_signer::ZkLinkSigner;
 
     #[test]
     fn test_zklink_tx_deserde() {
@@ -241,4 +455,280 @@ mod test {
         println!("{tx:?}");
         assert!(tx.is_ok());
     }
+
+    #[test]
+    fn test_zklink_tx_verify() {
+        let private_key = "be725250b123a39dab5b7579334d5888987c72a58f4508062545fe6e08ca94f4";
+        let zklink_signer = ZkLinkSigner::new_from_hex_eth_signer(private_key).unwrap();
+        let eth_signer = EthSigner::try_from(private_key).unwrap();
+        let mut tx = TransferBuilder {
+            account_id: AccountId(10),
+            to_address: ZkLinkAddress::from_str("0xAFAFf3aD1a0425D792432D9eCD1c3e26Ef2C42E9")
+                .unwrap(),
+            from_sub_account_id: SubAccountId(1),
+            to_sub_account_id: SubAccountId(1),
+            token: TokenId(18),
+            amount: BigUint::from(10000u32),
+            fee: BigUint::from(3u32),
+            nonce: Nonce(1),
+            timestamp: TimeStamp(1693472232),
+        }
+        .build();
+        tx.sign(&zklink_signer).unwrap();
+        let eth_signature = eth_signer
+            .sign_message(tx.get_eth_sign_msg("USDC").as_bytes())
+            .unwrap();
+        let tx: ZkLinkTx = tx.into();
+
+        let mut ctx = TxVerifyContext {
+            pub_key_hash: zklink_signer.public_key().public_key_hash(),
+            address: eth_signer.get_address().into(),
+            token_symbol: Some("USDC".to_string()),
+            layer1_signature: Some(eth_signature.into()),
+            starknet_chain_id: None,
+            eth_auth_scheme: EthECDSAAuthScheme::PersonalMessage,
+            order_pub_key_hashes: HashMap::new(),
+        };
+        assert_eq!(tx.verify(&ctx), Ok(()));
+
+        // signed for another token
+        ctx.token_symbol = Some("USDT".to_string());
+        assert!(matches!(
+            tx.verify(&ctx),
+            Err(TxVerifyError::Layer1SignerMismatch(_, _))
+        ));
+        ctx.token_symbol = None;
+        assert_eq!(tx.verify(&ctx), Err(TxVerifyError::MissingTokenSymbol));
+
+        // the pubkey hash of the account has been changed
+        ctx.pub_key_hash = PubKeyHash::default();
+        assert!(matches!(
+            tx.verify(&ctx),
+            Err(TxVerifyError::ZkLinkSignerMismatch(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_zklink_tx_verify_required_layer1_signature() {
+        let private_key = "be725250b123a39dab5b7579334d5888987c72a58f4508062545fe6e08ca94f4";
+        let zklink_signer = ZkLinkSigner::new_from_hex_eth_signer(private_key).unwrap();
+        let eth_signer = EthSigner::try_from(private_key).unwrap();
+        let mut tx = Withdraw {
+            account_id: AccountId(10),
+            sub_account_id: SubAccountId(1),
+            to: eth_signer.get_address().into(),
+            l2_source_token: TokenId(18),
+            l1_target_token: TokenId(17),
+            amount: BigUint::from(10000u32),
+            nonce: Nonce(1),
+            ts: TimeStamp(1693472232),
+            ..Default::default()
+        };
+        tx.sign(&zklink_signer).unwrap();
+        let tx: ZkLinkTx = tx.into();
+        let ctx = TxVerifyContext {
+            pub_key_hash: zklink_signer.public_key().public_key_hash(),
+            address: eth_signer.get_address().into(),
+            token_symbol: Some("USDC".to_string()),
+            layer1_signature: None,
+            starknet_chain_id: None,
+            eth_auth_scheme: EthECDSAAuthScheme::PersonalMessage,
+            order_pub_key_hashes: HashMap::new(),
+        };
+        assert_eq!(tx.verify(&ctx), Err(TxVerifyError::MissingLayer1Signature));
+    }
+
+    #[test]
+    fn test_zklink_tx_verify_order_matching() {
+        let submitter = ZkLinkSigner::new_from_hex_eth_signer(
+            "be725250b123a39dab5b7579334d5888987c72a58f4508062545fe6e08ca94f4",
+        )
+        .unwrap();
+        let maker_signer = ZkLinkSigner::new_from_hex_eth_signer(
+            "0505050505050505050505050505050505050505050505050505050505050505",
+        )
+        .unwrap();
+        let taker_signer = ZkLinkSigner::new_from_hex_eth_signer(
+            "0707070707070707070707070707070707070707070707070707070707070707",
+        )
+        .unwrap();
+        let mut maker = Order {
+            account_id: AccountId(5),
+            amount: BigUint::from(10000u32),
+            price: BigUint::from(100u32),
+            ..Default::default()
+        };
+        maker.sign(&maker_signer).unwrap();
+        let mut taker = Order {
+            account_id: AccountId(7),
+            amount: BigUint::from(10000u32),
+            price: BigUint::from(100u32),
+            is_sell: 1,
+            ..Default::default()
+        };
+        taker.sign(&taker_signer).unwrap();
+        let mut tx = OrderMatching {
+            account_id: AccountId(1),
+            maker,
+            taker,
+            expect_base_amount: BigUint::from(10000u32),
+            expect_quote_amount: BigUint::from(100u32),
+            ..Default::default()
+        };
+        tx.sign(&submitter).unwrap();
+        let tx: ZkLinkTx = tx.into();
+
+        let mut ctx = TxVerifyContext {
+            pub_key_hash: submitter.public_key().public_key_hash(),
+            address: ZkLinkAddress::default(),
+            token_symbol: None,
+            layer1_signature: None,
+            starknet_chain_id: None,
+            eth_auth_scheme: EthECDSAAuthScheme::PersonalMessage,
+            order_pub_key_hashes: HashMap::from([
+                (AccountId(5), maker_signer.public_key().public_key_hash()),
+                (AccountId(7), taker_signer.public_key().public_key_hash()),
+            ]),
+        };
+        assert_eq!(tx.verify(&ctx), Ok(()));
+
+        // the maker order is not signed by the maker account
+        ctx.order_pub_key_hashes
+            .insert(AccountId(5), taker_signer.public_key().public_key_hash());
+        assert!(matches!(
+            tx.verify(&ctx),
+            Err(TxVerifyError::ZkLinkSignerMismatch(_, _))
+        ));
+        ctx.order_pub_key_hashes.remove(&AccountId(5));
+        assert_eq!(
+            tx.verify(&ctx),
+            Err(TxVerifyError::MissingOrderPubKeyHash(AccountId(5)))
+        );
+    }
+
+    #[test]
+    fn test_zklink_tx_verify_change_pubkey() {
+        let private_key = "be725250b123a39dab5b7579334d5888987c72a58f4508062545fe6e08ca94f4";
+        let zklink_signer = ZkLinkSigner::new_from_hex_eth_signer(private_key).unwrap();
+        let eth_signer = EthSigner::try_from(private_key).unwrap();
+        let mut tx = ChangePubKey {
+            chain_id: ChainId(1),
+            account_id: AccountId(2),
+            sub_account_id: SubAccountId(4),
+            new_pk_hash: zklink_signer.public_key().public_key_hash(),
+            fee_token: TokenId(1),
+            nonce: Nonce(100),
+            ts: TimeStamp(1695105758),
+            ..Default::default()
+        };
+        let message = ChangePubKey::get_eth_sign_msg(&tx.new_pk_hash, tx.nonce, tx.account_id);
+        tx.eth_auth_data = ChangePubKeyAuthData::EthECDSA {
+            eth_signature: eth_signer.sign_message(message.as_bytes()).unwrap(),
+        };
+        tx.sign(&zklink_signer).unwrap();
+        let tx: ZkLinkTx = tx.into();
+
+        let mut ctx = TxVerifyContext {
+            pub_key_hash: PubKeyHash::default(),
+            address: eth_signer.get_address().into(),
+            token_symbol: None,
+            layer1_signature: None,
+            starknet_chain_id: None,
+            eth_auth_scheme: EthECDSAAuthScheme::PersonalMessage,
+            order_pub_key_hashes: HashMap::new(),
+        };
+        assert_eq!(tx.verify(&ctx), Ok(()));
+
+        // authorized by another account
+        ctx.address =
+            ZkLinkAddress::from_str("0xAFAFf3aD1a0425D792432D9eCD1c3e26Ef2C42E9").unwrap();
+        assert!(matches!(
+            tx.verify(&ctx),
+            Err(TxVerifyError::ChangePubKeyAuthMismatch(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_from_signed_bytes() {
+        let address =
+            ZkLinkAddress::from_str("0xAFAFf3aD1a0425D792432D9eCD1c3e26Ef2C42E9").unwrap();
+        let transfer = Transfer {
+            account_id: AccountId(10),
+            from_sub_account_id: SubAccountId(1),
+            to_sub_account_id: SubAccountId(2),
+            to: address.clone(),
+            token: TokenId(18),
+            amount: BigUint::from(10000u32),
+            fee: BigUint::from(3u32),
+            nonce: Nonce(1),
+            ts: TimeStamp(1693472232),
+            ..Default::default()
+        };
+        let withdraw = Withdraw {
+            to_chain_id: ChainId(3),
+            account_id: AccountId(10),
+            sub_account_id: SubAccountId(1),
+            to: address.clone(),
+            l2_source_token: TokenId(18),
+            l1_target_token: TokenId(17),
+            amount: BigUint::from(10000u32),
+            data_hash: Some(H256::repeat_byte(7)),
+            fee: BigUint::from(3u32),
+            nonce: Nonce(1),
+            withdraw_to_l1: 1,
+            withdraw_fee_ratio: 50,
+            ts: TimeStamp(1693472232),
+            ..Default::default()
+        };
+        let change_pubkey = ChangePubKey {
+            chain_id: ChainId(1),
+            account_id: AccountId(2),
+            sub_account_id: SubAccountId(4),
+            new_pk_hash: PubKeyHash::from_hex("0xd8d5fb6a6caef06aa3dc2abdcdc240987e5330fe")
+                .unwrap(),
+            fee_token: TokenId(1),
+            fee: BigUint::from(100u32),
+            nonce: Nonce(100),
+            ts: TimeStamp(1695105758),
+            ..Default::default()
+        };
+        let forced_exit = ForcedExit {
+            to_chain_id: ChainId(1),
+            initiator_account_id: AccountId(10),
+            initiator_sub_account_id: SubAccountId(1),
+            initiator_nonce: Nonce(5),
+            target: address,
+            target_sub_account_id: SubAccountId(2),
+            l2_source_token: TokenId(18),
+            l1_target_token: TokenId(17),
+            exit_amount: BigUint::from(10000u32),
+            withdraw_to_l1: 0,
+            ts: TimeStamp(1693472232),
+            ..Default::default()
+        };
+        let txs = [
+            (transfer.get_bytes(), ZkLinkTx::from(transfer)),
+            (withdraw.get_bytes(), ZkLinkTx::from(withdraw)),
+            (change_pubkey.get_bytes(), ZkLinkTx::from(change_pubkey)),
+            (forced_exit.get_bytes(), ZkLinkTx::from(forced_exit)),
+        ];
+        for (bytes, tx) in txs {
+            let decoded = ZkLinkTx::from_signed_bytes(&bytes).unwrap();
+            assert_eq!(
+                serde_json::to_value(decoded).unwrap(),
+                serde_json::to_value(tx).unwrap()
+            );
+            // truncated or extended bytes
+            assert!(ZkLinkTx::from_signed_bytes(&bytes[..bytes.len() - 1]).is_err());
+            assert!(ZkLinkTx::from_signed_bytes(&[bytes, vec![0]].concat()).is_err());
+        }
+
+        // the orders are hashed in the signed bytes of order matching
+        let bytes = OrderMatching::default().get_bytes();
+        assert!(matches!(
+            ZkLinkTx::from_signed_bytes(&bytes),
+            Err(TypeError::InvalidSignedBytes(_))
+        ));
+        assert!(ZkLinkTx::from_signed_bytes(&[]).is_err());
+    }
 }
@@ -1 +1,3 @@
+#[cfg(feature = "schemars")]
+pub mod schema;
 pub mod serde;
@@ -0,0 +1,211 @@
+//! The JSON Schemas of the values serialized as strings and the TypeScript definitions generated
+//! from the JSON Schemas, enabled by the `schemars` feature.
+use crate::serde::{BigIntSerdeAsRadix10Str, BigUintSerdeAsRadix10Str};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Metadata, RootSchema, Schema, SchemaObject, SingleOrVec};
+use schemars::JsonSchema;
+use serde_json::Value;
+
+const INDENT: &str = "    ";
+
+/// The schema of the values serialized as strings, e.g. the hex strings of the hashes.
+pub fn string_schema(description: &str) -> Schema {
+    SchemaObject {
+        metadata: Some(Box::new(Metadata {
+            description: Some(description.into()),
+            ..Default::default()
+        })),
+        instance_type: Some(InstanceType::String.into()),
+        ..Default::default()
+    }
+    .into()
+}
+
+impl JsonSchema for BigUintSerdeAsRadix10Str {
+    fn schema_name() -> String {
+        "BigUintString".into()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        string_schema("The unsigned big integer as a radix 10 string, e.g. \"1000\"")
+    }
+}
+
+impl JsonSchema for BigIntSerdeAsRadix10Str {
+    fn schema_name() -> String {
+        "BigIntString".into()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        string_schema("The signed big integer as a radix 10 string, e.g. \"-1000\"")
+    }
+}
+
+/// Generate the TypeScript definitions of the `definitions` of the root schema, every definition
+/// is exported as a type alias of the same name.
+pub fn typescript_definitions(root: &RootSchema) -> String {
+    let mut ts = String::new();
+    for (name, schema) in &root.definitions {
+        push_description(&mut ts, schema, "");
+        ts.push_str(&format!(
+            "export type {name} = {};\n\n",
+            ts_type(schema, "")
+        ));
+    }
+    ts
+}
+
+fn push_description(ts: &mut String, schema: &Schema, indent: &str) {
+    let Schema::Object(SchemaObject {
+        metadata: Some(metadata),
+        ..
+    }) = schema
+    else {
+        return;
+    };
+    if let Some(description) = &metadata.description {
+        let lines: Vec<&str> = description
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        ts.push_str(&format!("{indent}/** {} */\n", lines.join(" ")));
+    }
+}
+
+fn ts_type(schema: &Schema, indent: &str) -> String {
+    let schema = match schema {
+        Schema::Bool(true) => return "unknown".into(),
+        Schema::Bool(false) => return "never".into(),
+        Schema::Object(schema) => schema,
+    };
+    if let Some(reference) = &schema.reference {
+        // "#/definitions/Name" => "Name"
+        return reference.rsplit('/').next().unwrap_or(reference).into();
+    }
+    if let Some(values) = &schema.enum_values {
+        return union(values.iter().map(Value::to_string).collect());
+    }
+    if let Some(value) = &schema.const_value {
+        return value.to_string();
+    }
+
+    let mut parts = Vec::new();
+    match &schema.instance_type {
+        Some(SingleOrVec::Single(instance_type)) => {
+            parts.push(instance_ts_type(instance_type, schema, indent))
+        }
+        Some(SingleOrVec::Vec(instance_types)) => parts.push(union(
+            instance_types
+                .iter()
+                .map(|instance_type| instance_ts_type(instance_type, schema, indent))
+                .collect(),
+        )),
+        None if schema.object.is_some() => parts.push(object_ts_type(schema, indent)),
+        None => {}
+    }
+    if let Some(subschemas) = &schema.subschemas {
+        if let Some(all_of) = &subschemas.all_of {
+            parts.extend(all_of.iter().map(|schema| ts_type(schema, indent)));
+        }
+        for variants in [&subschemas.any_of, &subschemas.one_of]
+            .into_iter()
+            .flatten()
+        {
+            let variants = variants.iter().map(|schema| ts_type(schema, indent));
+            parts.push(union(variants.collect()));
+        }
+    }
+    match parts.len() {
+        0 => "unknown".into(),
+        1 => parts.remove(0),
+        _ => parts
+            .iter()
+            .map(|part| parenthesize(part))
+            .collect::<Vec<_>>()
+            .join(" & "),
+    }
+}
+
+fn instance_ts_type(instance_type: &InstanceType, schema: &SchemaObject, indent: &str) -> String {
+    match instance_type {
+        InstanceType::Null => "null".into(),
+        InstanceType::Boolean => "boolean".into(),
+        InstanceType::Integer | InstanceType::Number => "number".into(),
+        InstanceType::String => "string".into(),
+        InstanceType::Array => match schema.array.as_ref().and_then(|a| a.items.as_ref()) {
+            Some(SingleOrVec::Single(item)) => {
+                format!("{}[]", parenthesize(&ts_type(item, indent)))
+            }
+            Some(SingleOrVec::Vec(items)) => {
+                let items: Vec<_> = items.iter().map(|item| ts_type(item, indent)).collect();
+                format!("[{}]", items.join(", "))
+            }
+            None => "unknown[]".into(),
+        },
+        InstanceType::Object => object_ts_type(schema, indent),
+    }
+}
+
+fn object_ts_type(schema: &SchemaObject, indent: &str) -> String {
+    let Some(object) = &schema.object else {
+        return "Record<string, unknown>".into();
+    };
+    if object.properties.is_empty() {
+        return match &object.additional_properties {
+            Some(values) => format!("Record<string, {}>", ts_type(values, indent)),
+            None => "Record<string, unknown>".into(),
+        };
+    }
+    let inner = format!("{indent}{INDENT}");
+    let mut ts = String::from("{\n");
+    for (name, property) in &object.properties {
+        push_description(&mut ts, property, &inner);
+        let optional = if object.required.contains(name) {
+            ""
+        } else {
+            "?"
+        };
+        ts.push_str(&format!(
+            "{inner}{}{optional}: {};\n",
+            property_name(name),
+            ts_type(property, &inner)
+        ));
+    }
+    ts.push_str(indent);
+    ts.push('}');
+    ts
+}
+
+fn union(types: Vec<String>) -> String {
+    let mut distinct: Vec<String> = Vec::with_capacity(types.len());
+    for ty in types {
+        if !distinct.contains(&ty) {
+            distinct.push(ty);
+        }
+    }
+    distinct.join(" | ")
+}
+
+fn parenthesize(ty: &str) -> String {
+    if ty.contains(" | ") || ty.contains(" & ") {
+        format!("({ty})")
+    } else {
+        ty.into()
+    }
+}
+
+fn property_name(name: &str) -> String {
+    let is_identifier = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        name.into()
+    } else {
+        Value::from(name).to_string()
+    }
+}
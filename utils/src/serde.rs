@@ -14,6 +14,14 @@ impl Prefix for ZeroxPrefix {
     }
 }
 
+/// No prefix, e.g. the hex strings of the keystore
+pub struct NoPrefix;
+impl Prefix for NoPrefix {
+    fn prefix() -> &'static str {
+        ""
+    }
+}
+
 /// Used to annotate `Vec<u8>` fields that you want to serialize like hex-encoded string with prefix
 /// Use this struct in annotation like that `[serde(with = "BytesToHexSerde::<T>"]`
 /// where T is concrete prefix type (e.g. `SyncBlockPrefix`)
@@ -51,6 +59,7 @@ impl<P: Prefix> BytesToHexSerde<P> {
 }
 
 pub type ZeroPrefixHexSerde = BytesToHexSerde<ZeroxPrefix>;
+pub type NoPrefixHexSerde = BytesToHexSerde<NoPrefix>;
 
 /// Used to annotate `Option<Vec<u8>>` fields that you want to serialize like hex-encoded string with prefix
 /// Use this struct in annotation like that `[serde(with = "OptionBytesToHexSerde::<T>"]`
@@ -1,6 +1,6 @@
 use ethers::abi::AbiError;
 use ethers::abi::Error as EthAbiError;
-use ethers::providers::ProviderError;
+use ethers::providers::{MiddlewareError, ProviderError};
 use thiserror::Error;
 use wasm_bindgen::JsValue;
 use zklink_sdk_signers::eth_signer::EthSignerError;
@@ -23,6 +23,20 @@ pub enum WalletError {
     EthSignerError(#[from] EthSignerError),
     #[error("Eth abi error: {0}")]
     EthAbiError(#[from] EthAbiError),
+    #[error("Decode event error: {0}")]
+    DecodeEventError(String),
+    #[error("Layer1 middleware error: {0}")]
+    MiddlewareError(String),
+    #[error("Replace tx error: {0}")]
+    ReplaceTxError(String),
+    #[error("Multicall error: {0}")]
+    MulticallError(String),
+}
+
+impl WalletError {
+    pub(crate) fn middleware<E: MiddlewareError>(error: E) -> Self {
+        WalletError::MiddlewareError(error.to_string())
+    }
 }
 
 impl From<WalletError> for JsValue {
@@ -4,7 +4,9 @@ use ethers::core::types::transaction::eip2718::TypedTransaction;
 use ethers::types::{Address, Eip1559TransactionRequest, TransactionRequest, U256};
 use serde::{Deserialize, Serialize};
 use zklink_sdk_types::basic_types::BigUint;
-use zklink_sdk_types::prelude::ZkLinkAddress;
+use zklink_sdk_types::prelude::{
+    AccountId, BigUintSerdeWrapper, SubAccountId, TokenId, ZkLinkAddress, H256,
+};
 
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct EthTxOption {
@@ -14,6 +16,8 @@ pub struct EthTxOption {
     pub value: Option<BigUint>,
     pub gas: Option<u64>,
     pub gas_price: Option<BigUint>,
+    pub max_fee_per_gas: Option<BigUint>,
+    pub max_priority_fee_per_gas: Option<BigUint>,
 }
 
 #[derive(Clone, Default)]
@@ -25,6 +29,25 @@ pub struct EthTxParam {
     pub(crate) value: Option<U256>,
     pub(crate) gas: Option<U256>,
     pub(crate) gas_price: Option<U256>,
+    pub(crate) max_fee_per_gas: Option<U256>,
+    pub(crate) max_priority_fee_per_gas: Option<U256>,
+}
+
+/// How to fill the fees of the layer1 txs if they are not set in `EthTxParam`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FeeStrategy {
+    /// The gas price of `eth_gasPrice`, the EIP-1559 txs use it as both the max fee and the
+    /// max priority fee.
+    #[default]
+    Legacy,
+    /// The max fee and the max priority fee estimated from the rewards of `eth_feeHistory`,
+    /// the legacy txs use the max fee as the gas price.
+    Eip1559,
+    /// The fixed fees, the legacy txs use the max fee as the gas price.
+    Fixed {
+        max_fee_per_gas: U256,
+        max_priority_fee_per_gas: U256,
+    },
 }
 
 impl From<EthTxOption> for EthTxParam {
@@ -41,10 +64,98 @@ impl From<EthTxOption> for EthTxParam {
             gas_price: eth_tx_option
                 .gas_price
                 .map(|g| U256::from_str_radix(&g.to_string(), 10).unwrap_or_default()),
+            max_fee_per_gas: eth_tx_option
+                .max_fee_per_gas
+                .map(|g| U256::from_str_radix(&g.to_string(), 10).unwrap_or_default()),
+            max_priority_fee_per_gas: eth_tx_option
+                .max_priority_fee_per_gas
+                .map(|g| U256::from_str_radix(&g.to_string(), 10).unwrap_or_default()),
         }
     }
 }
 
+/// The block info stored in the zklink contract, the exodus proof is generated against the last
+/// executed block.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoredBlockInfo {
+    pub block_number: u32,
+    pub priority_operations: u64,
+    pub pending_onchain_operations_hash: H256,
+    pub timestamp: BigUintSerdeWrapper,
+    pub state_hash: H256,
+    pub commitment: H256,
+    pub sync_hash: H256,
+}
+
+impl StoredBlockInfo {
+    pub(crate) fn to_token(&self) -> Result<Token, WalletError> {
+        Ok(Token::Tuple(vec![
+            Token::Uint(U256::from(self.block_number)),
+            Token::Uint(U256::from(self.priority_operations)),
+            Token::FixedBytes(self.pending_onchain_operations_hash.as_bytes().to_vec()),
+            Token::Uint(biguint_to_u256(&self.timestamp)?),
+            Token::FixedBytes(self.state_hash.as_bytes().to_vec()),
+            Token::FixedBytes(self.commitment.as_bytes().to_vec()),
+            Token::FixedBytes(self.sync_hash.as_bytes().to_vec()),
+        ]))
+    }
+}
+
+/// The exodus proof generated by the exodus prover, the `proof` is passed to the zklink contract
+/// as it is.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExodusProof {
+    pub stored_block_info: StoredBlockInfo,
+    pub owner: ZkLinkAddress,
+    pub account_id: AccountId,
+    pub sub_account_id: SubAccountId,
+    pub withdraw_token_id: TokenId,
+    pub deduct_token_id: TokenId,
+    pub amount: BigUintSerdeWrapper,
+    pub proof: Vec<BigUintSerdeWrapper>,
+}
+
+impl ExodusProof {
+    pub(crate) fn to_tokens(&self) -> Result<Vec<Token>, WalletError> {
+        let proof = self
+            .proof
+            .iter()
+            .map(|p| biguint_to_u256(p).map(Token::Uint))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(vec![
+            self.stored_block_info.to_token()?,
+            Token::FixedBytes(self.owner.to_fixed_bytes().to_vec()),
+            Token::Uint(U256::from(*self.account_id)),
+            Token::Uint(U256::from(*self.sub_account_id)),
+            Token::Uint(U256::from(*self.withdraw_token_id)),
+            Token::Uint(U256::from(*self.deduct_token_id)),
+            Token::Uint(biguint_to_u256(&self.amount)?),
+            Token::Array(proof),
+        ])
+    }
+}
+
+pub(crate) fn biguint_to_u256(value: &BigUint) -> Result<U256, WalletError> {
+    U256::from_str_radix(&value.to_string(), 10).map_err(|_e| WalletError::InvalidInputParameter)
+}
+
+/// The evm address of the 20 bytes `ZkLinkAddress`, the 32 bytes (non-evm) addresses are
+/// rejected instead of being truncated.
+pub(crate) fn evm_address(address: &ZkLinkAddress) -> Result<Address, WalletError> {
+    if address.as_bytes().len() != Address::len_bytes() {
+        return Err(WalletError::InvalidInputParameter);
+    }
+    Ok(Address::from_slice(address.as_bytes()))
+}
+
+pub(crate) fn u256_to_biguint(value: U256) -> BigUint {
+    let mut bytes = [0u8; 32];
+    value.to_big_endian(&mut bytes);
+    BigUint::from_bytes_be(&bytes)
+}
+
 pub fn new_call_typed_tx(tx_params: EthTxParam, chain_id: u64) -> TypedTransaction {
     let mut tx = if tx_params.is_support_eip1559 {
         TypedTransaction::Eip1559(Eip1559TransactionRequest::new())
@@ -80,6 +191,14 @@ pub fn new_typed_tx(from: Address, tx_params: EthTxParam, chain_id: u64) -> Type
     if let Some(gas_price) = tx_params.gas_price {
         tx.set_gas_price(gas_price);
     }
+    if let TypedTransaction::Eip1559(tx) = &mut tx {
+        if let Some(max_fee_per_gas) = tx_params.max_fee_per_gas {
+            tx.max_fee_per_gas = Some(max_fee_per_gas);
+        }
+        if let Some(max_priority_fee_per_gas) = tx_params.max_priority_fee_per_gas {
+            tx.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+        }
+    }
     tx.set_chain_id(chain_id);
     tx
 }
@@ -93,3 +212,35 @@ pub fn encode_tx(
     let tx_data = function.encode_input(&params)?;
     Ok(tx_data)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::abi::load_contracts;
+
+    #[test]
+    fn test_encode_exodus_proof() {
+        let exodus_proof = r#"{
+            "storedBlockInfo": {
+                "blockNumber": 10,
+                "priorityOperations": 2,
+                "pendingOnchainOperationsHash": "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+                "timestamp": "1700000000",
+                "stateHash": "0x0000000000000000000000000000000000000000000000000000000000000001",
+                "commitment": "0x0000000000000000000000000000000000000000000000000000000000000002",
+                "syncHash": "0x0000000000000000000000000000000000000000000000000000000000000003"
+            },
+            "owner": "0xAFAFf3aD1a0425D792432D9eCD1c3e26Ef2C42E9",
+            "accountId": 10,
+            "subAccountId": 1,
+            "withdrawTokenId": 18,
+            "deductTokenId": 17,
+            "amount": "1000000",
+            "proof": ["1", "2", "3"]
+        }"#;
+        let exodus_proof: ExodusProof = serde_json::from_str(exodus_proof).unwrap();
+        let contract = load_contracts().remove("zklink").unwrap();
+        let tx_data = encode_tx(contract, "performExodus", exodus_proof.to_tokens().unwrap());
+        assert!(tx_data.is_ok());
+    }
+}
@@ -0,0 +1,422 @@
+//! Decode the events of the zklink contract into the sdk types.
+use crate::error::WalletError;
+use ethers::abi::{Contract, Log as AbiLog, RawLog, Token};
+use ethers::types::{Address, Log, U256};
+use zklink_sdk_types::basic_types::BigUint;
+use zklink_sdk_types::params::{DEPOSIT_BYTES, FULL_EXIT_BYTES};
+use zklink_sdk_types::prelude::{
+    BlockNumber, Deposit, DepositOp, FullExit, FullExitOp, Nonce, PubdataOp, TokenId,
+    ZkLinkAddress, H256,
+};
+
+/// The pubdata of deposit in the priority queue:
+/// op_type(1) | chain_id(1) | account_id(4) | sub_account_id(1) | token_id(2) | target_token_id(2) | amount(16) | owner(32)
+pub const DEPOSIT_PUBDATA_BYTES: usize = 59;
+/// The pubdata of full exit in the priority queue:
+/// op_type(1) | chain_id(1) | account_id(4) | sub_account_id(1) | owner(32) | token_id(2) | src_token_id(2) | amount(16)
+pub const FULL_EXIT_PUBDATA_BYTES: usize = 59;
+
+/// The names of the events that can be decoded into `ZkLinkEvent`.
+pub const ZKLINK_EVENTS: [&str; 8] = [
+    "NewPriorityRequest",
+    "Withdrawal",
+    "WithdrawalPending",
+    "BlockCommit",
+    "BlockExecuted",
+    "ExodusMode",
+    "FactAuth",
+    "SetGateway",
+];
+
+/// A deposit or full exit requested in layer1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NewPriorityRequest {
+    pub sender: Address,
+    pub serial_id: u64,
+    pub op_type: u8,
+    pub pubdata: Vec<u8>,
+    pub expiration_block: U256,
+}
+
+/// The withdrawal is transferred to the recipient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Withdrawal {
+    pub token_id: TokenId,
+    pub amount: BigUint,
+}
+
+/// The withdrawal is failed to transfer and kept as the pending balance of the recipient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WithdrawalPending {
+    pub token_id: TokenId,
+    pub recipient: ZkLinkAddress,
+    pub amount: BigUint,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FactAuth {
+    pub sender: Address,
+    pub nonce: Nonce,
+    pub fact: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZkLinkEvent {
+    NewPriorityRequest(NewPriorityRequest),
+    Withdrawal(Withdrawal),
+    WithdrawalPending(WithdrawalPending),
+    BlockCommit(BlockNumber),
+    BlockExecuted(BlockNumber),
+    ExodusMode,
+    FactAuth(FactAuth),
+    SetGateway(Address),
+}
+
+/// The decoded event with the position of the log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZkLinkEventLog {
+    pub event: ZkLinkEvent,
+    pub block_number: Option<u64>,
+    pub tx_hash: Option<H256>,
+    pub log_index: Option<U256>,
+}
+
+/// The priority operation decoded from the pubdata of `NewPriorityRequest`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PriorityOp {
+    Deposit(Deposit),
+    FullExit(FullExit),
+}
+
+impl NewPriorityRequest {
+    /// Decode the pubdata, `l2_hash` is the hash of the layer1 transaction that emits the event.
+    pub fn priority_op(&self, l2_hash: H256) -> Result<PriorityOp, WalletError> {
+        let pubdata = &self.pubdata;
+        match self.op_type {
+            // the pubdata in the priority queue is the block pubdata without the padding
+            Deposit::TX_TYPE => {
+                check_pubdata(pubdata, Deposit::TX_TYPE, DEPOSIT_PUBDATA_BYTES)?;
+                let op = DepositOp::from_pubdata(&pad_pubdata(pubdata, DEPOSIT_BYTES))
+                    .map_err(|e| WalletError::DecodeEventError(e.to_string()))?;
+                Ok(PriorityOp::Deposit(Deposit {
+                    from: self.sender.into(),
+                    serial_id: self.serial_id,
+                    l2_hash,
+                    ..op.tx
+                }))
+            }
+            FullExit::TX_TYPE => {
+                check_pubdata(pubdata, FullExit::TX_TYPE, FULL_EXIT_PUBDATA_BYTES)?;
+                let op = FullExitOp::from_pubdata(&pad_pubdata(pubdata, FULL_EXIT_BYTES))
+                    .map_err(|e| WalletError::DecodeEventError(e.to_string()))?;
+                Ok(PriorityOp::FullExit(FullExit {
+                    serial_id: self.serial_id,
+                    l2_hash,
+                    ..op.tx
+                }))
+            }
+            op_type => Err(WalletError::DecodeEventError(format!(
+                "unsupported priority op type {op_type}"
+            ))),
+        }
+    }
+}
+
+impl ZkLinkEventLog {
+    /// Decode the log emitted by the zklink `contract` with the position of the log.
+    pub fn decode(contract: &Contract, log: &Log) -> Result<Option<Self>, WalletError> {
+        Ok(
+            ZkLinkEvent::decode(contract, log)?.map(|event| ZkLinkEventLog {
+                event,
+                block_number: log.block_number.map(|n| n.as_u64()),
+                tx_hash: log.transaction_hash,
+                log_index: log.log_index,
+            }),
+        )
+    }
+
+    /// Decode the priority operation if the event is `NewPriorityRequest`.
+    pub fn priority_op(&self) -> Option<Result<PriorityOp, WalletError>> {
+        match &self.event {
+            ZkLinkEvent::NewPriorityRequest(request) => {
+                Some(request.priority_op(self.tx_hash.unwrap_or_default()))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl ZkLinkEvent {
+    /// Decode the log emitted by the zklink `contract`, returns `None` if the log is not one of
+    /// the [`ZKLINK_EVENTS`].
+    pub fn decode(contract: &Contract, log: &Log) -> Result<Option<Self>, WalletError> {
+        let Some(topic) = log.topics.first() else {
+            return Ok(None);
+        };
+        let Some(event) = contract.events().find(|event| event.signature() == *topic) else {
+            return Ok(None);
+        };
+        let raw_log = RawLog {
+            topics: log.topics.clone(),
+            data: log.data.to_vec(),
+        };
+        let params = event.parse_log(raw_log)?;
+        let event = match event.name.as_str() {
+            "NewPriorityRequest" => ZkLinkEvent::NewPriorityRequest(NewPriorityRequest {
+                sender: address_param(&params, "sender")?,
+                serial_id: uint_param(&params, "serialId")?.as_u64(),
+                op_type: uint_param(&params, "opType")?.as_u32() as u8,
+                pubdata: bytes_param(&params, "pubData")?,
+                expiration_block: uint_param(&params, "expirationBlock")?,
+            }),
+            "Withdrawal" => ZkLinkEvent::Withdrawal(Withdrawal {
+                token_id: TokenId(uint_param(&params, "tokenId")?.as_u32()),
+                amount: BigUint::from(uint_param(&params, "amount")?.as_u128()),
+            }),
+            "WithdrawalPending" => ZkLinkEvent::WithdrawalPending(WithdrawalPending {
+                token_id: TokenId(uint_param(&params, "tokenId")?.as_u32()),
+                recipient: owner_address(&bytes_param(&params, "recepient")?),
+                amount: BigUint::from(uint_param(&params, "amount")?.as_u128()),
+            }),
+            "BlockCommit" => {
+                ZkLinkEvent::BlockCommit(BlockNumber(uint_param(&params, "blockNumber")?.as_u32()))
+            }
+            "BlockExecuted" => ZkLinkEvent::BlockExecuted(BlockNumber(
+                uint_param(&params, "blockNumber")?.as_u32(),
+            )),
+            "ExodusMode" => ZkLinkEvent::ExodusMode,
+            "FactAuth" => ZkLinkEvent::FactAuth(FactAuth {
+                sender: address_param(&params, "sender")?,
+                nonce: Nonce(uint_param(&params, "nonce")?.as_u32()),
+                fact: bytes_param(&params, "fact")?,
+            }),
+            "SetGateway" => ZkLinkEvent::SetGateway(address_param(&params, "newGateway")?),
+            _ => return Ok(None),
+        };
+        Ok(Some(event))
+    }
+}
+
+fn check_pubdata(pubdata: &[u8], op_type: u8, len: usize) -> Result<(), WalletError> {
+    if pubdata.len() != len || pubdata[0] != op_type {
+        return Err(WalletError::DecodeEventError(format!(
+            "invalid pubdata of op type {op_type}: 0x{}",
+            ethers::utils::hex::encode(pubdata)
+        )));
+    }
+    Ok(())
+}
+
+fn pad_pubdata(pubdata: &[u8], len: usize) -> Vec<u8> {
+    let mut pubdata = pubdata.to_vec();
+    pubdata.resize(len, 0);
+    pubdata
+}
+
+/// The owner is encoded as bytes32, the ethereum address is left padded with zeros.
+fn owner_address(bytes: &[u8]) -> ZkLinkAddress {
+    if bytes.len() == 32 && bytes[..12].iter().all(|b| *b == 0) {
+        ZkLinkAddress::from(bytes[12..].to_vec())
+    } else {
+        ZkLinkAddress::from(bytes.to_vec())
+    }
+}
+
+fn param(log: &AbiLog, name: &str) -> Result<Token, WalletError> {
+    log.params
+        .iter()
+        .find(|param| param.name == name)
+        .map(|param| param.value.clone())
+        .ok_or_else(|| WalletError::DecodeEventError(format!("missing event param {name}")))
+}
+
+fn uint_param(log: &AbiLog, name: &str) -> Result<U256, WalletError> {
+    param(log, name)?
+        .into_uint()
+        .ok_or_else(|| WalletError::DecodeEventError(format!("event param {name} is not uint")))
+}
+
+fn address_param(log: &AbiLog, name: &str) -> Result<Address, WalletError> {
+    param(log, name)?
+        .into_address()
+        .ok_or_else(|| WalletError::DecodeEventError(format!("event param {name} is not address")))
+}
+
+fn bytes_param(log: &AbiLog, name: &str) -> Result<Vec<u8>, WalletError> {
+    match param(log, name)? {
+        Token::Bytes(bytes) | Token::FixedBytes(bytes) => Ok(bytes),
+        _ => Err(WalletError::DecodeEventError(format!(
+            "event param {name} is not bytes"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::abi::load_contracts;
+    use ethers::abi::encode;
+    use std::str::FromStr;
+    use zklink_sdk_types::prelude::{AccountId, ChainId, SubAccountId};
+
+    const SENDER: &str = "0xAFAFf3aD1a0425D792432D9eCD1c3e26Ef2C42E9";
+
+    fn zklink_contract() -> Contract {
+        load_contracts().remove("zklink").unwrap()
+    }
+
+    fn new_log(contract: &Contract, name: &str, indexed: Vec<H256>, data: &[Token]) -> Log {
+        let mut topics = vec![contract.event(name).unwrap().signature()];
+        topics.extend(indexed);
+        Log {
+            topics,
+            data: encode(data).into(),
+            block_number: Some(100.into()),
+            transaction_hash: Some(H256::repeat_byte(1)),
+            ..Default::default()
+        }
+    }
+
+    fn priority_request_log(contract: &Contract, op_type: u8, pubdata: Vec<u8>) -> Log {
+        let data = [
+            Token::Address(Address::from_str(SENDER).unwrap()),
+            Token::Uint(7.into()),
+            Token::Uint(op_type.into()),
+            Token::Bytes(pubdata),
+            Token::Uint(1000.into()),
+        ];
+        new_log(contract, "NewPriorityRequest", vec![], &data)
+    }
+
+    #[test]
+    fn test_decode_deposit() {
+        let contract = zklink_contract();
+        let mut pubdata = vec![Deposit::TX_TYPE, 1, 0, 0, 0, 0, 2, 0, 18, 0, 17];
+        pubdata.extend(100u128.to_be_bytes());
+        pubdata.extend([0u8; 12]);
+        pubdata.extend(Address::from_str(SENDER).unwrap().as_bytes());
+        let log = priority_request_log(&contract, Deposit::TX_TYPE, pubdata);
+
+        let event_log = ZkLinkEventLog::decode(&contract, &log).unwrap().unwrap();
+        assert_eq!(event_log.block_number, Some(100));
+        let PriorityOp::Deposit(deposit) = event_log.priority_op().unwrap().unwrap() else {
+            panic!("not deposit");
+        };
+        let address = ZkLinkAddress::from_str(SENDER).unwrap();
+        assert_eq!(deposit.from, address);
+        assert_eq!(deposit.to, address);
+        assert_eq!(deposit.from_chain_id, ChainId(1));
+        assert_eq!(deposit.sub_account_id, SubAccountId(2));
+        assert_eq!(deposit.l1_source_token, TokenId(18));
+        assert_eq!(deposit.l2_target_token, TokenId(17));
+        assert_eq!(deposit.amount, BigUint::from(100u32));
+        assert_eq!(deposit.serial_id, 7);
+        assert_eq!(deposit.l2_hash, H256::repeat_byte(1));
+    }
+
+    #[test]
+    fn test_decode_full_exit() {
+        let contract = zklink_contract();
+        let mut pubdata = vec![FullExit::TX_TYPE, 1, 0, 0, 0, 10, 2];
+        pubdata.extend([0u8; 12]);
+        pubdata.extend(Address::from_str(SENDER).unwrap().as_bytes());
+        pubdata.extend([0, 18, 0, 17]);
+        pubdata.extend([0u8; 16]);
+        let log = priority_request_log(&contract, FullExit::TX_TYPE, pubdata.clone());
+
+        let event_log = ZkLinkEventLog::decode(&contract, &log).unwrap().unwrap();
+        let PriorityOp::FullExit(full_exit) = event_log.priority_op().unwrap().unwrap() else {
+            panic!("not full exit");
+        };
+        assert_eq!(full_exit.to_chain_id, ChainId(1));
+        assert_eq!(full_exit.account_id, AccountId(10));
+        assert_eq!(full_exit.sub_account_id, SubAccountId(2));
+        assert_eq!(
+            full_exit.exit_address,
+            ZkLinkAddress::from_str(SENDER).unwrap()
+        );
+        assert_eq!(full_exit.l1_target_token, TokenId(18));
+        assert_eq!(full_exit.l2_source_token, TokenId(17));
+
+        // the op type mismatch with the pubdata
+        let log = priority_request_log(&contract, Deposit::TX_TYPE, pubdata);
+        let event_log = ZkLinkEventLog::decode(&contract, &log).unwrap().unwrap();
+        assert!(event_log.priority_op().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_decode_events() {
+        let contract = zklink_contract();
+        let token_topic = H256::from_low_u64_be(18);
+        let log = new_log(
+            &contract,
+            "Withdrawal",
+            vec![token_topic],
+            &[Token::Uint(100.into())],
+        );
+        assert_eq!(
+            ZkLinkEvent::decode(&contract, &log).unwrap(),
+            Some(ZkLinkEvent::Withdrawal(Withdrawal {
+                token_id: TokenId(18),
+                amount: BigUint::from(100u32),
+            }))
+        );
+
+        let recipient = Address::from_str(SENDER).unwrap();
+        let log = new_log(
+            &contract,
+            "WithdrawalPending",
+            vec![token_topic, recipient.into()],
+            &[Token::Uint(100.into())],
+        );
+        assert_eq!(
+            ZkLinkEvent::decode(&contract, &log).unwrap(),
+            Some(ZkLinkEvent::WithdrawalPending(WithdrawalPending {
+                token_id: TokenId(18),
+                recipient: recipient.into(),
+                amount: BigUint::from(100u32),
+            }))
+        );
+
+        let log = new_log(
+            &contract,
+            "BlockExecuted",
+            vec![H256::from_low_u64_be(5)],
+            &[],
+        );
+        assert_eq!(
+            ZkLinkEvent::decode(&contract, &log).unwrap(),
+            Some(ZkLinkEvent::BlockExecuted(BlockNumber(5)))
+        );
+
+        let log = new_log(&contract, "ExodusMode", vec![], &[]);
+        assert_eq!(
+            ZkLinkEvent::decode(&contract, &log).unwrap(),
+            Some(ZkLinkEvent::ExodusMode)
+        );
+
+        let log = new_log(
+            &contract,
+            "FactAuth",
+            vec![recipient.into()],
+            &[Token::Uint(3.into()), Token::Bytes(vec![1, 2, 3])],
+        );
+        assert_eq!(
+            ZkLinkEvent::decode(&contract, &log).unwrap(),
+            Some(ZkLinkEvent::FactAuth(FactAuth {
+                sender: recipient,
+                nonce: Nonce(3),
+                fact: vec![1, 2, 3],
+            }))
+        );
+
+        // the other events are ignored
+        let log = new_log(
+            &contract,
+            "BlockProven",
+            vec![H256::from_low_u64_be(5)],
+            &[],
+        );
+        assert_eq!(ZkLinkEvent::decode(&contract, &log).unwrap(), None);
+    }
+}
@@ -1,4 +1,7 @@
 pub mod abi;
 pub mod error;
 pub mod eth;
+pub mod events;
+pub mod multicall;
+pub mod signer;
 pub mod wallet;
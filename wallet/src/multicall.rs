@@ -0,0 +1,241 @@
+//! Batch the layer1 reads of many tokens and accounts into one `eth_call` by Multicall3.
+use crate::error::WalletError;
+use crate::eth::{evm_address, u256_to_biguint};
+use crate::signer::EthTxSigner;
+use crate::wallet::Wallet;
+use ethers::abi::Token;
+use ethers::contract::{Contract, Multicall, MulticallError};
+use ethers::providers::Middleware;
+use ethers::types::{Address, Bytes, H160, H256, U256};
+use std::collections::HashMap;
+use zklink_sdk_types::basic_types::BigUint;
+use zklink_sdk_types::prelude::{TokenId, ZkLinkAddress};
+
+/// The address that represents ETH in the zklink contracts.
+pub const ETH_ADDRESS: Address = H160([0xee; 20]);
+
+impl<M: Middleware, S: EthTxSigner> Wallet<M, S> {
+    /// Get the balances of `owner` for the `tokens`, ETH is included if its address is
+    /// [`ETH_ADDRESS`]. The tokens failed to query are omitted in the result. The `tokens` of
+    /// the zklink gateway of this chain could be got by `EthPropertyResp::gateway_tokens` of the
+    /// provider. The 32 bytes (non-evm) addresses are rejected.
+    pub async fn get_erc20_balances(
+        &self,
+        owner: ZkLinkAddress,
+        tokens: &[(TokenId, ZkLinkAddress)],
+    ) -> Result<HashMap<TokenId, BigUint>, WalletError> {
+        if tokens.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let owner = evm_address(&owner)?;
+        let token_addrs = tokens
+            .iter()
+            .map(|(_, token_addr)| evm_address(token_addr))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut multicall = self.multicall().await?;
+        for token_addr in token_addrs {
+            if token_addr == ETH_ADDRESS {
+                multicall.add_get_eth_balance(owner, true);
+            } else {
+                let call = self
+                    .erc20_contract(token_addr)
+                    .method::<_, U256>("balanceOf", owner)?;
+                multicall.add_call(call, true);
+            }
+        }
+        let token_ids = tokens.iter().map(|(token_id, _)| *token_id);
+        self.call_uints(multicall, token_ids).await
+    }
+
+    /// Get the allowances of `spender` (e.g. the zklink contract) to spend the `tokens` of
+    /// `owner`. ETH is skipped and the tokens failed to query are omitted in the result.
+    pub async fn get_erc20_allowances(
+        &self,
+        owner: ZkLinkAddress,
+        spender: ZkLinkAddress,
+        tokens: &[(TokenId, ZkLinkAddress)],
+    ) -> Result<HashMap<TokenId, BigUint>, WalletError> {
+        let owner = evm_address(&owner)?;
+        let spender = evm_address(&spender)?;
+        let tokens = tokens
+            .iter()
+            .map(|(token_id, token_addr)| Ok((*token_id, evm_address(token_addr)?)))
+            .collect::<Result<Vec<_>, WalletError>>()?;
+        let mut multicall = self.multicall().await?;
+        let mut token_ids = Vec::with_capacity(tokens.len());
+        for (token_id, token_addr) in tokens {
+            if token_addr == ETH_ADDRESS {
+                continue;
+            }
+            let call = self
+                .erc20_contract(token_addr)
+                .method::<_, U256>("allowance", (owner, spender))?;
+            multicall.add_call(call, true);
+            token_ids.push(token_id);
+        }
+        if token_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        self.call_uints(multicall, token_ids).await
+    }
+
+    /// Get the pending balances of all the `accounts` for the `token_ids` in the zklink contract
+    /// at `zklink_addr`.
+    pub async fn get_pending_balances(
+        &self,
+        zklink_addr: ZkLinkAddress,
+        accounts: &[ZkLinkAddress],
+        token_ids: &[TokenId],
+    ) -> Result<HashMap<ZkLinkAddress, HashMap<TokenId, BigUint>>, WalletError> {
+        if accounts.is_empty() || token_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let zklink = Contract::new(
+            evm_address(&zklink_addr)?,
+            self.get_l1_contract(false),
+            self.provider.clone(),
+        );
+        let mut multicall = self.multicall().await?;
+        for account in accounts {
+            let account_bytes = H256::from(account.to_fixed_bytes());
+            for token_id in token_ids {
+                let call = zklink
+                    .method::<_, U256>("getPendingBalance", (account_bytes, **token_id as u16))?;
+                multicall.add_call(call, true);
+            }
+        }
+        let keys = accounts
+            .iter()
+            .flat_map(|account| token_ids.iter().map(move |token_id| (account, *token_id)));
+        let results = self.call_raw(multicall).await?;
+        let mut balances: HashMap<ZkLinkAddress, HashMap<TokenId, BigUint>> = HashMap::new();
+        for ((account, token_id), result) in keys.zip(results) {
+            if let Ok(Token::Uint(balance)) = result {
+                balances
+                    .entry(account.clone())
+                    .or_default()
+                    .insert(token_id, u256_to_biguint(balance));
+            }
+        }
+        Ok(balances)
+    }
+
+    async fn multicall(&self) -> Result<Multicall<M>, WalletError> {
+        Multicall::new(self.provider.clone(), self.multicall_address)
+            .await
+            .map_err(multicall_error)
+    }
+
+    fn erc20_contract(&self, token_addr: Address) -> Contract<M> {
+        let abi = self.contracts.get("erc20").unwrap().clone();
+        Contract::new(token_addr, abi, self.provider.clone())
+    }
+
+    async fn call_raw(
+        &self,
+        multicall: Multicall<M>,
+    ) -> Result<Vec<Result<Token, Bytes>>, WalletError> {
+        multicall.call_raw().await.map_err(multicall_error)
+    }
+
+    async fn call_uints(
+        &self,
+        multicall: Multicall<M>,
+        keys: impl IntoIterator<Item = TokenId>,
+    ) -> Result<HashMap<TokenId, BigUint>, WalletError> {
+        let results = self.call_raw(multicall).await?;
+        let values = keys
+            .into_iter()
+            .zip(results)
+            .filter_map(|(token_id, result)| match result {
+                Ok(Token::Uint(value)) => Some((token_id, u256_to_biguint(value))),
+                _ => None,
+            })
+            .collect();
+        Ok(values)
+    }
+}
+
+fn multicall_error<M: Middleware>(error: MulticallError<M>) -> WalletError {
+    WalletError::MulticallError(error.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ethers::abi::encode;
+    use ethers::providers::{MockProvider, Provider};
+    use std::str::FromStr;
+    use zklink_sdk_signers::eth_signer::EthSigner;
+
+    const PRIVATE_KEY: &str = "be725250b123a39dab5b7579334d5888987c72a58f4508062545fe6e08ca94f4";
+
+    #[tokio::test]
+    async fn test_get_erc20_balances() {
+        let (provider, mock) = Provider::mocked();
+        let signer = EthSigner::try_from(PRIVATE_KEY).unwrap();
+        let wallet: Wallet<Provider<MockProvider>, _> =
+            Wallet::new_with_middleware(provider, signer)
+                .with_multicall_address(Address::repeat_byte(0xca));
+
+        // the results of aggregate3: (bool success, bytes returnData)[]
+        let result = |success: bool, value: u64| {
+            let return_data = if success {
+                encode(&[Token::Uint(value.into())])
+            } else {
+                vec![]
+            };
+            Token::Tuple(vec![Token::Bool(success), Token::Bytes(return_data)])
+        };
+        let results = encode(&[Token::Array(vec![
+            result(true, 100),
+            result(true, 200),
+            result(false, 0),
+        ])]);
+        mock.push::<Bytes, _>(results.into()).unwrap();
+
+        let owner = ZkLinkAddress::from_str("0xAFAFf3aD1a0425D792432D9eCD1c3e26Ef2C42E9").unwrap();
+        let tokens = [
+            (TokenId(1), ZkLinkAddress::from(ETH_ADDRESS)),
+            (TokenId(17), ZkLinkAddress::from(Address::repeat_byte(0x11))),
+            (TokenId(18), ZkLinkAddress::from(Address::repeat_byte(0x12))),
+        ];
+        let balances = wallet.get_erc20_balances(owner, &tokens).await.unwrap();
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[&TokenId(1)], BigUint::from(100u32));
+        assert_eq!(balances[&TokenId(17)], BigUint::from(200u32));
+        assert!(!balances.contains_key(&TokenId(18)));
+    }
+
+    #[tokio::test]
+    async fn test_get_erc20_balances_non_evm_owner() {
+        let (provider, _mock) = Provider::mocked();
+        let signer = EthSigner::try_from(PRIVATE_KEY).unwrap();
+        let wallet: Wallet<Provider<MockProvider>, _> =
+            Wallet::new_with_middleware(provider, signer)
+                .with_multicall_address(Address::repeat_byte(0xca));
+
+        let owner = ZkLinkAddress::from_str(
+            "0x04b2f7ee4ba1a2a8e2b3f4b7b9bd4d5f7f1b2d6e6e0d4c2c3b5e1d8f9a0b1c2d",
+        )
+        .unwrap();
+        let tokens = [(TokenId(1), ZkLinkAddress::from(ETH_ADDRESS))];
+        let result = wallet.get_erc20_balances(owner.clone(), &tokens).await;
+        assert!(matches!(result, Err(WalletError::InvalidInputParameter)));
+
+        // the non-evm tokens and zklink contract
+        let evm_owner =
+            ZkLinkAddress::from_str("0xAFAFf3aD1a0425D792432D9eCD1c3e26Ef2C42E9").unwrap();
+        let tokens = [(TokenId(17), owner.clone())];
+        let result = wallet.get_erc20_balances(evm_owner.clone(), &tokens).await;
+        assert!(matches!(result, Err(WalletError::InvalidInputParameter)));
+        let result = wallet
+            .get_erc20_allowances(evm_owner.clone(), evm_owner.clone(), &tokens)
+            .await;
+        assert!(matches!(result, Err(WalletError::InvalidInputParameter)));
+        let result = wallet
+            .get_pending_balances(owner, &[evm_owner], &[TokenId(17)])
+            .await;
+        assert!(matches!(result, Err(WalletError::InvalidInputParameter)));
+    }
+}
@@ -0,0 +1,50 @@
+use crate::error::WalletError;
+use async_trait::async_trait;
+use ethers::signers::Signer;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address, Signature};
+use zklink_sdk_signers::eth_signer::{EthSigner, EthSignerError};
+
+/// The signer of the layer1 transactions sent by `Wallet`, e.g. the local `EthSigner`, or any
+/// ethers `Signer` like a hardware wallet or a KMS wrapped in `EthersSigner`.
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+pub trait EthTxSigner: Send + Sync {
+    /// The address that sends the transactions.
+    fn address(&self) -> Address;
+
+    /// Sign the transaction, the chain id of the transaction is always set.
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, WalletError>;
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl EthTxSigner for EthSigner {
+    fn address(&self) -> Address {
+        self.get_address()
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, WalletError> {
+        let signature = EthSigner::sign_transaction(self, tx)?;
+        Ok(signature.0)
+    }
+}
+
+/// Adapt the ethers `Signer` to `EthTxSigner`.
+#[derive(Debug, Clone)]
+pub struct EthersSigner<S>(pub S);
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<S: Signer> EthTxSigner for EthersSigner<S> {
+    fn address(&self) -> Address {
+        self.0.address()
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, WalletError> {
+        self.0
+            .sign_transaction(tx)
+            .await
+            .map_err(|e| EthSignerError::SigningFailed(e.to_string()).into())
+    }
+}
@@ -1,11 +1,16 @@
 use crate::abi::load_contracts;
 use crate::error::WalletError;
-use crate::eth::{encode_tx, new_call_typed_tx, new_typed_tx, EthTxOption, EthTxParam};
+use crate::eth::{
+    biguint_to_u256, encode_tx, evm_address, new_call_typed_tx, new_typed_tx, u256_to_biguint,
+    EthTxOption, EthTxParam, ExodusProof, FeeStrategy,
+};
+use crate::events::{PriorityOp, ZkLinkEventLog, ZKLINK_EVENTS};
+use crate::signer::EthTxSigner;
 use bigdecimal::num_bigint::BigUint;
 use ethers::abi::{Address, Contract, Detokenize, Token, Tokenize, Uint};
 use ethers::contract::encode_function_data;
 use ethers::providers::{Http, Middleware, Provider};
-use ethers::types::BlockNumber;
+use ethers::types::{BlockNumber, Filter, Transaction, TransactionReceipt};
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::str::FromStr;
@@ -16,31 +21,76 @@ use zklink_sdk_signers::eth_signer::EthSigner;
 use zklink_sdk_types::basic_types::ZkLinkAddress;
 use zklink_sdk_types::prelude::{PubKeyHash, H256, U256};
 
-pub struct Wallet {
+/// The max block range of one `eth_getLogs` request, most of the rpc providers limit it.
+const GET_LOGS_BLOCK_RANGE: u64 = 2000;
+/// The percent to bump the fees of the replacement tx, most of the nodes require at least 10%.
+const REPLACEMENT_FEE_BUMP_PERCENT: u64 = 10;
+/// The gas limit of the ETH transfer.
+const TRANSFER_GAS_LIMIT: u64 = 21000;
+
+/// The layer1 wallet to interact with the zklink contracts, `M` can be any ethers `Middleware`
+/// stack, e.g. the WS/IPC providers, the gas escalator or the nonce manager.
+pub struct Wallet<M = Provider<Http>, S = EthSigner> {
     pub contracts: HashMap<String, Contract>,
-    pub signer: EthSigner,
-    pub provider: Arc<Provider<Http>>,
+    pub signer: S,
+    pub provider: Arc<M>,
+    pub fee_strategy: FeeStrategy,
+    /// The Multicall3 contract to batch the reads, the canonical deployment is used if `None`.
+    pub multicall_address: Option<Address>,
 }
 
 #[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WaitForTxStatus {
     Success,
     Failed,
     Pending,
 }
 
+#[derive(Debug, Clone)]
+pub struct WaitForTxResult {
+    pub status: WaitForTxStatus,
+    /// The receipt of the tx, `None` if the tx is still pending.
+    pub receipt: Option<TransactionReceipt>,
+}
+
+impl WaitForTxResult {
+    pub fn gas_used(&self) -> Option<U256> {
+        self.receipt.as_ref().and_then(|receipt| receipt.gas_used)
+    }
+}
+
 impl Wallet {
-    pub fn new(url: &str, private_key: &str) -> Self {
-        let signer = EthSigner::try_from(private_key).unwrap();
-        let provider = Arc::new(Provider::<Http>::try_from(url).unwrap());
+    /// Create the wallet with the http provider of `url` and the local `EthSigner`.
+    pub fn new(url: &str, private_key: &str) -> Result<Self, WalletError> {
+        let signer = EthSigner::try_from(private_key)?;
+        let provider = Provider::<Http>::try_from(url).map_err(|_e| WalletError::InvalidNetwork)?;
+        Ok(Self::new_with_middleware(provider, signer))
+    }
+}
+
+impl<M: Middleware, S: EthTxSigner> Wallet<M, S> {
+    pub fn new_with_middleware(provider: impl Into<Arc<M>>, signer: S) -> Self {
         let contracts = load_contracts();
         Self {
             signer,
-            provider,
+            provider: provider.into(),
             contracts,
+            fee_strategy: FeeStrategy::default(),
+            multicall_address: None,
         }
     }
 
+    pub fn with_fee_strategy(mut self, fee_strategy: FeeStrategy) -> Self {
+        self.fee_strategy = fee_strategy;
+        self
+    }
+
+    pub fn with_multicall_address(mut self, multicall_address: Address) -> Self {
+        self.multicall_address = Some(multicall_address);
+        self
+    }
+
     pub fn get_l1_contract(&self, is_gateway: bool) -> Contract {
         let contract_name = if is_gateway { "l1_gateway" } else { "zklink" };
         self.contracts
@@ -50,40 +100,54 @@ impl Wallet {
     }
 
     pub async fn get_balance(&self) -> Result<U256, WalletError> {
-        let from = self.signer.get_address();
-        Ok(self.provider.get_balance(from, None).await?)
+        let from = self.signer.address();
+        self.provider
+            .get_balance(from, None)
+            .await
+            .map_err(WalletError::middleware)
     }
 
     pub async fn get_nonce(&self, block_number: String) -> Result<U256, WalletError> {
         let block_number = BlockNumber::from_str(&block_number)
             .map_err(|_e| WalletError::InvalidInputParameter)?;
-        let from = self.signer.get_address();
-        Ok(self
-            .provider
+        let from = self.signer.address();
+        self.provider
             .get_transaction_count(from, Some(block_number.into()))
-            .await?)
+            .await
+            .map_err(WalletError::middleware)
     }
 
     pub async fn wait_for_transaction(
         &self,
         tx_hash: H256,
         retries: Option<u32>,
-    ) -> Result<WaitForTxStatus, WalletError> {
+    ) -> Result<WaitForTxResult, WalletError> {
         let mut retries = retries.unwrap_or(60);
         loop {
-            let receipt = self.provider.get_transaction_receipt(tx_hash).await?;
+            let receipt = self
+                .provider
+                .get_transaction_receipt(tx_hash)
+                .await
+                .map_err(WalletError::middleware)?;
             if let Some(receipt) = receipt {
                 if let Some(status) = receipt.status {
-                    if status.as_u64() == 1 {
-                        return Ok(WaitForTxStatus::Success);
+                    let status = if status.as_u64() == 1 {
+                        WaitForTxStatus::Success
                     } else {
-                        return Ok(WaitForTxStatus::Failed);
-                    }
+                        WaitForTxStatus::Failed
+                    };
+                    return Ok(WaitForTxResult {
+                        status,
+                        receipt: Some(receipt),
+                    });
                 }
             }
             retries -= 1;
             if retries == 0 {
-                return Ok(WaitForTxStatus::Pending);
+                return Ok(WaitForTxResult {
+                    status: WaitForTxStatus::Pending,
+                    receipt: None,
+                });
             }
             async_std::task::sleep(Duration::from_secs(1)).await
         }
@@ -105,47 +169,187 @@ impl Wallet {
             data: Some(encoded_data.to_vec()),
             ..eth_params.clone()
         };
-        let chain_id = self.provider.get_chainid().await?;
+        let chain_id = self
+            .provider
+            .get_chainid()
+            .await
+            .map_err(WalletError::middleware)?;
         let typed_tx = new_call_typed_tx(params, chain_id.as_u64());
-        let data: Vec<u8> = (*(self.provider).call(&typed_tx, None).await?).to_vec();
+        let data: Vec<u8> = (*(self.provider)
+            .call(&typed_tx, None)
+            .await
+            .map_err(WalletError::middleware)?)
+        .to_vec();
         let tokens = function
             .decode_output(&data)
             .map_err(WalletError::EthAbiError)?;
         Ok(tokens)
     }
 
+    /// Estimate the max fee and the max priority fee by the `fee_strategy`.
+    pub async fn estimate_fees(&self) -> Result<(U256, U256), WalletError> {
+        match self.fee_strategy {
+            FeeStrategy::Legacy => {
+                let gas_price = self
+                    .provider
+                    .get_gas_price()
+                    .await
+                    .map_err(WalletError::middleware)?;
+                Ok((gas_price, gas_price))
+            }
+            FeeStrategy::Eip1559 => self
+                .provider
+                .estimate_eip1559_fees(None)
+                .await
+                .map_err(WalletError::middleware),
+            FeeStrategy::Fixed {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } => Ok((max_fee_per_gas, max_priority_fee_per_gas)),
+        }
+    }
+
+    /// Fill the fees that are not set in `params` by the `fee_strategy`.
+    async fn fill_fees(&self, mut params: EthTxParam) -> Result<EthTxParam, WalletError> {
+        if params.is_support_eip1559 {
+            if params.gas_price.is_none()
+                && (params.max_fee_per_gas.is_none() || params.max_priority_fee_per_gas.is_none())
+            {
+                let (max_fee_per_gas, max_priority_fee_per_gas) = self.estimate_fees().await?;
+                let max_fee_per_gas = *params.max_fee_per_gas.get_or_insert(max_fee_per_gas);
+                let max_priority_fee_per_gas = params
+                    .max_priority_fee_per_gas
+                    .get_or_insert(max_priority_fee_per_gas);
+                // the priority fee can't exceed the max fee
+                *max_priority_fee_per_gas = (*max_priority_fee_per_gas).min(max_fee_per_gas);
+            }
+        } else if params.gas_price.is_none() {
+            let gas_price = match params.max_fee_per_gas {
+                Some(max_fee_per_gas) => max_fee_per_gas,
+                None => self.estimate_fees().await?.0,
+            };
+            params.gas_price = Some(gas_price);
+        }
+        Ok(params)
+    }
+
     pub async fn sign_and_send_raw_tx(&self, params: EthTxParam) -> Result<H256, WalletError> {
-        let from = self.signer.get_address();
-        let gas_price = if let Some(gas_price) = params.gas_price {
-            gas_price
-        } else {
-            self.provider.get_gas_price().await?
-        };
+        let from = self.signer.address();
+        let params = self.fill_fees(params).await?;
         let nonce = if let Some(nonce) = params.nonce {
             nonce
         } else {
             self.provider
                 .get_transaction_count(from, Some(ethers::types::BlockNumber::Pending.into()))
-                .await?
+                .await
+                .map_err(WalletError::middleware)?
         };
 
-        let chain_id = self.provider.get_chainid().await?;
+        let chain_id = self
+            .provider
+            .get_chainid()
+            .await
+            .map_err(WalletError::middleware)?;
         let tx_params = EthTxParam {
             nonce: Some(nonce),
-            gas_price: Some(gas_price),
-            ..params.clone()
+            ..params
         };
         let mut typed_tx = new_typed_tx(from, tx_params, chain_id.as_u64());
         if typed_tx.gas().is_none() {
-            let gas_limit = self.provider.estimate_gas(&typed_tx, None).await?;
+            let gas_limit = self
+                .provider
+                .estimate_gas(&typed_tx, None)
+                .await
+                .map_err(WalletError::middleware)?;
             typed_tx.set_gas(gas_limit);
         }
-        let signature = self.signer.sign_transaction(&typed_tx)?;
-        let raw_tx = typed_tx.rlp_signed(&signature.0).to_vec();
-        let pending_tx = *(self.provider).send_raw_transaction(raw_tx.into()).await?;
+        let signature = self.signer.sign_transaction(&typed_tx).await?;
+        let raw_tx = typed_tx.rlp_signed(&signature).to_vec();
+        let pending_tx = *(self.provider)
+            .send_raw_transaction(raw_tx.into())
+            .await
+            .map_err(WalletError::middleware)?;
         Ok(pending_tx)
     }
 
+    /// Replace the pending tx of `tx_hash` with the same tx but the higher fees.
+    pub async fn speed_up_transaction(&self, tx_hash: H256) -> Result<H256, WalletError> {
+        let tx = self.get_pending_transaction(tx_hash).await?;
+        let to = tx.to.ok_or_else(|| {
+            WalletError::ReplaceTxError("can't replace the contract creation tx".to_string())
+        })?;
+        let params = EthTxParam {
+            to,
+            data: Some(tx.input.to_vec()),
+            value: Some(tx.value),
+            gas: Some(tx.gas),
+            ..self.replacement_params(&tx).await?
+        };
+        self.sign_and_send_raw_tx(params).await
+    }
+
+    /// Cancel the pending tx of `tx_hash` by replacing it with an empty ETH transfer to self
+    /// with the higher fees.
+    pub async fn cancel_transaction(&self, tx_hash: H256) -> Result<H256, WalletError> {
+        let tx = self.get_pending_transaction(tx_hash).await?;
+        let params = EthTxParam {
+            to: self.signer.address(),
+            value: Some(U256::zero()),
+            gas: Some(U256::from(TRANSFER_GAS_LIMIT)),
+            ..self.replacement_params(&tx).await?
+        };
+        self.sign_and_send_raw_tx(params).await
+    }
+
+    async fn get_pending_transaction(&self, tx_hash: H256) -> Result<Transaction, WalletError> {
+        let tx = self
+            .provider
+            .get_transaction(tx_hash)
+            .await
+            .map_err(WalletError::middleware)?
+            .ok_or_else(|| WalletError::ReplaceTxError(format!("tx {tx_hash:?} not found")))?;
+        if tx.block_number.is_some() {
+            return Err(WalletError::ReplaceTxError(format!(
+                "tx {tx_hash:?} is already mined"
+            )));
+        }
+        if tx.from != self.signer.address() {
+            return Err(WalletError::ReplaceTxError(format!(
+                "tx {tx_hash:?} is not sent by the signer"
+            )));
+        }
+        Ok(tx)
+    }
+
+    /// The nonce and the fees of the replacement tx, the fees are bumped from the replaced tx
+    /// or the current estimation if it's higher.
+    async fn replacement_params(&self, tx: &Transaction) -> Result<EthTxParam, WalletError> {
+        let (max_fee_per_gas, max_priority_fee_per_gas) = self.estimate_fees().await?;
+        let is_support_eip1559 = tx.transaction_type == Some(2u64.into());
+        let params = if is_support_eip1559 {
+            EthTxParam {
+                is_support_eip1559,
+                nonce: Some(tx.nonce),
+                max_fee_per_gas: Some(
+                    bump_fee(tx.max_fee_per_gas.unwrap_or_default()).max(max_fee_per_gas),
+                ),
+                max_priority_fee_per_gas: Some(
+                    bump_fee(tx.max_priority_fee_per_gas.unwrap_or_default())
+                        .max(max_priority_fee_per_gas),
+                ),
+                ..Default::default()
+            }
+        } else {
+            EthTxParam {
+                is_support_eip1559,
+                nonce: Some(tx.nonce),
+                gas_price: Some(bump_fee(tx.gas_price.unwrap_or_default()).max(max_fee_per_gas)),
+                ..Default::default()
+            }
+        };
+        Ok(params)
+    }
+
     pub async fn inner_approve_erc20(
         &self,
         zklink_addr: Address,
@@ -384,4 +588,437 @@ impl Wallet {
     pub async fn get_fee(&self, eth_params: EthTxOption) -> Result<BigUint, WalletError> {
         self.inner_get_fee(eth_params.into()).await
     }
+
+    pub async fn inner_get_pending_balance(
+        &self,
+        address: ZkLinkAddress,
+        token_id: u16,
+        eth_params: EthTxParam,
+    ) -> Result<BigUint, WalletError> {
+        let address = H256::from(address.to_fixed_bytes());
+        let tokens = self
+            .tx_call(eth_params, false, "getPendingBalance", (address, token_id))
+            .await?;
+        let balance = Uint::from_tokens(tokens).map_err(|e| WalletError::GetErrorResult(e.0))?;
+        Ok(u256_to_biguint(balance))
+    }
+
+    /// Get the balance of `address` that is failed to withdraw and kept in the zklink contract.
+    pub async fn get_pending_balance(
+        &self,
+        address: ZkLinkAddress,
+        token_id: u16,
+        eth_params: EthTxOption,
+    ) -> Result<BigUint, WalletError> {
+        self.inner_get_pending_balance(address, token_id, eth_params.into())
+            .await
+    }
+
+    pub async fn inner_withdraw_pending_balance(
+        &self,
+        owner: Address,
+        token_id: u16,
+        amount: BigUint,
+        eth_params: EthTxParam,
+    ) -> Result<H256, WalletError> {
+        let params = vec![
+            Token::Address(owner),
+            Token::Uint(ethers::types::U256::from(token_id)),
+            Token::Uint(biguint_to_u256(&amount)?),
+        ];
+        let contract = self.get_l1_contract(false);
+        let tx_data = encode_tx(contract.clone(), "withdrawPendingBalance", params)?;
+        let tx_params = EthTxParam {
+            data: Some(tx_data),
+            ..eth_params.clone()
+        };
+        let tx_hash = self.sign_and_send_raw_tx(tx_params).await?;
+        Ok(tx_hash)
+    }
+
+    /// Withdraw the pending balance of `owner` to `owner`, anyone can send the tx. The `owner`
+    /// must be a 20 bytes evm address.
+    pub async fn withdraw_pending_balance(
+        &self,
+        owner: ZkLinkAddress,
+        token_id: u16,
+        amount: BigUint,
+        eth_params: EthTxOption,
+    ) -> Result<H256, WalletError> {
+        let owner = evm_address(&owner)?;
+        self.inner_withdraw_pending_balance(owner, token_id, amount, eth_params.into())
+            .await
+    }
+
+    pub async fn inner_is_exodus_mode(&self, eth_params: EthTxParam) -> Result<bool, WalletError> {
+        let tokens = self.tx_call(eth_params, false, "exodusMode", ()).await?;
+        bool::from_tokens(tokens).map_err(|e| WalletError::GetErrorResult(e.0))
+    }
+
+    pub async fn is_exodus_mode(&self, eth_params: EthTxOption) -> Result<bool, WalletError> {
+        self.inner_is_exodus_mode(eth_params.into()).await
+    }
+
+    pub async fn inner_activate_exodus_mode(
+        &self,
+        eth_params: EthTxParam,
+    ) -> Result<H256, WalletError> {
+        let contract = self.get_l1_contract(false);
+        let tx_data = encode_tx(contract.clone(), "activateExodusMode", vec![])?;
+        let tx_params = EthTxParam {
+            data: Some(tx_data),
+            ..eth_params.clone()
+        };
+        let tx_hash = self.sign_and_send_raw_tx(tx_params).await?;
+        Ok(tx_hash)
+    }
+
+    /// Activate the exodus mode if the priority requests are not processed in time, the tx is
+    /// reverted if the condition is not met.
+    pub async fn activate_exodus_mode(&self, eth_params: EthTxOption) -> Result<H256, WalletError> {
+        self.inner_activate_exodus_mode(eth_params.into()).await
+    }
+
+    pub async fn inner_cancel_outstanding_deposits_for_exodus_mode(
+        &self,
+        n: u64,
+        deposits_pubdata: Vec<Vec<u8>>,
+        eth_params: EthTxParam,
+    ) -> Result<H256, WalletError> {
+        let params = vec![
+            Token::Uint(ethers::types::U256::from(n)),
+            Token::Array(deposits_pubdata.into_iter().map(Token::Bytes).collect()),
+        ];
+        let contract = self.get_l1_contract(false);
+        let tx_data = encode_tx(
+            contract.clone(),
+            "cancelOutstandingDepositsForExodusMode",
+            params,
+        )?;
+        let tx_params = EthTxParam {
+            data: Some(tx_data),
+            ..eth_params.clone()
+        };
+        let tx_hash = self.sign_and_send_raw_tx(tx_params).await?;
+        Ok(tx_hash)
+    }
+
+    /// Cancel the first `n` outstanding priority requests in exodus mode, the deposited amounts
+    /// become the pending balances of the depositors. `deposits_pubdata` is the pubdata of the
+    /// deposits in them, which can be taken from the `NewPriorityRequest` events.
+    pub async fn cancel_outstanding_deposits_for_exodus_mode(
+        &self,
+        n: u64,
+        deposits_pubdata: Vec<Vec<u8>>,
+        eth_params: EthTxOption,
+    ) -> Result<H256, WalletError> {
+        self.inner_cancel_outstanding_deposits_for_exodus_mode(
+            n,
+            deposits_pubdata,
+            eth_params.into(),
+        )
+        .await
+    }
+
+    pub async fn inner_perform_exodus(
+        &self,
+        exodus_proof: ExodusProof,
+        eth_params: EthTxParam,
+    ) -> Result<H256, WalletError> {
+        let params = exodus_proof.to_tokens()?;
+        let contract = self.get_l1_contract(false);
+        let tx_data = encode_tx(contract.clone(), "performExodus", params)?;
+        let tx_params = EthTxParam {
+            data: Some(tx_data),
+            ..eth_params.clone()
+        };
+        let tx_hash = self.sign_and_send_raw_tx(tx_params).await?;
+        Ok(tx_hash)
+    }
+
+    /// Withdraw the balance of the account in exodus mode with the proof against the last
+    /// executed block, the amount becomes the pending balance of the owner.
+    pub async fn perform_exodus(
+        &self,
+        exodus_proof: ExodusProof,
+        eth_params: EthTxOption,
+    ) -> Result<H256, WalletError> {
+        self.inner_perform_exodus(exodus_proof, eth_params.into())
+            .await
+    }
+
+    pub async fn inner_performed_exodus(
+        &self,
+        account_id: u32,
+        sub_account_id: u8,
+        withdraw_token_id: u16,
+        deduct_token_id: u16,
+        eth_params: EthTxParam,
+    ) -> Result<bool, WalletError> {
+        let args = (
+            account_id,
+            sub_account_id,
+            withdraw_token_id,
+            deduct_token_id,
+        );
+        let tokens = self
+            .tx_call(eth_params, false, "performedExodus", args)
+            .await?;
+        bool::from_tokens(tokens).map_err(|e| WalletError::GetErrorResult(e.0))
+    }
+
+    /// Check if the exodus of the account balance has been performed.
+    pub async fn performed_exodus(
+        &self,
+        account_id: u32,
+        sub_account_id: u8,
+        withdraw_token_id: u16,
+        deduct_token_id: u16,
+        eth_params: EthTxOption,
+    ) -> Result<bool, WalletError> {
+        self.inner_performed_exodus(
+            account_id,
+            sub_account_id,
+            withdraw_token_id,
+            deduct_token_id,
+            eth_params.into(),
+        )
+        .await
+    }
+
+    /// Scan the events of the zklink contract at `zklink_addr` in the blocks `[from_block, to_block]`,
+    /// `zklink_addr` must be a 20 bytes evm address.
+    pub async fn get_zklink_events(
+        &self,
+        zklink_addr: ZkLinkAddress,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<ZkLinkEventLog>, WalletError> {
+        let address = evm_address(&zklink_addr)?;
+        let contract = self.get_l1_contract(false);
+        let topics = ZKLINK_EVENTS
+            .iter()
+            .map(|name| contract.event(name).map(|event| event.signature()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut events = Vec::new();
+        let mut start = from_block;
+        while start <= to_block {
+            let end = to_block.min(start + GET_LOGS_BLOCK_RANGE - 1);
+            let filter = Filter::new()
+                .address(address)
+                .from_block(start)
+                .to_block(end)
+                .topic0(topics.clone());
+            for log in self
+                .provider
+                .get_logs(&filter)
+                .await
+                .map_err(WalletError::middleware)?
+            {
+                if let Some(event) = ZkLinkEventLog::decode(&contract, &log)? {
+                    events.push(event);
+                }
+            }
+            start = end + 1;
+        }
+        Ok(events)
+    }
+
+    /// Scan the deposits and full exits requested in the blocks `[from_block, to_block]`.
+    pub async fn get_priority_ops(
+        &self,
+        zklink_addr: ZkLinkAddress,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<PriorityOp>, WalletError> {
+        let events = self
+            .get_zklink_events(zklink_addr, from_block, to_block)
+            .await?;
+        events
+            .iter()
+            .filter_map(|event| event.priority_op())
+            .collect()
+    }
+}
+
+fn bump_fee(fee: U256) -> U256 {
+    fee * (100 + REPLACEMENT_FEE_BUMP_PERCENT) / 100 + 1
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::signer::EthersSigner;
+    use ethers::providers::MockProvider;
+    use ethers::signers::LocalWallet;
+
+    const PRIVATE_KEY: &str = "be725250b123a39dab5b7579334d5888987c72a58f4508062545fe6e08ca94f4";
+
+    #[test]
+    fn test_new_wallet() {
+        assert!(Wallet::new("http://127.0.0.1:8545", PRIVATE_KEY).is_ok());
+        assert!(matches!(
+            Wallet::new("127.0.0.1:8545", PRIVATE_KEY),
+            Err(WalletError::InvalidNetwork)
+        ));
+        assert!(matches!(
+            Wallet::new("http://127.0.0.1:8545", "0x1234"),
+            Err(WalletError::EthSignerError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_mocked_wallet() {
+        let (provider, mock) = Provider::mocked();
+        let local_wallet = LocalWallet::from_str(PRIVATE_KEY).unwrap();
+        let wallet: Wallet<Provider<MockProvider>, _> =
+            Wallet::new_with_middleware(provider, EthersSigner(local_wallet));
+        assert_eq!(
+            wallet.signer.address(),
+            EthSigner::try_from(PRIVATE_KEY).unwrap().get_address()
+        );
+
+        mock.push::<U256, _>(U256::from(100)).unwrap();
+        assert_eq!(wallet.get_balance().await.unwrap(), U256::from(100));
+
+        // the responses are popped in the reverse order: gas price, nonce, chain id, gas, tx hash
+        let tx_hash = H256::repeat_byte(1);
+        mock.push::<H256, _>(tx_hash).unwrap();
+        mock.push::<U256, _>(U256::from(50000)).unwrap();
+        mock.push::<U256, _>(U256::from(1)).unwrap();
+        mock.push::<U256, _>(U256::from(3)).unwrap();
+        mock.push::<U256, _>(U256::from(1_000_000_000)).unwrap();
+        let eth_params = EthTxOption {
+            to: ZkLinkAddress::from_str("0xAFAFf3aD1a0425D792432D9eCD1c3e26Ef2C42E9").unwrap(),
+            ..Default::default()
+        };
+        let deposit_to = ZkLinkAddress::from(wallet.signer.address());
+        let hash = wallet
+            .deposit_eth_to_layer1(1, deposit_to, eth_params)
+            .await
+            .unwrap();
+        assert_eq!(hash, tx_hash);
+    }
+
+    #[tokio::test]
+    async fn test_fill_fees() {
+        let (provider, _mock) = Provider::mocked();
+        let signer = EthSigner::try_from(PRIVATE_KEY).unwrap();
+        let wallet: Wallet<Provider<MockProvider>, _> =
+            Wallet::new_with_middleware(provider, signer).with_fee_strategy(FeeStrategy::Fixed {
+                max_fee_per_gas: U256::from(100),
+                max_priority_fee_per_gas: U256::from(2),
+            });
+
+        let params = wallet.fill_fees(EthTxParam::default()).await.unwrap();
+        assert_eq!(params.gas_price, Some(U256::from(100)));
+
+        let params = EthTxParam {
+            is_support_eip1559: true,
+            ..Default::default()
+        };
+        let params = wallet.fill_fees(params).await.unwrap();
+        assert_eq!(params.max_fee_per_gas, Some(U256::from(100)));
+        assert_eq!(params.max_priority_fee_per_gas, Some(U256::from(2)));
+
+        // the priority fee is capped by the max fee
+        let params = EthTxParam {
+            is_support_eip1559: true,
+            max_fee_per_gas: Some(U256::from(1)),
+            ..Default::default()
+        };
+        let params = wallet.fill_fees(params).await.unwrap();
+        assert_eq!(params.max_fee_per_gas, Some(U256::from(1)));
+        assert_eq!(params.max_priority_fee_per_gas, Some(U256::from(1)));
+
+        assert_eq!(bump_fee(U256::from(100)), U256::from(111));
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_pending_balance_non_evm_owner() {
+        let (provider, _mock) = Provider::mocked();
+        let signer = EthSigner::try_from(PRIVATE_KEY).unwrap();
+        let wallet: Wallet<Provider<MockProvider>, _> =
+            Wallet::new_with_middleware(provider, signer);
+        let owner = ZkLinkAddress::from_hex(
+            "0x04a69b67bcabfa7d3ccb96e1d25c2e6fc93589fe24a6fd04566b8700ff97a71a",
+        )
+        .unwrap();
+        assert!(matches!(
+            wallet
+                .withdraw_pending_balance(owner, 1, BigUint::from(1u32), EthTxOption::default())
+                .await,
+            Err(WalletError::InvalidInputParameter)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_get_zklink_events_non_evm_contract() {
+        let (provider, _mock) = Provider::mocked();
+        let signer = EthSigner::try_from(PRIVATE_KEY).unwrap();
+        let wallet: Wallet<Provider<MockProvider>, _> =
+            Wallet::new_with_middleware(provider, signer);
+        let zklink_addr = ZkLinkAddress::from_hex(
+            "0x04a69b67bcabfa7d3ccb96e1d25c2e6fc93589fe24a6fd04566b8700ff97a71a",
+        )
+        .unwrap();
+        assert!(matches!(
+            wallet.get_zklink_events(zklink_addr, 1, 100).await,
+            Err(WalletError::InvalidInputParameter)
+        ));
+    }
+
+    /// Deposit ETH into the zklink contract deployed on an anvil node and decode the deposit
+    /// from its events. It runs only if `ZKLINK_ANVIL_URL` and `ZKLINK_ANVIL_CONTRACT` (the
+    /// zklink contract accepting ETH) are set, the deposit is paid by the first anvil account
+    /// unless `ZKLINK_ANVIL_PRIVATE_KEY` is set.
+    #[tokio::test]
+    async fn test_anvil_deposit_eth() {
+        let (Ok(url), Ok(contract)) = (
+            std::env::var("ZKLINK_ANVIL_URL"),
+            std::env::var("ZKLINK_ANVIL_CONTRACT"),
+        ) else {
+            return;
+        };
+        let private_key = std::env::var("ZKLINK_ANVIL_PRIVATE_KEY").unwrap_or_else(|_| {
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".to_string()
+        });
+        let wallet = Wallet::new(&url, &private_key).unwrap();
+        let contract = ZkLinkAddress::from_hex(&contract).unwrap();
+        let deposit_to = ZkLinkAddress::from(wallet.signer.get_address());
+        let amount = BigUint::from(10u64.pow(16));
+        let from_block = wallet.provider.get_block_number().await.unwrap().as_u64();
+
+        let eth_params = EthTxOption {
+            to: contract.clone(),
+            value: Some(amount.clone()),
+            ..Default::default()
+        };
+        let tx_hash = wallet
+            .deposit_eth_to_layer1(1, deposit_to.clone(), eth_params)
+            .await
+            .unwrap();
+        let result = wallet
+            .wait_for_transaction(tx_hash, Some(10))
+            .await
+            .unwrap();
+        assert_eq!(result.status, WaitForTxStatus::Success);
+        let to_block = result.receipt.unwrap().block_number.unwrap().as_u64();
+
+        let ops = wallet
+            .get_priority_ops(contract, from_block, to_block)
+            .await
+            .unwrap();
+        let deposit = ops
+            .into_iter()
+            .find_map(|op| match op {
+                PriorityOp::Deposit(deposit) if deposit.l2_hash == tx_hash => Some(deposit),
+                _ => None,
+            })
+            .expect("the deposit event is not found");
+        assert_eq!(deposit.from, deposit_to);
+        assert_eq!(deposit.to, deposit_to);
+        assert_eq!(deposit.sub_account_id.0, 1);
+        assert_eq!(deposit.amount.to_string(), amount.to_string());
+    }
 }